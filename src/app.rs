@@ -1,18 +1,45 @@
-use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque};
 use std::io;
-use std::time::Duration;
+use std::path::PathBuf;
+use std::time::{Duration, Instant, SystemTime};
 
 use anyhow::{Result, anyhow};
-use crossterm::event::{self, Event, KeyCode};
+use crossterm::event::{
+    self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyEvent, KeyModifiers,
+};
+use crossterm::execute;
+use crossterm::terminal::{
+    EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode,
+};
 use ratatui::{Terminal, widgets::ListState};
 use tokio::sync::oneshot;
 
-use crate::cache::{LayoutCacheKey, read_layout_cache, write_layout_cache};
-use crate::config::{AppConfig, BoardConfig, IterationConfig, KeysConfig};
-use crate::models::{DetailField, WorkItem};
-use crate::services::{WorkItemFieldInfo, fetch_work_item_layout, update_work_item_in_ado};
-use crate::ui::{draw_detail_view, draw_list_view, draw_status_screen};
-
+use crate::cache::{
+    DraftKey, EditDraft, IterationIdCacheKey, LayoutCacheKey, WorkItemsCacheKey, read_draft,
+    read_iteration_id_cache, read_layout_cache, read_pinned, read_seen, read_work_items_cache,
+    remove_draft, update_cached_work_item, write_draft, write_iteration_id_cache,
+    write_layout_cache, write_pinned, write_seen, write_work_items_cache,
+};
+use crate::commands::{Command, CommandOutcome, CommandPaletteState};
+use crate::config::{
+    AppConfig, AssigneeDisplay, BoardConfig, DEFAULT_BACKLOG_LEVEL, DetailWrap,
+    ExternalCommandConfig, FieldFormat, IterationConfig, KeysConfig, OnEnter, SortConfig, SortKey,
+    load_startup_config,
+};
+use crate::error::summarize_error;
+use crate::models::{ArtifactKind, BoardTag, DetailField, WorkItem};
+use crate::services::{
+    CURRENT_ITERATION_MACRO, ListedItems, RankPlacement, ResolvedIteration, TeamMemberInfo,
+    WorkItemFieldInfo, assign_work_item, auth_method_label, build_field_metadata_cache,
+    build_update_operations, create_work_item, fetch_authenticated_user, fetch_follow_subscription,
+    fetch_repository_name, fetch_team_iteration_paths, fetch_team_members, fetch_work_item_layout,
+    follow_work_item, get_backlog_ids, get_items, get_iteration_ids, get_list_items, limited,
+    reorder_work_item, resolve_iteration_id, set_max_concurrent_requests, set_partial_loads,
+    set_request_timeout, unfollow_work_item, update_work_item_in_ado,
+};
+use crate::ui::{
+    draw_detail_view, draw_error_screen, draw_list_view, draw_status_message, draw_status_screen,
+};
 
 #[derive(Clone, PartialEq)]
 pub enum RefreshPolicy {
@@ -23,7 +50,12 @@ pub enum RefreshPolicy {
 pub enum LoadingState {
     Loading,
     Loaded,
-    Error(String),
+    Error(ErrorReport),
+}
+
+pub struct ErrorReport {
+    pub summary: String,
+    pub details: String,
 }
 
 #[derive(Clone, Default)]
@@ -72,13 +104,13 @@ impl PickerState {
     }
 
     pub fn toggle_active(&mut self) {
-        if let Some(idx) = self.selected {
-            if let Some(value) = self.options.get(idx).cloned() {
-                if self.active.contains(&value) {
-                    self.active.remove(&value);
-                } else {
-                    self.active.insert(value);
-                }
+        if let Some(idx) = self.selected
+            && let Some(value) = self.options.get(idx).cloned()
+        {
+            if self.active.contains(&value) {
+                self.active.remove(&value);
+            } else {
+                self.active.insert(value);
             }
         }
     }
@@ -102,13 +134,121 @@ impl PickerState {
     }
 }
 
+/// Filter pickers that can be opened over the list.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum ListPicker {
+    Type,
+    Column,
+    Tag,
+}
+
+impl ListPicker {
+    pub const ALL: [ListPicker; 3] = [ListPicker::Type, ListPicker::Column, ListPicker::Tag];
+
+    pub fn title(self) -> &'static str {
+        match self {
+            ListPicker::Type => "Type Filter",
+            ListPicker::Column => "Board Column Filter",
+            ListPicker::Tag => "Tag Filter",
+        }
+    }
+
+    /// Name used in the list title when the filter is active.
+    pub fn label(self) -> &'static str {
+        match self {
+            ListPicker::Type => "Types",
+            ListPicker::Column => "Columns",
+            ListPicker::Tag => "Tags",
+        }
+    }
+}
+
+/// Output of an external command, shown until a key other than scrolling.
+pub struct CommandOutputView {
+    pub title: String,
+    pub text: String,
+    pub scroll: u16,
+}
+
+/// What the popup next to the selected row shows.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum HoverContent {
+    Details,
+    Title,
+}
+
+/// Filters set aside while the list is temporarily shown unfiltered.
+pub struct FilterStash {
+    filter_query: String,
+    assigned_to_me_filter_on: bool,
+    hide_done: bool,
+    active_types: BTreeSet<String>,
+    active_columns: BTreeSet<String>,
+    active_tags: BTreeSet<String>,
+    current_sprint: Option<CurrentSprint>,
+}
+
+/// The team's active iteration a backlog is narrowed down to.
+#[derive(Clone)]
+pub struct CurrentSprint {
+    pub name: String,
+    pub path: String,
+}
+
 pub struct ListViewState {
     pub list_state: ListState,
     pub filter_query: String,
     pub is_filtering: bool,
+    /// Query jumped between with `find_next` / `find_previous`; matching
+    /// rows are highlighted but nothing is hidden.
+    pub find_query: String,
+    pub is_finding: bool,
+    /// While set, `next` / `previous` move the selected item in the backlog
+    /// order instead of the selection.
+    pub is_moving: bool,
+    /// Position of the selected row among the find matches.
+    pub find_match: Option<usize>,
+    /// Height of each list row as last drawn; rows are taller than one line
+    /// when `wrap_titles` is on.
+    pub row_heights: Vec<u16>,
     pub is_list_details_hover_visible: bool,
+    pub hover_content: HoverContent,
     pub assigned_to_me_filter_on: bool,
+    pub hide_done: bool,
     pub type_picker: PickerState,
+    pub column_picker: PickerState,
+    pub tag_picker: PickerState,
+    /// Whether an item needs every selected tag rather than any of them.
+    pub tag_match_all: bool,
+    /// Only items in this iteration are shown, when set.
+    pub current_sprint: Option<CurrentSprint>,
+    pub stashed_filters: Option<FilterStash>,
+    pub group_by_assignee: bool,
+    /// Shows the selected item's details next to the list instead of in the
+    /// hover popup.
+    pub details_pane_pinned: bool,
+    /// Shows item counts by state above the list.
+    pub show_summary: bool,
+    /// Assignees whose group only shows its header; empty for unassigned.
+    pub collapsed_groups: BTreeSet<String>,
+}
+
+/// Item counts shown above the list.
+pub struct ItemSummary {
+    pub total: usize,
+    /// Count per state, by state name.
+    pub by_state: Vec<(String, usize)>,
+    pub assigned_to_me: usize,
+}
+
+/// A line of the list: an item, or the header of a group while grouping.
+pub enum ListRow<'a> {
+    Group {
+        assignee: String,
+        count: usize,
+        collapsed: bool,
+    },
+    Item(&'a WorkItem),
 }
 
 impl ListViewState {
@@ -117,11 +257,71 @@ impl ListViewState {
             list_state,
             filter_query: String::new(),
             is_filtering: false,
+            find_query: String::new(),
+            is_finding: false,
+            is_moving: false,
+            find_match: None,
+            row_heights: Vec::new(),
             is_list_details_hover_visible: false,
+            hover_content: HoverContent::Details,
             assigned_to_me_filter_on: false,
+            hide_done: false,
             type_picker: PickerState::default(),
+            column_picker: PickerState::default(),
+            tag_picker: PickerState::default(),
+            tag_match_all: false,
+            current_sprint: None,
+            stashed_filters: None,
+            group_by_assignee: false,
+            details_pane_pinned: false,
+            show_summary: false,
+            collapsed_groups: BTreeSet::new(),
+        }
+    }
+
+    /// Turns every filter off, including ones set aside by bypassing them.
+    pub fn clear_filters(&mut self) {
+        self.filter_query.clear();
+        self.assigned_to_me_filter_on = false;
+        self.hide_done = false;
+        for which in ListPicker::ALL {
+            self.picker_mut(which).clear_active();
+        }
+        self.current_sprint = None;
+        self.stashed_filters = None;
+    }
+
+    pub fn has_active_filters(&self) -> bool {
+        !self.filter_query.is_empty()
+            || self.assigned_to_me_filter_on
+            || self.hide_done
+            || self.current_sprint.is_some()
+            || ListPicker::ALL
+                .into_iter()
+                .any(|which| !self.picker(which).active.is_empty())
+    }
+
+    pub fn picker(&self, which: ListPicker) -> &PickerState {
+        match which {
+            ListPicker::Type => &self.type_picker,
+            ListPicker::Column => &self.column_picker,
+            ListPicker::Tag => &self.tag_picker,
+        }
+    }
+
+    pub fn picker_mut(&mut self, which: ListPicker) -> &mut PickerState {
+        match which {
+            ListPicker::Type => &mut self.type_picker,
+            ListPicker::Column => &mut self.column_picker,
+            ListPicker::Tag => &mut self.tag_picker,
         }
     }
+
+    pub fn open_picker(&self) -> Option<ListPicker> {
+        ListPicker::ALL
+            .into_iter()
+            .find(|which| self.picker(*which).is_open)
+    }
 }
 
 impl Default for ListViewState {
@@ -135,6 +335,8 @@ pub struct VisibleField {
     pub label: String,
     pub reference: String,
     pub value: String,
+    /// Value as loaded from the item or last saved, to tell edited fields.
+    pub original_value: String,
     pub picker: Option<PickerState>,
 }
 
@@ -158,17 +360,22 @@ impl VisibleField {
         Self {
             label,
             reference,
+            original_value: value.clone(),
             value,
             picker,
         }
     }
 
+    pub fn is_modified(&self) -> bool {
+        self.value != self.original_value
+    }
+
     fn select_value(&mut self, idx: usize) {
-        if let Some(picker) = self.picker.as_mut() {
-            if let Some(choice) = picker.options.get(idx).cloned() {
-                self.value = choice;
-                picker.selected = Some(idx);
-            }
+        if let Some(picker) = self.picker.as_mut()
+            && let Some(choice) = picker.options.get(idx).cloned()
+        {
+            self.value = choice;
+            picker.selected = Some(idx);
         }
     }
 }
@@ -178,6 +385,7 @@ pub struct DetailEditState {
     pub is_editing: bool,
     pub active_field: DetailField,
     pub title: String,
+    pub original_title: String,
     pub visible_fields: Vec<VisibleField>,
 }
 
@@ -187,22 +395,129 @@ impl DetailEditState {
             is_editing: false,
             active_field: DetailField::Title,
             title: item.title.clone(),
+            original_title: item.title.clone(),
             visible_fields: Vec::new(),
         }
     }
+
+    pub fn is_title_modified(&self) -> bool {
+        self.title != self.original_title
+    }
+
+    /// Takes the current values as the saved ones, clearing the markers of
+    /// edited fields.
+    fn mark_saved(&mut self) {
+        self.original_title = self.title.clone();
+        for field in &mut self.visible_fields {
+            field.original_value = field.value.clone();
+        }
+    }
 }
 
+/// Requested item id and what came back for it.
+pub type RefreshOutcome = crate::error::Result<(u32, Vec<WorkItem>)>;
+
 #[derive(Default)]
 pub struct DetailViewState {
     pub edit_state: Option<DetailEditState>,
     pub save_status: SaveStatus,
-    pub save_receiver: Option<oneshot::Receiver<Result<(WorkItem, DetailEditState)>>>,
+    pub save_receiver: Option<oneshot::Receiver<crate::error::Result<(WorkItem, DetailEditState)>>>,
+    pub assign_picker: PickerState,
+    pub assign_receiver: Option<oneshot::Receiver<crate::error::Result<(u32, String)>>>,
+    /// Assignee (display name, email) an item had before being assigned to
+    /// me, put back if the assignment fails.
+    pub assign_previous: Option<(u32, String, String)>,
+    pub follow_receiver: Option<oneshot::Receiver<crate::error::Result<FollowToggle>>>,
+    pub refresh_receiver: Option<oneshot::Receiver<RefreshOutcome>>,
+    /// Draft found when starting to edit, waiting for the user to restore or
+    /// discard it.
+    pub draft_prompt: Option<EditDraft>,
+    pub confirming_clone: bool,
+    pub rank_receiver: Option<oneshot::Receiver<Result<()>>>,
+    /// Item being reordered and its index before the move, to undo the local
+    /// move if ADO rejects it.
+    pub rank_undo: Option<(u32, usize)>,
+    /// Reference names of fields shrunk to a few lines in the detail view.
+    pub collapsed_fields: BTreeSet<String>,
+    pub clone_receiver: Option<oneshot::Receiver<crate::error::Result<(u32, WorkItem)>>>,
+    /// Pretty-printed patch shown instead of saving, with its scroll offset.
+    pub patch_preview: Option<(String, u16)>,
+    /// Shows the description as returned by ADO instead of the cleaned text.
+    pub show_raw_description: bool,
+    /// Columns the fields are scrolled by when `detail_wrap = "scroll"`.
+    pub horizontal_scroll: u16,
+    /// What the save in flight does to the detail view once it succeeds.
+    pub after_save: AfterSave,
 }
 
 #[derive(Clone)]
 pub enum SourceKind {
-    Backlog,
+    /// Backlog of the given level (backlog category reference name).
+    Backlog(String),
     Iteration(IterationConfig),
+    /// Items assigned to `me` merged from the contained board sources.
+    MyWork(Vec<SourceEntry>),
+}
+
+/// Follow state of a work item after following or unfollowing it.
+pub struct FollowToggle {
+    pub organization: String,
+    pub item_id: u32,
+    pub subscription_id: Option<String>,
+}
+
+/// A work item found by the global search, with the source it belongs to.
+pub struct GlobalSearchResult {
+    pub source_index: usize,
+    pub item: WorkItem,
+}
+
+/// Results of searching one source, or why the source couldn't be searched.
+pub type SourceSearchOutcome = (usize, std::result::Result<Vec<WorkItem>, String>);
+
+/// Search by id / title across every configured source.
+#[derive(Default)]
+pub struct GlobalSearchState {
+    pub is_open: bool,
+    pub query: String,
+    /// Query the current results were found for.
+    pub searched_query: Option<String>,
+    pub results: Vec<GlobalSearchResult>,
+    /// Titles of sources that failed to load, with the reason.
+    pub failures: Vec<String>,
+    pub selected: usize,
+    pub receiver: Option<oneshot::Receiver<Vec<SourceSearchOutcome>>>,
+}
+
+impl GlobalSearchState {
+    pub fn open(&mut self) {
+        *self = GlobalSearchState {
+            is_open: true,
+            ..GlobalSearchState::default()
+        };
+    }
+
+    pub fn close(&mut self) {
+        *self = GlobalSearchState::default();
+    }
+
+    pub fn is_searching(&self) -> bool {
+        self.receiver.is_some()
+    }
+
+    /// Whether the results belong to the query as currently typed.
+    pub fn has_current_results(&self) -> bool {
+        self.searched_query.as_deref() == Some(self.query.as_str())
+    }
+
+    pub fn move_selection(&mut self, delta: isize) {
+        if self.results.is_empty() {
+            self.selected = 0;
+            return;
+        }
+        self.selected =
+            (self.selected as isize + delta).clamp(0, self.results.len() as isize - 1) as usize;
+    }
 }
 
 #[derive(Default, Clone)]
@@ -213,6 +528,158 @@ pub enum SaveStatus {
     Failed(String),
 }
 
+/// Where the detail view ends up once a save succeeds.
+#[derive(Default, Clone, Copy, PartialEq, Eq)]
+pub enum AfterSave {
+    /// Stop editing and keep showing the item.
+    #[default]
+    Stay,
+    /// Go on editing, e.g. another field.
+    KeepEditing,
+    /// Close the item and go back to the list.
+    ReturnToList,
+}
+
+/// How long a transient status message stays on screen.
+const STATUS_MESSAGE_TTL: Duration = Duration::from_secs(3);
+
+/// How long items changed by a reload stay marked; the marker dims for the
+/// second half.
+const CHANGE_MARKER_TTL: Duration = Duration::from_secs(6);
+
+pub struct StatusMessage {
+    pub text: String,
+    pub shown_at: Instant,
+}
+
+/// How many errors the error log keeps; older ones are dropped.
+const ERROR_LOG_CAPACITY: usize = 50;
+
+pub struct LoggedError {
+    pub message: String,
+    pub at: SystemTime,
+}
+
+/// Recent errors, newest last, shown by `error_log`.
+#[derive(Default)]
+pub struct ErrorLogView {
+    pub entries: VecDeque<LoggedError>,
+    pub is_open: bool,
+    pub scroll: u16,
+}
+
+/// Hours of remaining and completed work summed over a set of items.
+#[derive(Default, Clone, Copy)]
+pub struct WorkRollup {
+    pub remaining: f64,
+    pub completed: f64,
+}
+
+impl WorkRollup {
+    /// Sums the work fields over the items. A parent whose children carry
+    /// work of their own is skipped, as its hours usually repeat theirs.
+    /// `None` when no item has either field.
+    pub fn of(items: &[WorkItem], remaining_field: &str, completed_field: &str) -> Option<Self> {
+        let has_work = |item: &WorkItem| {
+            item.numeric_field(remaining_field).is_some()
+                || item.numeric_field(completed_field).is_some()
+        };
+        let parents_with_work: BTreeSet<u32> = items
+            .iter()
+            .filter(|item| has_work(item))
+            .filter_map(|item| item.parent_id)
+            .collect();
+
+        let mut rollup = WorkRollup::default();
+        let mut found = false;
+        for item in items
+            .iter()
+            .filter(|item| !parents_with_work.contains(&item.id))
+        {
+            found |= has_work(item);
+            rollup.remaining += item.numeric_field(remaining_field).unwrap_or_default();
+            rollup.completed += item.numeric_field(completed_field).unwrap_or_default();
+        }
+        found.then_some(rollup)
+    }
+}
+
+/// How a reload changed an item that is still on the board.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum ItemChange {
+    Added,
+    StateChanged,
+    Updated,
+}
+
+/// What a reload of the same source changed, keyed by work item id.
+#[derive(Default)]
+pub struct ItemsDiff {
+    pub added: usize,
+    pub moved: usize,
+    pub closed: usize,
+    pub updated: usize,
+    pub removed: usize,
+    pub changes: HashMap<u32, ItemChange>,
+}
+
+impl ItemsDiff {
+    fn between(old: &[WorkItem], new: &[WorkItem], is_done: impl Fn(&WorkItem) -> bool) -> Self {
+        let old_by_id: HashMap<u32, &WorkItem> = old.iter().map(|item| (item.id, item)).collect();
+        let new_ids: BTreeSet<u32> = new.iter().map(|item| item.id).collect();
+
+        let mut diff = ItemsDiff {
+            removed: old
+                .iter()
+                .filter(|item| !new_ids.contains(&item.id))
+                .count(),
+            ..ItemsDiff::default()
+        };
+        for item in new {
+            let change = match old_by_id.get(&item.id) {
+                None => {
+                    diff.added += 1;
+                    ItemChange::Added
+                }
+                Some(previous) if previous.state != item.state => {
+                    if is_done(item) {
+                        diff.closed += 1;
+                    } else {
+                        diff.moved += 1;
+                    }
+                    ItemChange::StateChanged
+                }
+                Some(previous) if previous.changed_date != item.changed_date => {
+                    diff.updated += 1;
+                    ItemChange::Updated
+                }
+                Some(_) => continue,
+            };
+            diff.changes.insert(item.id, change);
+        }
+        diff
+    }
+
+    fn summary(&self) -> String {
+        let parts: Vec<String> = [
+            (self.added, "new"),
+            (self.moved, "moved"),
+            (self.closed, "closed"),
+            (self.updated, "updated"),
+            (self.removed, "removed"),
+        ]
+        .into_iter()
+        .filter(|(count, _)| *count > 0)
+        .map(|(count, label)| format!("{} {}", count, label))
+        .collect();
+        if parts.is_empty() {
+            "No changes".to_string()
+        } else {
+            parts.join(" · ")
+        }
+    }
+}
+
 #[derive(Clone)]
 pub struct SourceEntry {
     pub title: String,
@@ -220,6 +687,174 @@ pub struct SourceEntry {
     pub organization: String,
     pub project: String,
     pub kind: SourceKind,
+    pub me: String,
+    /// Other names the user's items may be assigned under, e.g. from before
+    /// an organization migration.
+    pub me_aliases: Vec<String>,
+    /// Only the first this many items are loaded; `None` loads everything.
+    pub max_items: Option<usize>,
+}
+
+impl SourceEntry {
+    /// Drops ids past the configured cap, keeping ADO's (priority) order.
+    pub fn cap_ids(&self, mut ids: Vec<i32>) -> Vec<i32> {
+        if let Some(max_items) = self.max_items {
+            ids.truncate(max_items);
+        }
+        ids
+    }
+
+    /// Identifies the source's list in the on-disk caches.
+    pub fn cache_key(&self) -> WorkItemsCacheKey {
+        match &self.kind {
+            SourceKind::Backlog(backlog_level) => WorkItemsCacheKey::Backlog {
+                organization: self.organization.clone(),
+                project: self.project.clone(),
+                team: self.team.clone(),
+                backlog_level: backlog_level.clone(),
+            },
+            SourceKind::Iteration(iteration) => WorkItemsCacheKey::Iteration {
+                organization: iteration.organization.clone(),
+                project: iteration.project.clone(),
+                team: iteration.team.clone(),
+                iteration: iteration.iteration.clone(),
+            },
+            SourceKind::MyWork(_) => WorkItemsCacheKey::MyWork,
+        }
+    }
+
+    /// Whether the loaded list may have been cut off by `max_items`.
+    pub fn is_capped(&self, item_count: usize) -> bool {
+        self.max_items
+            .is_some_and(|max_items| item_count >= max_items)
+    }
+}
+
+/// Boards first, then iterations, in the order they are configured.
+fn build_sources(config: &AppConfig) -> Vec<SourceEntry> {
+    let mut sources: Vec<SourceEntry> = Vec::new();
+
+    for board in &config.boards {
+        let backlog_level = board
+            .backlog_level
+            .clone()
+            .unwrap_or_else(|| DEFAULT_BACKLOG_LEVEL.to_string());
+        let title = if let Some(title) = &board.title {
+            title.clone()
+        } else if backlog_level == DEFAULT_BACKLOG_LEVEL {
+            format!("{} Backlog", board.team)
+        } else {
+            format!(
+                "{} Backlog ({})",
+                board.team,
+                backlog_level_label(&backlog_level)
+            )
+        };
+        sources.push(SourceEntry {
+            title,
+            team: board.team.clone(),
+            organization: board.organization.clone(),
+            project: board.project.clone(),
+            kind: SourceKind::Backlog(backlog_level),
+            me: board.me.clone().unwrap_or_else(|| config.common.me.clone()),
+            me_aliases: board
+                .me_aliases
+                .clone()
+                .unwrap_or_else(|| config.common.me_aliases.clone()),
+            max_items: resolve_max_items(board.max_items, config.common.max_items),
+        });
+    }
+
+    for iteration in &config.iterations {
+        let title = iteration
+            .title
+            .clone()
+            .unwrap_or_else(|| format!("{} Iteration: {}", iteration.team, iteration.iteration));
+        sources.push(SourceEntry {
+            title,
+            team: iteration.team.clone(),
+            organization: iteration.organization.clone(),
+            project: iteration.project.clone(),
+            kind: SourceKind::Iteration(iteration.clone()),
+            me: iteration
+                .me
+                .clone()
+                .unwrap_or_else(|| config.common.me.clone()),
+            me_aliases: iteration
+                .me_aliases
+                .clone()
+                .unwrap_or_else(|| config.common.me_aliases.clone()),
+            max_items: resolve_max_items(iteration.max_items, config.common.max_items),
+        });
+    }
+
+    sources
+}
+
+/// Short name of a backlog category, e.g. "Feature" for
+/// `Microsoft.FeatureCategory`.
+fn backlog_level_label(backlog_level: &str) -> &str {
+    let name = backlog_level.rsplit('.').next().unwrap_or(backlog_level);
+    name.strip_suffix("Category")
+        .filter(|name| !name.is_empty())
+        .unwrap_or(name)
+}
+
+/// Title of a source in the board picker, with what kind of source it is.
+fn source_picker_label(source: &SourceEntry) -> String {
+    match &source.kind {
+        SourceKind::Backlog(level) if level == DEFAULT_BACKLOG_LEVEL => {
+            format!("{} (board)", source.title)
+        }
+        SourceKind::Backlog(level) => {
+            format!("{} ({} backlog)", source.title, backlog_level_label(level))
+        }
+        SourceKind::Iteration(_) => format!("{} (iteration)", source.title),
+        SourceKind::MyWork(_) => source.title.clone(),
+    }
+}
+
+/// Source merging the items assigned to `me` on every board; organization and
+/// project are the first board's, used for actions that need a single one.
+fn my_work_source(sources: &[SourceEntry]) -> SourceEntry {
+    let boards: Vec<SourceEntry> = sources
+        .iter()
+        .filter(|source| matches!(source.kind, SourceKind::Backlog(_)))
+        .cloned()
+        .collect();
+    let first = sources
+        .iter()
+        .find(|source| matches!(source.kind, SourceKind::Backlog(_)))
+        .or(sources.first());
+    SourceEntry {
+        title: "My Work".to_string(),
+        team: first.map(|source| source.team.clone()).unwrap_or_default(),
+        organization: first
+            .map(|source| source.organization.clone())
+            .unwrap_or_default(),
+        project: first
+            .map(|source| source.project.clone())
+            .unwrap_or_default(),
+        kind: SourceKind::MyWork(boards),
+        me: first.map(|source| source.me.clone()).unwrap_or_default(),
+        me_aliases: first
+            .map(|source| source.me_aliases.clone())
+            .unwrap_or_default(),
+        max_items: None,
+    }
+}
+
+/// Whether the item is assigned to `me` or any of the aliases; empty names
+/// match nothing.
+fn is_assigned_to_any(item: &WorkItem, me: &str, aliases: &[String]) -> bool {
+    std::iter::once(me)
+        .chain(aliases.iter().map(String::as_str))
+        .any(|name| !name.is_empty() && item.is_assigned_to(name))
+}
+
+/// Per-source cap if set, otherwise the common one; 0 disables the cap.
+fn resolve_max_items(source_max_items: Option<usize>, common_max_items: usize) -> Option<usize> {
+    Some(source_max_items.unwrap_or(common_max_items)).filter(|max_items| *max_items > 0)
 }
 
 pub struct App {
@@ -229,41 +864,92 @@ pub struct App {
     pub loading_state: LoadingState,
     pub sources: Vec<SourceEntry>,
     pub current_source_index: usize,
-    pub me: String,
     pub keys: KeysConfig,
     pub last_key_press: Option<KeyCode>,
-    pub work_item_types: BTreeMap<String, String>,
-    pub process_template_type: Option<String>,
+    /// Work item types (display name to reference name) by organization and
+    /// project.
+    pub work_item_types: HashMap<(String, String), BTreeMap<String, String>>,
+    pub process_template_types: HashMap<(String, String), String>,
     pub layout_cache: HashMap<(String, String, String), Vec<(String, String)>>,
-    pub field_meta_cache: HashMap<String, Vec<WorkItemFieldInfo>>,
+    pub field_meta_cache: HashMap<(String, String, String), Vec<WorkItemFieldInfo>>,
     pub refresh_policy: RefreshPolicy,
     pub showing_help: bool,
+    pub status_message: Option<StatusMessage>,
+    /// Changes found by the last reload, shown until the next key press.
+    pub refresh_summary: Option<String>,
+    /// Items the last reload added or changed, and when it finished.
+    recent_changes: HashMap<u32, ItemChange>,
+    recent_changes_at: Option<Instant>,
+    pub confirm_quit: bool,
+    pub confirming_quit: bool,
+    /// Set by the edit-config command; the editor needs the terminal, which
+    /// only `run_app` holds.
+    pub config_edit_requested: bool,
+    loaded_source_index: Option<usize>,
+    pub field_formats: BTreeMap<String, FieldFormat>,
+    pub item_urls: BTreeMap<String, String>,
+    pub config_path: Option<PathBuf>,
+    pub age_heatmap: bool,
+    pub done_states: Vec<String>,
+    pub sort: SortConfig,
+    pub branch_name_template: String,
+    pub list_title_format: String,
+    pub remaining_work_field: String,
+    pub assignee_display: AssigneeDisplay,
+    pub startup_banner: bool,
+    /// User each organization was signed in as, or why it couldn't be told.
+    pub signed_in_users: HashMap<String, String>,
+    /// Display name of the signed in user per organization, used as `me`
+    /// where none is configured. Empty when it couldn't be looked up.
+    pub resolved_me: HashMap<String, String>,
+    /// Whether the merged "my work" source is shown ahead of the boards.
+    pub my_work: bool,
+    /// Started with `--debug`, which enables the field inspector.
+    pub debug: bool,
+    /// Name and dates of the iteration the current board shows, when known.
+    pub current_iteration: Option<ResolvedIteration>,
+    pub list_width_percent: u16,
+    pub unread_markers: bool,
+    pub max_title_width: usize,
+    pub wrap_titles: bool,
+    pub preserve_filters_on_refresh: bool,
+    pub details_pane_width: u16,
+    pub detail_wrap: DetailWrap,
+    /// Changed date of each item of the current source when it was last
+    /// opened, by id.
+    seen: BTreeMap<u32, String>,
+    /// Items of the current source kept at the top of the list.
+    pub pinned_ids: BTreeSet<u32>,
+    pub external_commands: Vec<ExternalCommandConfig>,
+    external_receiver: Option<oneshot::Receiver<(String, io::Result<std::process::Output>)>>,
+    pub command_output: Option<CommandOutputView>,
+    pub error_log: ErrorLogView,
+    pub completed_work_field: String,
+    pub repo_paths: BTreeMap<String, PathBuf>,
+    pub repo_editor: String,
+    pub checkout_linked_branch: bool,
+    pub repository_names: HashMap<(String, String), String>,
+    pub show_full_error: bool,
+    pub base_url: String,
+    pub open_url_template: String,
+    pub on_enter: OnEnter,
+    pub command_palette: CommandPaletteState,
+    pub board_picker: PickerState,
+    pub team_members_cache: HashMap<(String, String, String), Vec<TeamMemberInfo>>,
+    /// Iteration paths of each team, offered when editing the iteration path.
+    pub team_iterations_cache: HashMap<(String, String, String), Vec<String>>,
+    /// Follow subscription id per (organization, item id); `None` when the
+    /// item is known not to be followed.
+    pub follow_states: HashMap<(String, u32), Option<String>>,
+    pub global_search: GlobalSearchState,
+    /// Item to select once the source being loaded is shown.
+    pending_selection: Option<u32>,
 }
 
 impl App {
-    pub fn new(config: AppConfig) -> App {
+    pub fn new(config: AppConfig, config_path: Option<PathBuf>) -> App {
         let mut list_state = ListState::default();
-        let mut sources: Vec<SourceEntry> = Vec::new();
-
-        for board in &config.boards {
-            sources.push(SourceEntry {
-                title: format!("{} Backlog", board.team),
-                team: board.team.clone(),
-                organization: board.organization.clone(),
-                project: board.project.clone(),
-                kind: SourceKind::Backlog,
-            });
-        }
-
-        for iteration in &config.iterations {
-            sources.push(SourceEntry {
-                title: format!("{} Iteration: {}", iteration.team, iteration.iteration),
-                team: iteration.team.clone(),
-                organization: iteration.organization.clone(),
-                project: iteration.project.clone(),
-                kind: SourceKind::Iteration(iteration.clone()),
-            });
-        }
+        let sources = build_sources(&config);
 
         if !sources.is_empty() {
             list_state.select(Some(0));
@@ -271,37 +957,236 @@ impl App {
 
         App {
             items: Vec::new(),
-            list_view_state: ListViewState::new(list_state),
-            detail_view_state: DetailViewState::default(),
+            list_view_state: ListViewState {
+                hide_done: config.common.hide_done,
+                show_summary: config.common.summary_header,
+                ..ListViewState::new(list_state)
+            },
+            detail_view_state: DetailViewState {
+                collapsed_fields: config.common.collapsed_fields.iter().cloned().collect(),
+                ..DetailViewState::default()
+            },
             loading_state: LoadingState::Loading,
             sources,
             current_source_index: 0,
-            me: config.common.me,
+            age_heatmap: config.common.age_heatmap,
+            done_states: config.common.done_states.clone(),
+            sort: config.sort.clone(),
+            branch_name_template: config.common.branch_name_template.clone(),
+            list_title_format: config.common.list_title_format.clone(),
+            remaining_work_field: config.common.remaining_work_field.clone(),
+            assignee_display: config.common.assignee_display,
+            startup_banner: config.common.startup_banner,
+            signed_in_users: HashMap::new(),
+            resolved_me: HashMap::new(),
+            my_work: false,
+            debug: false,
+            current_iteration: None,
+            list_width_percent: config
+                .common
+                .list_width_percent
+                .clamp(MIN_LIST_WIDTH_PERCENT, MAX_LIST_WIDTH_PERCENT),
+            unread_markers: config.common.unread_markers,
+            max_title_width: config.common.max_title_width,
+            wrap_titles: config.common.wrap_titles,
+            preserve_filters_on_refresh: config.common.preserve_filters_on_refresh,
+            details_pane_width: config.common.details_pane_width,
+            detail_wrap: config.common.detail_wrap,
+            seen: BTreeMap::new(),
+            pinned_ids: BTreeSet::new(),
+            external_commands: config.commands.clone(),
+            external_receiver: None,
+            command_output: None,
+            error_log: ErrorLogView::default(),
+            completed_work_field: config.common.completed_work_field.clone(),
+            repo_paths: config.repo_paths.clone(),
+            repo_editor: config.common.repo_editor.clone(),
+            checkout_linked_branch: config.common.checkout_linked_branch,
+            repository_names: HashMap::new(),
+            show_full_error: config.common.full_errors,
+            base_url: config.common.base_url.trim_end_matches('/').to_string(),
+            open_url_template: config.common.open_url_template,
+            on_enter: config.common.on_enter,
+            command_palette: CommandPaletteState::default(),
+            board_picker: PickerState::default(),
+            team_members_cache: HashMap::new(),
+            team_iterations_cache: HashMap::new(),
+            follow_states: HashMap::new(),
+            global_search: GlobalSearchState::default(),
+            pending_selection: None,
             keys: config.keys,
             last_key_press: None,
-            work_item_types: BTreeMap::new(),
-            process_template_type: None,
+            work_item_types: HashMap::new(),
+            process_template_types: HashMap::new(),
             layout_cache: HashMap::new(),
             field_meta_cache: HashMap::new(),
             refresh_policy: RefreshPolicy::Normal,
             showing_help: false,
+            status_message: None,
+            refresh_summary: None,
+            recent_changes: HashMap::new(),
+            recent_changes_at: None,
+            confirm_quit: config.common.confirm_quit,
+            confirming_quit: false,
+            config_edit_requested: false,
+            loaded_source_index: None,
+            field_formats: config.field_formats,
+            item_urls: config.item_urls,
+            config_path,
+        }
+    }
+
+    /// Reads the configuration file again and applies it, then reloads the
+    /// current board, or the first one if it is no longer configured. Fails,
+    /// leaving everything as it was, when the file can't be used.
+    fn reload_config(&mut self) -> Result<()> {
+        let config = crate::config::reload_config(self.config_path.as_deref())?;
+        let mut sources = build_sources(&config);
+        if sources.is_empty() {
+            return Err(anyhow!("No boards or iterations configured"));
+        }
+        if self.my_work {
+            sources.insert(0, my_work_source(&sources));
+        }
+
+        let current = self.current_source();
+        let same_source = |source: &SourceEntry| {
+            source.organization == current.organization
+                && source.project == current.project
+                && source.title == current.title
+        };
+        let kept_index = sources.iter().position(same_source);
+        let loaded_index = self
+            .loaded_source_index
+            .filter(|index| *index == self.current_source_index)
+            .and(kept_index);
+        if kept_index.is_none() {
+            let text = format!("{} is no longer configured", current.title);
+            self.set_status_message(text);
         }
+        self.sources = sources;
+        self.current_source_index = kept_index.unwrap_or(0);
+        self.loaded_source_index = loaded_index;
+
+        let common = config.common;
+        self.age_heatmap = common.age_heatmap;
+        self.done_states = common.done_states;
+        self.sort = config.sort;
+        self.branch_name_template = common.branch_name_template;
+        self.list_title_format = common.list_title_format;
+        self.remaining_work_field = common.remaining_work_field;
+        self.completed_work_field = common.completed_work_field;
+        self.assignee_display = common.assignee_display;
+        self.startup_banner = common.startup_banner;
+        self.list_width_percent = common
+            .list_width_percent
+            .clamp(MIN_LIST_WIDTH_PERCENT, MAX_LIST_WIDTH_PERCENT);
+        self.unread_markers = common.unread_markers;
+        self.max_title_width = common.max_title_width;
+        self.wrap_titles = common.wrap_titles;
+        self.preserve_filters_on_refresh = common.preserve_filters_on_refresh;
+        self.details_pane_width = common.details_pane_width;
+        self.detail_wrap = common.detail_wrap;
+        set_request_timeout(common.request_timeout_seconds);
+        set_max_concurrent_requests(common.max_concurrent_requests);
+        set_partial_loads(common.partial_loads);
+        self.detail_view_state.collapsed_fields = common.collapsed_fields.into_iter().collect();
+        self.external_commands = config.commands;
+        self.repo_paths = config.repo_paths;
+        self.repo_editor = common.repo_editor;
+        self.checkout_linked_branch = common.checkout_linked_branch;
+        self.show_full_error = common.full_errors;
+        self.base_url = common.base_url.trim_end_matches('/').to_string();
+        self.open_url_template = common.open_url_template;
+        self.on_enter = common.on_enter;
+        self.confirm_quit = common.confirm_quit;
+        self.field_formats = config.field_formats;
+        self.item_urls = config.item_urls;
+        self.keys = config.keys;
+        self.last_key_press = None;
+
+        self.loading_state = LoadingState::Loading;
+        Ok(())
     }
 
-    pub fn set_work_item_types(&mut self, types: BTreeMap<String, String>) {
-        self.work_item_types = types;
-        self.clear_layout_cache();
-        self.field_meta_cache.clear();
+    /// Process and work item types of a project, as fetched for its layouts.
+    pub fn set_process_metadata(
+        &mut self,
+        organization: &str,
+        project: &str,
+        process_template_type: String,
+        types: BTreeMap<String, String>,
+    ) {
+        let key = (organization.to_string(), project.to_string());
+        self.process_template_types
+            .insert(key.clone(), process_template_type);
+        self.work_item_types.insert(key, types);
     }
 
     pub fn clear_layout_cache(&mut self) {
         self.layout_cache.clear();
     }
 
-    pub fn set_process_template_type(&mut self, process_template_type: String) {
-        self.process_template_type = Some(process_template_type);
-        self.clear_layout_cache();
-        self.field_meta_cache.clear();
+    /// Organization and project an item lives in: the board it was merged
+    /// from in My Work, the current source otherwise.
+    pub fn item_project<'a>(&'a self, item: &'a WorkItem) -> (&'a str, &'a str) {
+        match &item.board {
+            Some(board) => (&board.organization, &board.project),
+            None => {
+                let source = self.current_source();
+                (&source.organization, &source.project)
+            }
+        }
+    }
+
+    /// Configured source an item belongs to, for actions that write to ADO
+    /// or need the team. `None` when a merged item's board is no longer
+    /// configured.
+    fn item_source(&self, item: &WorkItem) -> Option<&SourceEntry> {
+        let current = self.current_source();
+        match (&current.kind, &item.board) {
+            (SourceKind::MyWork(boards), Some(board)) => boards.iter().find(|source| {
+                source.title == board.title
+                    && source.organization == board.organization
+                    && source.project == board.project
+            }),
+            (SourceKind::MyWork(_), None) => None,
+            _ => Some(current),
+        }
+    }
+
+    /// Source of the selected item, explaining in the status line when it
+    /// has none.
+    fn selected_item_source(&mut self) -> Option<SourceEntry> {
+        let item = self.get_selected_item()?;
+        let source = self.item_source(item).cloned();
+        if source.is_none() {
+            let message = format!("The board of #{} is no longer configured", item.id);
+            self.set_status_message(message);
+        }
+        source
+    }
+
+    /// Field metadata of an item's type in its project.
+    pub fn type_fields(&self, item: &WorkItem) -> Option<&Vec<WorkItemFieldInfo>> {
+        let (organization, project) = self.item_project(item);
+        self.field_meta_cache.get(&(
+            organization.to_string(),
+            project.to_string(),
+            item.work_item_type.clone(),
+        ))
+    }
+
+    /// Starts on the items assigned to `me` across all boards, ahead of the
+    /// configured sources.
+    pub fn enable_my_work(&mut self) {
+        if self.my_work || self.sources.is_empty() {
+            return;
+        }
+        let source = my_work_source(&self.sources);
+        self.sources.insert(0, source);
+        self.current_source_index = 0;
+        self.my_work = true;
     }
 
     pub fn current_source(&self) -> &SourceEntry {
@@ -309,41 +1194,184 @@ impl App {
     }
 
     pub fn load_data(&mut self, items: Vec<WorkItem>) {
+        let reloaded = self.loaded_source_index == Some(self.current_source_index);
+        let diff =
+            reloaded.then(|| ItemsDiff::between(&self.items, &items, |item| self.is_done(item)));
+        self.refresh_summary = diff.as_ref().map(ItemsDiff::summary);
+        self.recent_changes = diff.map(|diff| diff.changes).unwrap_or_default();
+        self.recent_changes_at = Some(Instant::now());
+        // A reload of the same board keeps the selected item where possible.
+        let previous_selection = self
+            .get_selected_item()
+            .filter(|_| reloaded)
+            .map(|item| item.id);
+        // Collapsed groups are kept by assignee, so a selection hidden in one
+        // falls back to its header.
+        let previous_group = self
+            .get_selected_item()
+            .map(|item| item.assigned_to.clone())
+            .or_else(|| self.selected_group())
+            .filter(|_| reloaded);
+        self.list_view_state.is_moving = false;
+        if !reloaded {
+            // The sprint belongs to the team of the board it was picked on.
+            self.list_view_state.current_sprint = None;
+        }
+        self.loaded_source_index = Some(self.current_source_index);
         let mut list_state = ListState::default();
         if !items.is_empty() {
             list_state.select(Some(0));
         }
-        self.list_view_state
-            .type_picker
-            .set_options(items.iter().map(|i| i.work_item_type.clone()));
         self.items = items;
+        self.load_seen();
+        self.pinned_ids = read_pinned(&self.current_source().cache_key()).unwrap_or_default();
+        self.refresh_picker_options();
         self.list_view_state.list_state = list_state;
         self.list_view_state.type_picker.selected = None;
+        self.list_view_state.column_picker.selected = None;
         self.detail_view_state.edit_state = None;
         self.detail_view_state.save_status = SaveStatus::Idle;
         self.detail_view_state.save_receiver = None;
         self.loading_state = LoadingState::Loaded;
+        if let Some(item_id) = self.pending_selection.take() {
+            self.select_item_by_id(item_id);
+        } else if let Some(item_id) = previous_selection
+            && let Some(index) = self
+                .list_rows()
+                .iter()
+                .position(|row| matches!(row, ListRow::Item(item) if item.id == item_id))
+        {
+            self.list_view_state.list_state.select(Some(index));
+        } else {
+            self.select_group_header(previous_group);
+        }
     }
 
-    fn reset_inactive_edit_state(&mut self) {
-        if let Some(state) = self.detail_view_state.edit_state.as_ref() {
-            if !state.is_editing {
-                self.detail_view_state.edit_state = None;
-                self.detail_view_state.save_status = SaveStatus::Idle;
-                self.detail_view_state.save_receiver = None;
+    /// Reads which items of the current source were opened before. The first
+    /// time a source is loaded all of its items count as seen, so only what
+    /// is added or changed afterwards shows up as unread.
+    fn load_seen(&mut self) {
+        let cache_key = self.current_source().cache_key();
+        self.seen = match read_seen(&cache_key) {
+            Some(seen) => seen,
+            None => {
+                let seen: BTreeMap<u32, String> = self
+                    .items
+                    .iter()
+                    .map(|item| (item.id, item.changed_date.clone()))
+                    .collect();
+                let _ = write_seen(&cache_key, &seen);
+                seen
+            }
+        };
+    }
+
+    /// Whether the item was never opened, or changed since it last was.
+    pub fn is_unread(&self, item: &WorkItem) -> bool {
+        self.unread_markers && self.seen.get(&item.id) != Some(&item.changed_date)
+    }
+
+    fn mark_selected_seen(&mut self) {
+        let Some(item) = self.get_selected_item() else {
+            return;
+        };
+        if self.seen.get(&item.id) == Some(&item.changed_date) {
+            return;
+        }
+        self.seen.insert(item.id, item.changed_date.clone());
+        let _ = write_seen(&self.current_source().cache_key(), &self.seen);
+    }
+
+    pub fn is_pinned(&self, item: &WorkItem) -> bool {
+        self.pinned_ids.contains(&item.id)
+    }
+
+    /// Pins the selected item to the top of the list, or unpins it, keeping
+    /// it selected.
+    fn toggle_pin(&mut self) {
+        let Some(item_id) = self.get_selected_item().map(|item| item.id) else {
+            self.set_status_message("No item selected");
+            return;
+        };
+        let text = if self.pinned_ids.remove(&item_id) {
+            format!("Unpinned #{}", item_id)
+        } else {
+            self.pinned_ids.insert(item_id);
+            format!("Pinned #{}", item_id)
+        };
+        match write_pinned(&self.current_source().cache_key(), &self.pinned_ids) {
+            Ok(()) => self.set_status_message(text),
+            Err(err) => {
+                self.set_status_message(format!("{} (not saved: {})", text, summarize_error(&err)))
+            }
+        }
+        self.select_item_by_id(item_id);
+        self.clamp_selection();
+    }
+
+    /// Selects the item in the list, or explains why it can't be shown. A
+    /// collapsed group holding the item is expanded.
+    fn select_item_by_id(&mut self, item_id: u32) {
+        if let Some(item) = self
+            .get_filtered_items()
+            .into_iter()
+            .find(|item| item.id == item_id)
+        {
+            let assignee = item.assigned_to.clone();
+            self.list_view_state.collapsed_groups.remove(&assignee);
+        }
+        match self
+            .list_rows()
+            .iter()
+            .position(|row| matches!(row, ListRow::Item(item) if item.id == item_id))
+        {
+            Some(index) => self.list_view_state.list_state.select(Some(index)),
+            None if self.items.iter().any(|item| item.id == item_id) => {
+                self.set_status_message(format!("#{} is hidden by the current filters", item_id))
             }
+            None => self.set_status_message(format!("#{} is no longer on this board", item_id)),
+        }
+    }
+
+    /// Orders items by the configured primary key, then the secondary key,
+    /// and finally by id so that ties don't move around between refreshes.
+    /// Without a primary key the order returned by ADO is kept.
+    fn sort_items(&self, items: &mut [&WorkItem]) {
+        if self.sort.primary == SortKey::None {
+            return;
+        }
+        items.sort_by(|a, b| a.compare_sorted(b, &self.sort));
+    }
+
+    /// Sorts by the next key, or flips the direction, keeping the selected
+    /// item selected.
+    fn change_sort(&mut self, change: impl FnOnce(&mut SortConfig)) {
+        let selected = self.get_selected_item().map(|item| item.id);
+        change(&mut self.sort);
+        if let Some(item_id) = selected {
+            self.select_item_by_id(item_id);
+        }
+    }
+
+    fn reset_inactive_edit_state(&mut self) {
+        if let Some(state) = self.detail_view_state.edit_state.as_ref()
+            && !state.is_editing
+        {
+            self.detail_view_state.edit_state = None;
+            self.detail_view_state.save_status = SaveStatus::Idle;
+            self.detail_view_state.save_receiver = None;
         }
     }
 
     pub fn jump_to_start(&mut self) {
-        if !self.get_filtered_items().is_empty() {
+        if !self.list_rows().is_empty() {
             self.list_view_state.list_state.select(Some(0));
             self.reset_inactive_edit_state();
         }
     }
 
     pub fn jump_to_end(&mut self) {
-        let items_len = self.get_filtered_items().len();
+        let items_len = self.list_rows().len();
         if items_len > 0 {
             self.list_view_state.list_state.select(Some(items_len - 1));
             self.reset_inactive_edit_state();
@@ -357,11 +1385,16 @@ impl App {
         self.detail_view_state.save_status = SaveStatus::Idle;
         self.detail_view_state.save_receiver = None;
         if let Some(item) = self.get_selected_item().cloned() {
-            let reference_name = self.work_item_types.get(&item.work_item_type).cloned();
             let mut edit_state = DetailEditState::new_from_item(&item);
 
-            let organization = self.current_source().organization.clone();
-            let project = self.current_source().project.clone();
+            let (organization, project) = self.item_project(&item);
+            let (organization, project) = (organization.to_string(), project.to_string());
+            let project_key = (organization.clone(), project.clone());
+            let reference_name = self
+                .work_item_types
+                .get(&project_key)
+                .and_then(|types| types.get(&item.work_item_type))
+                .cloned();
             let cache_key = (
                 organization.clone(),
                 project.clone(),
@@ -382,11 +1415,9 @@ impl App {
                 None
             } else if let Some(cached) = self.layout_cache.get(&cache_key) {
                 Some(cached.clone())
-            } else if let Some(disk) = read_layout_cache(&layout_key_display).or_else(|| {
-                layout_key_ref
-                    .as_ref()
-                    .and_then(|ref_key| read_layout_cache(ref_key))
-            }) {
+            } else if let Some(disk) = read_layout_cache(&layout_key_display)
+                .or_else(|| layout_key_ref.as_ref().and_then(read_layout_cache))
+            {
                 self.layout_cache.insert(cache_key.clone(), disk.clone());
                 Some(disk)
             } else {
@@ -395,16 +1426,18 @@ impl App {
 
             let controls = if let Some(cached) = cached_controls {
                 cached
-            } else if let (Some(process_id), Some(reference)) =
-                (self.process_template_type.clone(), reference_name.clone())
-            {
+            } else if let (Some(process_id), Some(reference)) = (
+                self.process_template_types.get(&project_key).cloned(),
+                reference_name.clone(),
+            ) {
                 match fetch_visible_controls(&organization, &process_id, &reference).await {
                     Ok(controls) => {
                         if let Some(ref_key) = layout_key_ref.as_ref() {
                             let _ = write_layout_cache(ref_key, &controls);
                         }
                         let _ = write_layout_cache(&layout_key_display, &controls);
-                        self.layout_cache.insert(cache_key.clone(), controls.clone());
+                        self.layout_cache
+                            .insert(cache_key.clone(), controls.clone());
                         controls
                     }
                     Err(err) => {
@@ -419,20 +1452,19 @@ impl App {
             let visible_fields = controls
                 .into_iter()
                 .filter_map(|(id, label)| {
-                    item.fields.get(&id).cloned().map(|value| {
-                        let allowed_values = self.field_meta_cache.get(&item.work_item_type).and_then(
-                            |fields| {
-                                fields
-                                    .iter()
-                                    .find(|f| f.reference_name == id)
-                                    .map(|f| f.allowed_values.clone())
-                            },
-                        );
+                    item.edit_value(&id, &self.field_formats).map(|value| {
+                        let allowed_values = self.type_fields(&item).and_then(|fields| {
+                            fields
+                                .iter()
+                                .find(|f| f.reference_name == id)
+                                .map(|f| f.allowed_values.clone())
+                        });
                         VisibleField::with_value(label, id, value, allowed_values)
                     })
                 })
                 .collect();
             edit_state.visible_fields = visible_fields;
+            self.add_iteration_path_field(&item, &mut edit_state).await;
 
             self.detail_view_state.edit_state = Some(edit_state);
             self.detail_view_state.save_status = SaveStatus::Idle;
@@ -440,45 +1472,303 @@ impl App {
         }
     }
 
-    pub fn toggle_type_filter_menu(&mut self) {
-        self.list_view_state.type_picker.toggle_open();
-        if self.list_view_state.type_picker.is_open {
-            self.list_view_state.is_list_details_hover_visible = false;
-        }
-    }
-
-    pub fn toggle_type_selection(&mut self) {
-        if !self.list_view_state.type_picker.is_open {
-            return;
+    /// Iteration paths of the source's team, fetched once per team.
+    async fn team_iteration_paths(&mut self, source: &SourceEntry) -> Result<Vec<String>> {
+        let cache_key = (
+            source.organization.clone(),
+            source.project.clone(),
+            source.team.clone(),
+        );
+        if let Some(paths) = self.team_iterations_cache.get(&cache_key) {
+            return Ok(paths.clone());
         }
-
-        self.list_view_state.type_picker.toggle_active();
-        self.clamp_selection();
-    }
-
-    pub fn clear_type_filters(&mut self) {
-        self.list_view_state.type_picker.clear_active();
-        self.clamp_selection();
+        let paths = fetch_team_iteration_paths(&cache_key.0, &cache_key.1, &cache_key.2).await?;
+        self.team_iterations_cache.insert(cache_key, paths.clone());
+        Ok(paths)
     }
 
-    pub fn move_type_selection(&mut self, direction: isize) {
-        if !self.list_view_state.type_picker.is_open {
+    /// Makes the iteration path editable with a picker of the team's
+    /// iterations, adding the field when the layout doesn't show it.
+    async fn add_iteration_path_field(
+        &mut self,
+        item: &WorkItem,
+        edit_state: &mut DetailEditState,
+    ) {
+        let fetched = match self.item_source(item).cloned() {
+            Some(source) => self.team_iteration_paths(&source).await.map(Some),
+            None => Ok(None),
+        };
+        let iterations = match fetched {
+            Ok(paths) => paths,
+            Err(err) => {
+                let message = format!("Failed to load iterations: {}", err);
+                self.log_error(message.clone());
+                self.set_status_message(message);
+                None
+            }
+        };
+        let fields = &mut edit_state.visible_fields;
+        match fields
+            .iter_mut()
+            .find(|field| field.reference == ITERATION_PATH_FIELD)
+        {
+            Some(field) => {
+                if iterations.is_some() {
+                    *field = VisibleField::with_value(
+                        field.label.clone(),
+                        field.reference.clone(),
+                        field.value.clone(),
+                        iterations,
+                    );
+                }
+            }
+            None => fields.push(VisibleField::with_value(
+                "Iteration".to_string(),
+                ITERATION_PATH_FIELD.to_string(),
+                item.fields
+                    .get(ITERATION_PATH_FIELD)
+                    .cloned()
+                    .unwrap_or_default(),
+                iterations,
+            )),
+        }
+    }
+
+    /// The edited iteration path when it isn't one of the team's iterations.
+    fn invalid_iteration_path(
+        &self,
+        source: &SourceEntry,
+        state: &DetailEditState,
+    ) -> Option<String> {
+        let cache_key = (
+            source.organization.clone(),
+            source.project.clone(),
+            source.team.clone(),
+        );
+        let paths = self.team_iterations_cache.get(&cache_key)?;
+        state
+            .visible_fields
+            .iter()
+            .find(|field| field.reference == ITERATION_PATH_FIELD && field.is_modified())
+            .filter(|field| !paths.contains(&field.value))
+            .map(|field| field.value.clone())
+    }
+
+    fn refresh_picker_options(&mut self) {
+        self.list_view_state
+            .type_picker
+            .set_options(self.items.iter().map(|i| i.work_item_type.clone()));
+        self.list_view_state.column_picker.set_options(
+            self.items
+                .iter()
+                .filter(|i| !i.board_column.is_empty())
+                .map(|i| i.board_column.clone()),
+        );
+        self.list_view_state
+            .tag_picker
+            .set_options(self.items.iter().flat_map(|i| i.tags.iter().cloned()));
+    }
+
+    pub fn toggle_filter_menu(&mut self, which: ListPicker) {
+        for other in ListPicker::ALL.into_iter().filter(|other| *other != which) {
+            self.list_view_state.picker_mut(other).close();
+        }
+        self.list_view_state.picker_mut(which).toggle_open();
+        if self.list_view_state.picker(which).is_open {
+            self.list_view_state.is_list_details_hover_visible = false;
+        }
+    }
+
+    pub fn toggle_column_filter_menu(&mut self) {
+        // Board columns only exist for teams using Kanban boards.
+        if self.list_view_state.column_picker.options.is_empty() {
+            self.set_status_message("No board columns on this board");
+            return;
+        }
+        self.toggle_filter_menu(ListPicker::Column);
+    }
+
+    pub fn toggle_tag_filter_menu(&mut self) {
+        if self.list_view_state.tag_picker.options.is_empty() {
+            self.set_status_message("No tags on the loaded items");
             return;
         }
+        self.toggle_filter_menu(ListPicker::Tag);
+    }
+
+    pub fn toggle_tag_match_mode(&mut self) {
+        self.list_view_state.tag_match_all = !self.list_view_state.tag_match_all;
+        self.clamp_selection();
+    }
+
+    pub fn close_picker(&mut self) {
+        if let Some(which) = self.list_view_state.open_picker() {
+            self.list_view_state.picker_mut(which).close();
+        }
+    }
+
+    pub fn toggle_picker_selection(&mut self) {
+        let Some(which) = self.list_view_state.open_picker() else {
+            return;
+        };
+
+        self.list_view_state.picker_mut(which).toggle_active();
+        self.clamp_selection();
+    }
+
+    /// Filters on one work item type at a time, moving from no filter through
+    /// each loaded type and back. Several selected types count as no filter.
+    pub fn cycle_type_filter(&mut self) {
+        let picker = &mut self.list_view_state.type_picker;
+        if picker.options.is_empty() {
+            self.set_status_message("No work item types on the loaded items");
+            return;
+        }
+        let next = match picker.active.iter().collect::<Vec<_>>().as_slice() {
+            [current] => picker
+                .options
+                .iter()
+                .position(|option| option == *current)
+                .and_then(|index| picker.options.get(index + 1)),
+            _ => picker.options.first(),
+        }
+        .cloned();
+        picker.clear_active();
+        let text = match next {
+            Some(work_item_type) => {
+                picker.active.insert(work_item_type.clone());
+                format!("Showing {} only", work_item_type)
+            }
+            None => "Showing all types".to_string(),
+        };
+        self.set_status_message(text);
+        self.clamp_selection();
+    }
+
+    pub fn clear_picker_filters(&mut self) {
+        let Some(which) = self.list_view_state.open_picker() else {
+            return;
+        };
+
+        self.list_view_state.picker_mut(which).clear_active();
+        self.clamp_selection();
+    }
+
+    pub fn move_picker_selection(&mut self, direction: isize) {
+        let Some(which) = self.list_view_state.open_picker() else {
+            return;
+        };
+
+        self.list_view_state
+            .picker_mut(which)
+            .move_selection(direction);
+    }
+
+    fn project_url(&self) -> String {
+        let source = self.current_source();
+        self.project_url_for(&source.organization, &source.project)
+    }
 
-        self.list_view_state.type_picker.move_selection(direction);
+    fn project_url_for(&self, organization: &str, project: &str) -> String {
+        format!(
+            "{}/{}/{}",
+            self.base_url,
+            encode_url_segment(organization),
+            encode_url_segment(project),
+        )
+    }
+
+    /// Web URL of the item, from `[item_urls]` when its type has an entry
+    /// and `open_url_template` otherwise. Items of the merged view link to
+    /// the board they came from.
+    fn item_url(&self, item: &WorkItem) -> String {
+        let (organization, project) = self.item_project(item);
+        let template = self
+            .item_urls
+            .get(&item.work_item_type)
+            .unwrap_or(&self.open_url_template);
+        template
+            .replace(
+                "{project_url}",
+                &self.project_url_for(organization, project),
+            )
+            .replace("{base_url}", &self.base_url)
+            .replace("{org}", &encode_url_segment(organization))
+            .replace("{project}", &encode_url_segment(project))
+            .replace("{type}", &encode_url_segment(&item.work_item_type))
+            .replace("{id}", &item.id.to_string())
     }
 
     pub fn open_item(&mut self) {
-        let item = self.get_selected_item().unwrap();
+        let Some(item) = self.get_selected_item().filter(|item| item.id != 0) else {
+            self.set_status_message("No item selected");
+            return;
+        };
+        let url = self.item_url(item);
+
+        if let Err(e) = open::that(&url) {
+            self.set_status_message(format!("Failed to open {}: {}", url, e));
+        }
+    }
+
+    /// Web URL of the current backlog, or of the sprint taskboard for iterations.
+    pub fn current_source_url(&self) -> String {
         let source = self.current_source();
-        let url = format!(
-            "https://dev.azure.com/{}/{}/_workitems/edit/{}",
-            source.organization, source.project, item.id,
-        );
+        let project_url = self.project_url();
+        match &source.kind {
+            SourceKind::Backlog(_) => format!(
+                "{}/_backlogs/backlog/{}",
+                project_url,
+                encode_url_segment(&source.team)
+            ),
+            SourceKind::Iteration(iteration) => {
+                let iteration_path = iteration
+                    .iteration
+                    .split('\\')
+                    .map(encode_url_segment)
+                    .collect::<Vec<_>>()
+                    .join("/");
+                format!(
+                    "{}/_sprints/taskboard/{}/{}",
+                    project_url,
+                    encode_url_segment(&source.team),
+                    iteration_path
+                )
+            }
+            SourceKind::MyWork(_) => format!("{}/_workitems/assignedtome/", project_url),
+        }
+    }
 
+    /// Opens the first pull request linked to the selected item, or its
+    /// first linked commit when there is no pull request.
+    fn open_linked_pull_request(&mut self) {
+        let Some(item) = self.get_selected_item() else {
+            return;
+        };
+        let artifact = item
+            .linked_artifacts
+            .iter()
+            .find(|artifact| matches!(artifact.kind, ArtifactKind::PullRequest(_)))
+            .or_else(|| item.linked_artifacts.first());
+        let Some(artifact) = artifact else {
+            let message = format!("#{} has no linked PRs", item.id);
+            self.set_status_message(message);
+            return;
+        };
+        let (organization, project) = self.item_project(item);
+        let project_url = self.project_url_for(organization, project);
+        let url = format!("{}/{}", project_url, artifact.web_path());
         if let Err(e) = open::that(url) {
-            eprintln!("Failed to open link: {}", e);
+            self.set_status_message(format!("Failed to open link: {}", e));
+        }
+    }
+
+    /// Opens the backlog or sprint board; the URL is shown when no browser
+    /// could be started, so it can be opened by hand.
+    pub fn open_board(&mut self) {
+        let url = self.current_source_url();
+        if let Err(e) = open::that(&url) {
+            self.set_status_message(format!("Failed to open {}: {}", url, e));
         }
     }
 
@@ -500,244 +1790,2124 @@ impl App {
         }
     }
 
+    /// Lists every source in configuration order, the current one marked.
+    fn open_board_picker(&mut self) {
+        // Options are set directly rather than through `set_options`, which
+        // sorts them; the picked index is the source index.
+        let options: Vec<String> = self.sources.iter().map(source_picker_label).collect();
+        let mut picker = PickerState {
+            is_open: true,
+            selected: Some(self.current_source_index),
+            ..PickerState::default()
+        };
+        if let Some(current) = options.get(self.current_source_index) {
+            picker.active.insert(current.clone());
+        }
+        picker.options = options;
+        self.board_picker = picker;
+    }
+
+    /// Switches to the source picked in the board picker. Returns whether a
+    /// different source is now loading.
+    fn switch_to_picked_board(&mut self) -> bool {
+        let picked = self.board_picker.selected;
+        self.board_picker.close();
+        match picked {
+            Some(index) if index < self.sources.len() && index != self.current_source_index => {
+                self.current_source_index = index;
+                self.loading_state = LoadingState::Loading;
+                true
+            }
+            _ => false,
+        }
+    }
+
     pub fn get_selected_item(&self) -> Option<&WorkItem> {
         let selected_index = self.list_view_state.list_state.selected()?;
-        self.get_filtered_items().get(selected_index).copied()
+        match self.list_rows().into_iter().nth(selected_index)? {
+            ListRow::Item(item) => Some(item),
+            ListRow::Group { .. } => None,
+        }
     }
 
-    pub fn current_title(&self) -> String {
-        self.current_source().title.clone()
+    /// Assignee of the group whose header is selected.
+    fn selected_group(&self) -> Option<String> {
+        let selected_index = self.list_view_state.list_state.selected()?;
+        match self.list_rows().into_iter().nth(selected_index)? {
+            ListRow::Group { assignee, .. } => Some(assignee),
+            ListRow::Item(_) => None,
+        }
     }
 
-    pub fn clamp_selection(&mut self) {
-        let item_count = self.get_filtered_items().len();
+    /// Expands or collapses the group whose header is selected; false when
+    /// an item is selected instead.
+    fn toggle_selected_group(&mut self) -> bool {
+        let Some(assignee) = self.selected_group() else {
+            return false;
+        };
+        let collapsed = &mut self.list_view_state.collapsed_groups;
+        if !collapsed.remove(&assignee) {
+            collapsed.insert(assignee);
+        }
+        self.clamp_selection();
+        true
+    }
 
-        if item_count == 0 {
-            self.list_view_state.list_state.select(None);
+    /// Collapses every group to its header, keeping the group of the
+    /// selection selected.
+    fn collapse_all_groups(&mut self) {
+        if !self.list_view_state.group_by_assignee {
+            self.set_status_message("Group by assignee to collapse groups");
+            return;
+        }
+        let selected_group = self
+            .get_selected_item()
+            .map(|item| item.assigned_to.clone())
+            .or_else(|| self.selected_group());
+        let assignees: Vec<String> = self
+            .get_filtered_items()
+            .into_iter()
+            .map(|item| item.assigned_to.clone())
+            .collect();
+        self.list_view_state.collapsed_groups.extend(assignees);
+        self.select_group_header(selected_group);
+    }
+
+    fn expand_all_groups(&mut self) {
+        if !self.list_view_state.group_by_assignee {
+            self.set_status_message("Group by assignee to expand groups");
             return;
         }
+        let selected_id = self.get_selected_item().map(|item| item.id);
+        let selected_group = self.selected_group();
+        self.list_view_state.collapsed_groups.clear();
+        match selected_id {
+            Some(item_id) => self.select_item_by_id(item_id),
+            None => self.select_group_header(selected_group),
+        }
+    }
 
-        if let Some(current_index) = self.list_view_state.list_state.selected() {
-            if current_index >= item_count {
-                self.list_view_state.list_state.select(Some(item_count - 1));
+    fn select_group_header(&mut self, assignee: Option<String>) {
+        let index = assignee.and_then(|assignee| {
+            self.list_rows().iter().position(
+                |row| matches!(row, ListRow::Group { assignee: name, .. } if *name == assignee),
+            )
+        });
+        if index.is_some() {
+            self.list_view_state.list_state.select(index);
+        }
+        self.clamp_selection();
+    }
+
+    fn toggle_group_by_assignee(&mut self) {
+        let selected_id = self.get_selected_item().map(|item| item.id);
+        self.list_view_state.group_by_assignee = !self.list_view_state.group_by_assignee;
+        self.list_view_state.list_state.select(None);
+        self.clamp_selection();
+        if let Some(item_id) = selected_id {
+            self.select_item_by_id(item_id);
+        }
+    }
+
+    /// The list as shown: the filtered items, grouped by assignee with a
+    /// header per group when grouping is on. Unassigned items come first and
+    /// collapsed groups only contribute their header.
+    pub fn list_rows(&self) -> Vec<ListRow<'_>> {
+        let items = self.get_filtered_items();
+        if !self.list_view_state.group_by_assignee {
+            return items.into_iter().map(ListRow::Item).collect();
+        }
+
+        // The empty name of unassigned items sorts before everyone else.
+        let mut groups: BTreeMap<&str, Vec<&WorkItem>> = BTreeMap::new();
+        for item in items {
+            groups
+                .entry(item.assigned_to.as_str())
+                .or_default()
+                .push(item);
+        }
+        let mut rows = Vec::new();
+        for (assignee, members) in groups {
+            let collapsed = self.list_view_state.collapsed_groups.contains(assignee);
+            rows.push(ListRow::Group {
+                assignee: assignee.to_string(),
+                count: members.len(),
+                collapsed,
+            });
+            if !collapsed {
+                rows.extend(members.into_iter().map(ListRow::Item));
             }
-        } else {
-            self.list_view_state.list_state.select(Some(0));
         }
+        rows
     }
 
-    pub fn get_filtered_items(&self) -> Vec<&WorkItem> {
-        self.items
-            .iter()
-            .filter(|item| {
-                if self.list_view_state.assigned_to_me_filter_on {
-                    if !item.assigned_to.contains(&self.me) {
-                        return false;
-                    }
-                }
+    /// How the last reload changed the item while its marker is shown, and
+    /// whether the marker has started to fade.
+    pub fn recent_change(&self, item: &WorkItem) -> Option<(ItemChange, bool)> {
+        let elapsed = self.recent_changes_at?.elapsed();
+        if elapsed >= CHANGE_MARKER_TTL {
+            return None;
+        }
+        let change = self.recent_changes.get(&item.id)?;
+        Some((*change, elapsed >= CHANGE_MARKER_TTL / 2))
+    }
 
-                if !self.list_view_state.type_picker.active.is_empty()
-                    && !self
-                        .list_view_state
-                        .type_picker
-                        .active
-                        .contains(&item.work_item_type)
-                {
-                    return false;
-                }
+    pub fn set_status_message(&mut self, text: impl Into<String>) {
+        self.status_message = Some(StatusMessage {
+            text: text.into(),
+            shown_at: Instant::now(),
+        });
+    }
 
-                if !self.list_view_state.filter_query.is_empty() {
-                    let query = self.list_view_state.filter_query.to_lowercase();
-                    let id_match = item.id.to_string().contains(&query);
-                    let title_match = item.title.to_lowercase().contains(&query);
-                    return id_match || title_match;
-                }
-                true
-            })
-            .collect()
+    /// Keeps the error for the error log, dropping the oldest once full.
+    pub fn log_error(&mut self, message: impl Into<String>) {
+        let entries = &mut self.error_log.entries;
+        if entries.len() == ERROR_LOG_CAPACITY {
+            entries.pop_front();
+        }
+        entries.push_back(LoggedError {
+            message: message.into(),
+            at: SystemTime::now(),
+        });
     }
 
-    pub fn toggle_assigned_to_me_filter(&mut self) {
-        self.list_view_state.assigned_to_me_filter_on =
-            !self.list_view_state.assigned_to_me_filter_on;
-        self.list_view_state.is_list_details_hover_visible = false;
-        self.list_view_state
-            .list_state
-            .select(self.get_filtered_items().first().map(|_| 0));
+    fn scroll_error_log(&mut self, delta: i16) {
+        self.error_log.scroll = self.error_log.scroll.saturating_add_signed(delta);
     }
 
-    pub fn navigate_list(&mut self, direction: isize) {
-        let count = self.get_filtered_items().len();
-        if count == 0 {
+    pub fn active_status_message(&self) -> Option<&str> {
+        self.status_message
+            .as_ref()
+            .filter(|message| message.shown_at.elapsed() < STATUS_MESSAGE_TTL)
+            .map(|message| message.text.as_str())
+            .or(self.refresh_summary.as_deref())
+    }
+
+    fn refresh_selected_item(&mut self) {
+        if self.detail_view_state.refresh_receiver.is_some() {
             return;
         }
-        let current = self.list_view_state.list_state.selected().unwrap_or(0) as isize;
-        let next = (current + direction).clamp(0, count as isize - 1);
-        self.list_view_state.list_state.select(Some(next as usize));
+        let Some(item_id) = self.get_selected_item().map(|item| item.id) else {
+            return;
+        };
+        let Some(source) = self.selected_item_source() else {
+            return;
+        };
+        let (tx, rx) = oneshot::channel();
+        tokio::spawn(async move {
+            let result = get_items(&source.organization, &source.project, vec![item_id as i32])
+                .await
+                .map(|fetched| (item_id, fetched));
+            let _ = tx.send(result);
+        });
+        self.detail_view_state.refresh_receiver = Some(rx);
+        self.set_status_message(format!("Refreshing #{}...", item_id));
     }
 
-    fn clamp_active_field(edit_state: &mut DetailEditState) {
-        match edit_state.active_field {
-            DetailField::Title => {}
-            DetailField::Dynamic(idx) => {
-                let total = edit_state.visible_fields.len();
-                if total == 0 {
-                    edit_state.active_field = DetailField::Title;
-                } else if idx >= total {
-                    edit_state.active_field = DetailField::Dynamic(total - 1);
+    fn poll_refresh_completion(&mut self) {
+        if let Some(receiver) = self.detail_view_state.refresh_receiver.as_mut() {
+            use tokio::sync::oneshot::error::TryRecvError;
+
+            match receiver.try_recv() {
+                Ok(Ok((item_id, fetched))) => {
+                    self.detail_view_state.refresh_receiver = None;
+                    let Some(mut updated) = fetched.into_iter().find(|item| item.id == item_id)
+                    else {
+                        self.set_status_message(format!("Item #{} was not returned", item_id));
+                        return;
+                    };
+                    if let Some(current) = self.items.iter_mut().find(|item| item.id == item_id) {
+                        // The merged list's board tag isn't part of the response.
+                        updated.board = current.board.take();
+                        *current = updated;
+                    }
+                    self.update_cached_item(item_id);
+                    self.refresh_picker_options();
+                    // The opened item picks up the new values unless it is being
+                    // edited or saved; edits of other items are left alone.
+                    let selected = self.get_selected_item().map(|item| item.id) == Some(item_id);
+                    let detail = &mut self.detail_view_state;
+                    let idle = detail
+                        .edit_state
+                        .as_ref()
+                        .is_none_or(|state| !state.is_editing)
+                        && detail.save_receiver.is_none();
+                    if selected && idle {
+                        detail.edit_state = None;
+                        detail.save_status = SaveStatus::Idle;
+                    }
+                    self.clamp_selection();
+                    self.set_status_message(format!("Refreshed #{}", item_id));
+                }
+                Ok(Err(err)) => {
+                    self.detail_view_state.refresh_receiver = None;
+                    let message = format!("Failed to refresh: {}", err);
+                    self.log_error(message.clone());
+                    self.set_status_message(message);
+                }
+                Err(TryRecvError::Closed) => {
+                    self.detail_view_state.refresh_receiver = None;
+                    self.set_status_message("Refresh was cancelled");
                 }
+                Err(TryRecvError::Empty) => {}
             }
         }
     }
 
-    fn active_picker(edit_state: &DetailEditState) -> Option<&PickerState> {
-        if let DetailField::Dynamic(idx) = edit_state.active_field {
-            edit_state
-                .visible_fields
-                .get(idx)
-                .and_then(|field| field.picker.as_ref())
-        } else {
-            None
-        }
+    /// Identity used by the assigned-to-me filter for the active source.
+    /// `me` of the current board, or the signed in user when not configured.
+    pub fn current_me(&self) -> &str {
+        self.source_me(self.current_source())
     }
 
-    fn active_picker_mut(edit_state: &mut DetailEditState) -> Option<&mut PickerState> {
-        if let DetailField::Dynamic(idx) = edit_state.active_field {
-            edit_state
-                .visible_fields
-                .get_mut(idx)
-                .and_then(|field| field.picker.as_mut())
+    /// The configured `me` of a source, or the signed in user of its organization.
+    fn source_me<'a>(&'a self, source: &'a SourceEntry) -> &'a str {
+        if source.me.is_empty() {
+            self.resolved_me
+                .get(&source.organization)
+                .map_or("", String::as_str)
         } else {
-            None
+            &source.me
         }
     }
 
-    fn apply_active_picker_selection(edit_state: &mut DetailEditState) {
-        if let DetailField::Dynamic(idx) = edit_state.active_field {
-            if let Some(field) = edit_state.visible_fields.get_mut(idx) {
-                if let Some(picker) = field.picker.as_mut() {
-                    if let Some(selected) = picker.selected {
-                        field.select_value(selected);
-                    }
-                }
-            }
+    /// Whether the item is assigned to `me` or to one of `me_aliases` of its board.
+    pub fn is_assigned_to_me(&self, item: &WorkItem) -> bool {
+        let source = self
+            .item_source(item)
+            .unwrap_or_else(|| self.current_source());
+        is_assigned_to_any(item, self.source_me(source), &source.me_aliases)
+    }
+
+    /// Counts of every loaded item, ignoring filters.
+    pub fn item_summary(&self) -> ItemSummary {
+        let mut by_state: BTreeMap<String, usize> = BTreeMap::new();
+        for item in &self.items {
+            *by_state
+                .entry(item.display_state().to_string())
+                .or_default() += 1;
+        }
+        ItemSummary {
+            total: self.items.len(),
+            by_state: by_state.into_iter().collect(),
+            assigned_to_me: self
+                .items
+                .iter()
+                .filter(|item| self.is_assigned_to_me(item))
+                .count(),
         }
     }
 
-    fn rebuild_edit_state_from_item(
-        item: &WorkItem,
-        existing_fields: &[VisibleField],
-    ) -> DetailEditState {
-        let mut new_state = DetailEditState::new_from_item(item);
-        new_state.visible_fields = existing_fields
-            .iter()
-            .map(|field| {
-                let value = item
-                    .fields
-                    .get(&field.reference)
-                    .cloned()
-                    .unwrap_or_default();
-                let allowed_values = field.picker.as_ref().map(|picker| picker.options.clone());
-                VisibleField::with_value(
-                    field.label.clone(),
-                    field.reference.clone(),
-                    value,
-                    allowed_values,
-                )
-            })
-            .collect();
-        App::clamp_active_field(&mut new_state);
-        new_state
+    /// Whether allowed values for the type's fields could not be loaded, in
+    /// which case every field falls back to free-text editing.
+    pub fn field_metadata_missing(&self, item: &WorkItem) -> bool {
+        self.type_fields(item).is_none()
     }
 
-    fn cancel_edit(&mut self) {
-        self.detail_view_state.save_receiver = None;
-        if let Some(state) = self.detail_view_state.edit_state.as_ref() {
-            if state.is_editing {
-                self.detail_view_state.edit_state = None;
-                self.detail_view_state.save_status = SaveStatus::Idle;
-            }
+    async fn retry_field_metadata(&mut self) {
+        let Some(item) = self.get_selected_item() else {
+            return;
+        };
+        let work_item_type = item.work_item_type.clone();
+        let (organization, project) = self.item_project(item);
+        let (organization, project) = (organization.to_string(), project.to_string());
+        let fetched = build_field_metadata_cache(
+            &organization,
+            &project,
+            vec![work_item_type.clone()],
+            RefreshPolicy::Full,
+        )
+        .await;
+
+        if fetched.is_empty() {
+            self.set_status_message(format!(
+                "Allowed values for {} are still unavailable",
+                work_item_type
+            ));
+        } else {
+            self.field_meta_cache
+                .extend(fetched.into_iter().map(|(display_name, fields)| {
+                    (
+                        (organization.clone(), project.clone(), display_name),
+                        fields,
+                    )
+                }));
+            self.reset_inactive_edit_state();
+            self.set_status_message(format!("Loaded allowed values for {}", work_item_type));
         }
     }
 
-    fn begin_edit(&mut self) {
-        self.detail_view_state.save_receiver = None;
-        self.detail_view_state.save_status = SaveStatus::Idle;
-        if let Some(state) = self.detail_view_state.edit_state.as_mut() {
-            state.is_editing = true;
-            state.active_field = DetailField::Title;
-            App::clamp_active_field(state);
-        } else if let Some(item) = self.get_selected_item() {
-            let mut state = DetailEditState::new_from_item(item);
-            state.is_editing = true;
-            self.detail_view_state.edit_state = Some(state);
+    /// Remaining and completed work of the shown iteration. Backlogs span
+    /// many sprints, so they get no rollup.
+    pub fn iteration_work_rollup(&self) -> Option<WorkRollup> {
+        match self.current_source().kind {
+            SourceKind::Iteration(_) => WorkRollup::of(
+                &self.items,
+                &self.remaining_work_field,
+                &self.completed_work_field,
+            ),
+            SourceKind::Backlog(_) | SourceKind::MyWork(_) => None,
         }
     }
 
-    fn apply_typing(&mut self, c: char) {
+    /// Connection details shown while the current source loads, so a wrong
+    /// organization or account is noticed before any data appears.
+    pub fn loading_details(&self) -> Vec<String> {
+        if !self.startup_banner {
+            return Vec::new();
+        }
+        let source = self.current_source();
+        let signed_in_as = self
+            .signed_in_users
+            .get(&source.organization)
+            .map_or("checking...", String::as_str);
+        vec![
+            format!(
+                "Organization: {}, project: {}, team: {}",
+                source.organization, source.project, source.team
+            ),
+            format!("Authentication: {}", auth_method_label()),
+            format!("Signed in as: {}", signed_in_as),
+        ]
+    }
+
+    pub fn current_title(&self) -> String {
+        self.current_source().title.clone()
+    }
+
+    pub fn clamp_selection(&mut self) {
+        let item_count = self.list_rows().len();
+
+        if item_count == 0 {
+            self.list_view_state.list_state.select(None);
+            return;
+        }
+
+        if let Some(current_index) = self.list_view_state.list_state.selected() {
+            if current_index >= item_count {
+                self.list_view_state.list_state.select(Some(item_count - 1));
+            }
+        } else {
+            self.list_view_state.list_state.select(Some(0));
+        }
+    }
+
+    pub fn get_filtered_items(&self) -> Vec<&WorkItem> {
+        let mut items: Vec<&WorkItem> = self
+            .items
+            .iter()
+            .filter(|item| self.passes_filters(item, true))
+            .collect();
+        self.sort_items(&mut items);
+        items.sort_by_key(|item| !self.is_pinned(item));
+        items
+    }
+
+    /// Whether the item is shown with the active filters, optionally leaving
+    /// the search query out.
+    fn passes_filters(&self, item: &WorkItem, include_query: bool) -> bool {
+        if self.list_view_state.assigned_to_me_filter_on && !self.is_assigned_to_me(item) {
+            return false;
+        }
+
+        if self.list_view_state.hide_done && self.is_done(item) {
+            return false;
+        }
+
+        if !self.list_view_state.type_picker.active.is_empty()
+            && !self
+                .list_view_state
+                .type_picker
+                .active
+                .contains(&item.work_item_type)
+        {
+            return false;
+        }
+
+        if !self.list_view_state.column_picker.active.is_empty()
+            && !self
+                .list_view_state
+                .column_picker
+                .active
+                .contains(&item.board_column)
+        {
+            return false;
+        }
+
+        if let Some(sprint) = &self.list_view_state.current_sprint
+            && item.fields.get(ITERATION_PATH_FIELD) != Some(&sprint.path)
+        {
+            return false;
+        }
+
+        let active_tags = &self.list_view_state.tag_picker.active;
+        if !active_tags.is_empty() {
+            let has_tag = |tag: &String| item.tags.contains(tag);
+            let tags_match = if self.list_view_state.tag_match_all {
+                active_tags.iter().all(has_tag)
+            } else {
+                active_tags.iter().any(has_tag)
+            };
+            if !tags_match {
+                return false;
+            }
+        }
+
+        // Pinned items stay in sight while searching.
+        if include_query && !self.list_view_state.filter_query.is_empty() && !self.is_pinned(item) {
+            return item.matches_query(&self.list_view_state.filter_query);
+        }
+        true
+    }
+
+    /// Placeholder for an empty list, naming why it is empty and how to get
+    /// items back.
+    pub fn empty_list_message(&self) -> String {
+        if self.items.is_empty() {
+            return format!(
+                "This board has no items — press {} to reload",
+                self.keys.refresh
+            );
+        }
+
+        let state = &self.list_view_state;
+        if !state.filter_query.is_empty()
+            && self
+                .items
+                .iter()
+                .any(|item| self.passes_filters(item, false))
+        {
+            return format!(
+                "No items match \"{}\" — press Esc to clear the search",
+                state.filter_query
+            );
+        }
+
+        let mut filters: Vec<(&str, &str)> = Vec::new();
+        if state.assigned_to_me_filter_on {
+            filters.push(("assigned to me", &self.keys.assigned_to_me_filter));
+        }
+        if state.hide_done {
+            filters.push(("hide done", &self.keys.hide_done));
+        }
+        if state.current_sprint.is_some() {
+            filters.push(("current sprint", &self.keys.current_sprint_filter));
+        }
+        for (which, name, key) in [
+            (ListPicker::Type, "type", &self.keys.work_item_type_filter),
+            (ListPicker::Column, "column", &self.keys.board_column_filter),
+            (ListPicker::Tag, "tag", &self.keys.tag_filter),
+        ] {
+            if !state.picker(which).active.is_empty() {
+                filters.push((name, key));
+            }
+        }
+
+        match filters.as_slice() {
+            [(name, key)] => format!(
+                "All items are filtered out by the {} filter — press {} to change it",
+                name, key
+            ),
+            _ => format!(
+                "All items are filtered out by the {} filters — press {} to turn filters off",
+                filters
+                    .iter()
+                    .map(|(name, _)| *name)
+                    .collect::<Vec<_>>()
+                    .join(", "),
+                self.keys.bypass_filters
+            ),
+        }
+    }
+
+    pub fn is_done(&self, item: &WorkItem) -> bool {
+        self.done_states
+            .iter()
+            .any(|state| state.eq_ignore_ascii_case(&item.state))
+    }
+
+    /// Number of items currently hidden because they are in a done state.
+    pub fn hidden_done_count(&self) -> usize {
+        if !self.list_view_state.hide_done {
+            return 0;
+        }
+        self.items.iter().filter(|item| self.is_done(item)).count()
+    }
+
+    pub fn toggle_hide_done(&mut self) {
+        self.list_view_state.hide_done = !self.list_view_state.hide_done;
+        self.list_view_state.is_list_details_hover_visible = false;
+        self.clamp_selection();
+    }
+
+    /// Turns all filters off as a unit, or restores the ones turned off before.
+    pub fn toggle_filter_bypass(&mut self) {
+        let state = &mut self.list_view_state;
+        state.is_list_details_hover_visible = false;
+        if let Some(stash) = state.stashed_filters.take() {
+            state.filter_query = stash.filter_query;
+            state.assigned_to_me_filter_on = stash.assigned_to_me_filter_on;
+            state.hide_done = stash.hide_done;
+            state.type_picker.active = stash.active_types;
+            state.column_picker.active = stash.active_columns;
+            state.tag_picker.active = stash.active_tags;
+            state.current_sprint = stash.current_sprint;
+            self.set_status_message("Filters restored");
+        } else if state.has_active_filters() {
+            state.stashed_filters = Some(FilterStash {
+                filter_query: std::mem::take(&mut state.filter_query),
+                assigned_to_me_filter_on: std::mem::take(&mut state.assigned_to_me_filter_on),
+                hide_done: std::mem::take(&mut state.hide_done),
+                active_types: std::mem::take(&mut state.type_picker.active),
+                active_columns: std::mem::take(&mut state.column_picker.active),
+                active_tags: std::mem::take(&mut state.tag_picker.active),
+                current_sprint: state.current_sprint.take(),
+            });
+            self.set_status_message("Filters off");
+        } else {
+            self.set_status_message("No active filters");
+        }
+        self.clamp_selection();
+    }
+
+    /// Narrows a backlog down to the items in the team's current iteration,
+    /// or shows the whole backlog again.
+    async fn toggle_current_sprint_filter(&mut self) {
+        self.list_view_state.is_list_details_hover_visible = false;
+        if self.list_view_state.current_sprint.take().is_some() {
+            self.set_status_message("Showing the whole backlog");
+            self.clamp_selection();
+            return;
+        }
+        let source = self.current_source().clone();
+        if !matches!(source.kind, SourceKind::Backlog(_)) {
+            self.set_status_message("Current sprint only applies to backlogs");
+            return;
+        }
+        let iteration = IterationConfig {
+            title: None,
+            organization: source.organization,
+            project: source.project,
+            team: source.team,
+            iteration: CURRENT_ITERATION_MACRO.to_string(),
+            me: None,
+            me_aliases: None,
+            max_items: None,
+        };
+        // Iterations cached by older versions have no path yet.
+        let resolved = match iteration_id_for(&iteration, true).await {
+            Ok(resolved) if resolved.path.is_none() => iteration_id_for(&iteration, false).await,
+            resolved => resolved,
+        };
+        match resolved {
+            Ok(ResolvedIteration {
+                path: Some(path),
+                name,
+                ..
+            }) => {
+                let name = name.unwrap_or_else(|| path.clone());
+                self.set_status_message(format!("Showing {} only", name));
+                self.list_view_state.current_sprint = Some(CurrentSprint { name, path });
+                self.clamp_selection();
+            }
+            Ok(_) => self.set_status_message("The current sprint has no iteration path"),
+            Err(err) => {
+                self.log_error(format!("Failed to find the current sprint: {:#}", err));
+                self.set_status_message(format!(
+                    "Failed to find the current sprint: {}",
+                    summarize_error(&err)
+                ))
+            }
+        }
+    }
+
+    pub fn toggle_assigned_to_me_filter(&mut self) {
+        self.list_view_state.assigned_to_me_filter_on =
+            !self.list_view_state.assigned_to_me_filter_on;
+        self.list_view_state.is_list_details_hover_visible = false;
+        self.list_view_state
+            .list_state
+            .select(self.get_filtered_items().first().map(|_| 0));
+    }
+
+    /// Rows whose item matches the find query.
+    pub fn find_matches(&self) -> Vec<usize> {
+        let query = &self.list_view_state.find_query;
+        if query.is_empty() {
+            return Vec::new();
+        }
+        self.list_rows()
+            .iter()
+            .enumerate()
+            .filter_map(|(index, row)| match row {
+                ListRow::Item(item) if item.matches_query(query) => Some(index),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Selects the next (`1`) or previous (`-1`) match of the find query,
+    /// wrapping around the list. `0` keeps the selected row if it matches,
+    /// for jumping while the query is typed.
+    fn jump_to_match(&mut self, direction: isize) {
+        let matches = self.find_matches();
+        let state = &mut self.list_view_state;
+        if matches.is_empty() {
+            state.find_match = None;
+            if !state.find_query.is_empty() && !state.is_finding {
+                let text = format!("No match for \"{}\"", state.find_query);
+                self.set_status_message(text);
+            }
+            return;
+        }
+        let selected = state.list_state.selected().unwrap_or(0);
+        let index = match direction {
+            0 => matches.iter().position(|&row| row >= selected),
+            d if d > 0 => matches.iter().position(|&row| row > selected),
+            _ => matches.iter().rposition(|&row| row < selected),
+        }
+        .unwrap_or(if direction < 0 { matches.len() - 1 } else { 0 });
+        state.find_match = Some(index);
+        state.list_state.select(Some(matches[index]));
+    }
+
+    fn start_find(&mut self) {
+        let state = &mut self.list_view_state;
+        state.is_list_details_hover_visible = false;
+        state.is_finding = true;
+        state.find_query.clear();
+        state.find_match = None;
+    }
+
+    pub fn navigate_list(&mut self, direction: isize) {
+        let count = self.list_rows().len();
+        if count == 0 {
+            return;
+        }
+        let current = self.list_view_state.list_state.selected().unwrap_or(0) as isize;
+        let next = (current + direction).clamp(0, count as isize - 1);
+        self.list_view_state.list_state.select(Some(next as usize));
+        self.detail_view_state.horizontal_scroll = 0;
+    }
+
+    /// Scrolls the detail fields sideways when long lines aren't wrapped.
+    fn scroll_detail_horizontally(&mut self, delta: i16) {
+        if self.detail_wrap != DetailWrap::Scroll {
+            return;
+        }
+        let state = &mut self.detail_view_state;
+        state.horizontal_scroll = state.horizontal_scroll.saturating_add_signed(delta);
+    }
+
+    fn clamp_active_field(edit_state: &mut DetailEditState) {
+        match edit_state.active_field {
+            DetailField::Title => {}
+            DetailField::Dynamic(idx) => {
+                let total = edit_state.visible_fields.len();
+                if total == 0 {
+                    edit_state.active_field = DetailField::Title;
+                } else if idx >= total {
+                    edit_state.active_field = DetailField::Dynamic(total - 1);
+                }
+            }
+        }
+    }
+
+    fn active_picker(edit_state: &DetailEditState) -> Option<&PickerState> {
+        if let DetailField::Dynamic(idx) = edit_state.active_field {
+            edit_state
+                .visible_fields
+                .get(idx)
+                .and_then(|field| field.picker.as_ref())
+        } else {
+            None
+        }
+    }
+
+    fn active_picker_mut(edit_state: &mut DetailEditState) -> Option<&mut PickerState> {
+        if let DetailField::Dynamic(idx) = edit_state.active_field {
+            edit_state
+                .visible_fields
+                .get_mut(idx)
+                .and_then(|field| field.picker.as_mut())
+        } else {
+            None
+        }
+    }
+
+    fn apply_active_picker_selection(edit_state: &mut DetailEditState) {
+        if let DetailField::Dynamic(idx) = edit_state.active_field
+            && let Some(field) = edit_state.visible_fields.get_mut(idx)
+            && let Some(picker) = field.picker.as_mut()
+            && let Some(selected) = picker.selected
+        {
+            field.select_value(selected);
+        }
+    }
+
+    fn rebuild_edit_state_from_item(
+        item: &WorkItem,
+        existing_fields: &[VisibleField],
+        field_formats: &BTreeMap<String, FieldFormat>,
+    ) -> DetailEditState {
+        let mut new_state = DetailEditState::new_from_item(item);
+        new_state.visible_fields = existing_fields
+            .iter()
+            .map(|field| {
+                let value = item
+                    .edit_value(&field.reference, field_formats)
+                    .unwrap_or_default();
+                let allowed_values = field.picker.as_ref().map(|picker| picker.options.clone());
+                VisibleField::with_value(
+                    field.label.clone(),
+                    field.reference.clone(),
+                    value,
+                    allowed_values,
+                )
+            })
+            .collect();
+        App::clamp_active_field(&mut new_state);
+        new_state
+    }
+
+    fn cancel_edit(&mut self) {
+        self.detail_view_state.save_receiver = None;
+        if let Some(state) = self.detail_view_state.edit_state.as_ref()
+            && state.is_editing
+        {
+            self.detail_view_state.edit_state = None;
+            self.detail_view_state.save_status = SaveStatus::Idle;
+        }
+    }
+
+    fn begin_edit(&mut self) {
+        self.detail_view_state.save_receiver = None;
+        self.detail_view_state.save_status = SaveStatus::Idle;
+        if let Some(state) = self.detail_view_state.edit_state.as_mut() {
+            state.is_editing = true;
+            state.active_field = DetailField::Title;
+            App::clamp_active_field(state);
+        } else if let Some(item) = self.get_selected_item() {
+            let mut state = DetailEditState::new_from_item(item);
+            state.is_editing = true;
+            self.detail_view_state.edit_state = Some(state);
+        }
+        self.detail_view_state.draft_prompt =
+            self.selected_draft_key().and_then(|key| read_draft(&key));
+    }
+
+    fn selected_draft_key(&self) -> Option<DraftKey> {
+        self.get_selected_item().map(|item| DraftKey {
+            organization: self.item_project(item).0.to_string(),
+            item_id: item.id,
+        })
+    }
+
+    /// Stores the edit in progress locally and leaves edit mode without
+    /// sending anything to ADO.
+    fn save_draft(&mut self) {
+        let (Some(key), Some(state)) = (
+            self.selected_draft_key(),
+            self.detail_view_state.edit_state.as_ref(),
+        ) else {
+            return;
+        };
+        let draft = EditDraft {
+            title: state.title.clone(),
+            fields: state
+                .visible_fields
+                .iter()
+                .map(|field| (field.reference.clone(), field.value.clone()))
+                .collect(),
+        };
+        match write_draft(&key, &draft) {
+            Ok(()) => {
+                self.cancel_edit();
+                self.set_status_message(format!("Draft of #{} saved", key.item_id));
+            }
+            Err(err) => {
+                self.detail_view_state.save_status =
+                    SaveStatus::Failed(format!("Failed to save draft: {}", err))
+            }
+        }
+    }
+
+    /// Applies the pending draft to the edit state, or deletes it when the
+    /// user chose to start from the item as it is in ADO.
+    fn resolve_draft_prompt(&mut self, restore: bool) {
+        let Some(draft) = self.detail_view_state.draft_prompt.take() else {
+            return;
+        };
+        if !restore {
+            if let Some(key) = self.selected_draft_key() {
+                let _ = remove_draft(&key);
+            }
+            return;
+        }
+        if let Some(state) = self.detail_view_state.edit_state.as_mut() {
+            state.title = draft.title;
+            for (reference, value) in draft.fields {
+                if let Some(field) = state
+                    .visible_fields
+                    .iter_mut()
+                    .find(|field| field.reference == reference)
+                {
+                    if let Some(picker) = field.picker.as_mut() {
+                        picker.set_selected_to_value(&value);
+                    }
+                    field.value = value;
+                }
+            }
+        }
+    }
+
+    fn apply_typing(&mut self, c: char) {
         if let Some(state) = self.detail_view_state.edit_state.as_mut() {
             if !state.is_editing {
                 return;
             }
-            Self::clamp_active_field(state);
-            match state.active_field {
-                DetailField::Title => state.title.push(c),
-                DetailField::Dynamic(idx) => {
-                    if let Some(field) = state.visible_fields.get_mut(idx) {
-                        let picker_has_options = field
-                            .picker
-                            .as_ref()
-                            .map(|p| !p.options.is_empty())
-                            .unwrap_or(false);
-                        if !picker_has_options {
-                            field.value.push(c);
-                        }
+            Self::clamp_active_field(state);
+            match state.active_field {
+                DetailField::Title => state.title.push(c),
+                DetailField::Dynamic(idx) => {
+                    if let Some(field) = state.visible_fields.get_mut(idx) {
+                        let picker_has_options = field
+                            .picker
+                            .as_ref()
+                            .map(|p| !p.options.is_empty())
+                            .unwrap_or(false);
+                        if !picker_has_options {
+                            field.value.push(c);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    fn move_active_picker(&mut self, direction: isize) {
+        if let Some(state) = self.detail_view_state.edit_state.as_mut() {
+            if !state.is_editing {
+                return;
+            }
+            Self::clamp_active_field(state);
+            if let Some(picker) = App::active_picker_mut(state) {
+                picker.move_selection(direction);
+            }
+        }
+    }
+
+    fn select_active_picker_value(&mut self) {
+        if let Some(state) = self.detail_view_state.edit_state.as_mut() {
+            if !state.is_editing {
+                return;
+            }
+            Self::clamp_active_field(state);
+            App::apply_active_picker_selection(state);
+            let picked_state = matches!(
+                state.active_field,
+                DetailField::Dynamic(idx)
+                    if state.visible_fields.get(idx).is_some_and(|field| field.reference == STATE_FIELD)
+            );
+            if picked_state {
+                self.follow_state_with_reason();
+            }
+        }
+    }
+
+    /// After a new state is picked, offers the work item type's reasons as a
+    /// follow-up picker right below the state, or applies the only one. Going
+    /// back to the loaded state restores the loaded reason.
+    fn follow_state_with_reason(&mut self) {
+        let Some(item) = self.get_selected_item() else {
+            return;
+        };
+        let reasons: Vec<String> = self
+            .type_fields(item)
+            .and_then(|fields| {
+                fields
+                    .iter()
+                    .find(|field| field.reference_name == REASON_FIELD)
+            })
+            .map(|field| field.allowed_values.clone())
+            .unwrap_or_default();
+        let loaded_reason = item.fields.get(REASON_FIELD).cloned().unwrap_or_default();
+        let Some(edit_state) = self.detail_view_state.edit_state.as_mut() else {
+            return;
+        };
+        let fields = &mut edit_state.visible_fields;
+        let Some(state_idx) = fields
+            .iter()
+            .position(|field| field.reference == STATE_FIELD)
+        else {
+            return;
+        };
+        let new_state = fields[state_idx].value.clone();
+        let state_changed = fields[state_idx].is_modified();
+        let reason_idx = match fields
+            .iter()
+            .position(|field| field.reference == REASON_FIELD)
+        {
+            Some(idx) => idx,
+            None if state_changed => {
+                fields.insert(
+                    state_idx + 1,
+                    VisibleField::with_value(
+                        "Reason".to_string(),
+                        REASON_FIELD.to_string(),
+                        loaded_reason,
+                        Some(reasons.clone()),
+                    ),
+                );
+                state_idx + 1
+            }
+            None => return,
+        };
+        let reason = &mut fields[reason_idx];
+        if !state_changed {
+            reason.value = reason.original_value.clone();
+            if let Some(picker) = reason.picker.as_mut() {
+                picker.set_selected_to_value(&reason.value);
+            }
+            return;
+        }
+        match reasons.as_slice() {
+            [] => self.set_status_message(format!(
+                "No reasons known for {}; Azure DevOps applies the default",
+                new_state
+            )),
+            [only] => {
+                let only = only.clone();
+                reason.select_value(0);
+                self.set_status_message(format!("Reason set to {}", only));
+            }
+            _ => {
+                edit_state.active_field = DetailField::Dynamic(reason_idx);
+                self.set_status_message(format!("Pick a reason for {}", new_state));
+            }
+        }
+    }
+
+    /// Runs a named command, as triggered by its key binding or the command palette.
+    pub async fn execute_command(&mut self, command: Command) -> CommandOutcome {
+        match command {
+            Command::JumpToTop => {
+                self.list_view_state.is_list_details_hover_visible = false;
+                self.jump_to_start();
+            }
+            Command::JumpToEnd => {
+                self.list_view_state.is_list_details_hover_visible = false;
+                self.jump_to_end();
+            }
+            Command::Find => self.start_find(),
+            Command::FindNext => {
+                self.list_view_state.is_list_details_hover_visible = false;
+                self.jump_to_match(1);
+            }
+            Command::FindPrevious => {
+                self.list_view_state.is_list_details_hover_visible = false;
+                self.jump_to_match(-1);
+            }
+            Command::GlobalSearch => {
+                self.list_view_state.is_list_details_hover_visible = false;
+                self.global_search.open();
+            }
+            Command::Search => {
+                self.list_view_state.is_list_details_hover_visible = false;
+                self.list_view_state.is_filtering = true;
+                self.list_view_state.filter_query.clear();
+                self.clamp_selection();
+            }
+            Command::Next => {
+                self.list_view_state.is_list_details_hover_visible = false;
+                self.navigate_list(1);
+            }
+            Command::Previous => {
+                self.list_view_state.is_list_details_hover_visible = false;
+                self.navigate_list(-1);
+            }
+            Command::NextBoard => {
+                self.list_view_state.is_list_details_hover_visible = false;
+                self.next_source();
+                return CommandOutcome::Return;
+            }
+            Command::PreviousBoard => {
+                self.list_view_state.is_list_details_hover_visible = false;
+                self.previous_source();
+                return CommandOutcome::Return;
+            }
+            Command::PickBoard => {
+                self.list_view_state.is_list_details_hover_visible = false;
+                self.open_board_picker();
+            }
+            Command::Hover => {
+                self.mark_selected_seen();
+                self.show_hover(HoverContent::Details);
+            }
+            Command::PeekTitle => self.show_hover(HoverContent::Title),
+            Command::ToggleSummary => {
+                self.list_view_state.show_summary = !self.list_view_state.show_summary
+            }
+            Command::PinDetails => {
+                let state = &mut self.list_view_state;
+                state.details_pane_pinned = !state.details_pane_pinned;
+                state.is_list_details_hover_visible = false;
+            }
+            Command::Open => {
+                self.mark_selected_seen();
+                self.open_item();
+            }
+            Command::OpenBoard => self.open_board(),
+            Command::CopyBranchName => self.copy_branch_name(),
+            Command::OpenRepo => self.open_linked_repo().await,
+            Command::OpenPullRequest => self.open_linked_pull_request(),
+            Command::AssignedToMeFilter => self.toggle_assigned_to_me_filter(),
+            Command::WorkItemTypeFilter => self.toggle_filter_menu(ListPicker::Type),
+            Command::CycleTypeFilter => self.cycle_type_filter(),
+            Command::CurrentSprintFilter => self.toggle_current_sprint_filter().await,
+            Command::BoardColumnFilter => self.toggle_column_filter_menu(),
+            Command::TagFilter => self.toggle_tag_filter_menu(),
+            Command::HideDone => self.toggle_hide_done(),
+            Command::GroupByAssignee => self.toggle_group_by_assignee(),
+            Command::CollapseAllGroups => self.collapse_all_groups(),
+            Command::ExpandAllGroups => self.expand_all_groups(),
+            Command::BypassFilters => self.toggle_filter_bypass(),
+            Command::Refresh => {
+                if !self.preserve_filters_on_refresh {
+                    self.list_view_state.clear_filters();
+                }
+                self.refresh_policy = RefreshPolicy::Normal;
+                self.loading_state = LoadingState::Loading;
+                return CommandOutcome::Return;
+            }
+            Command::HardRefresh => {
+                self.list_view_state.clear_filters();
+                self.refresh_policy = RefreshPolicy::Normal;
+                self.loading_state = LoadingState::Loading;
+                return CommandOutcome::Return;
+            }
+            Command::FullRefresh => {
+                self.refresh_policy = RefreshPolicy::Full;
+                self.loading_state = LoadingState::Loading;
+                return CommandOutcome::Return;
+            }
+            Command::EditConfig => self.config_edit_requested = true,
+            Command::ReloadConfig => match self.reload_config() {
+                Ok(()) => return CommandOutcome::Return,
+                Err(err) => self.set_status_message(format!("Configuration not reloaded: {err:#}")),
+            },
+            Command::EditItem => {
+                self.ensure_detail_state_for_selected_item().await;
+                self.begin_edit();
+            }
+            Command::RefreshItem => {
+                self.list_view_state.is_list_details_hover_visible = false;
+                self.refresh_selected_item();
+            }
+            Command::RetryFieldMetadata => self.retry_field_metadata().await,
+            Command::Reassign => self.open_assign_picker().await,
+            Command::AssignToMe => self.assign_to_me(),
+            Command::Follow => self.toggle_follow(),
+            Command::CloneItem => self.request_clone(),
+            Command::RankUp => self.move_rank(-1),
+            Command::RankDown => self.move_rank(1),
+            Command::MoveMode => self.toggle_move_mode(),
+            Command::CycleSort => self.change_sort(|sort| {
+                sort.primary = sort.primary.next();
+                sort.descending = false;
+            }),
+            Command::ReverseSort => self.change_sort(|sort| sort.descending = !sort.descending),
+            Command::NarrowList => self.resize_list(-(LIST_WIDTH_STEP as i16)),
+            Command::WidenList => self.resize_list(LIST_WIDTH_STEP as i16),
+            Command::CollapseDescription => {
+                self.toggle_field_collapse(DESCRIPTION_FIELD, "Description")
+            }
+            Command::CollapseAcceptanceCriteria => {
+                self.toggle_field_collapse(ACCEPTANCE_CRITERIA_FIELD, "Acceptance criteria")
+            }
+            Command::RawDescription => {
+                let state = &mut self.detail_view_state;
+                state.show_raw_description = !state.show_raw_description;
+            }
+            Command::InspectFields => self.inspect_fields(),
+            Command::ErrorLog => {
+                self.list_view_state.is_list_details_hover_visible = false;
+                self.error_log.is_open = true;
+                self.error_log.scroll = 0;
+            }
+            Command::TogglePin => self.toggle_pin(),
+            Command::Help => self.showing_help = !self.showing_help,
+            Command::Quit => return self.request_quit(),
+        }
+        CommandOutcome::Continue
+    }
+
+    fn resize_list(&mut self, delta: i16) {
+        let percent = (self.list_width_percent as i16 + delta)
+            .clamp(MIN_LIST_WIDTH_PERCENT as i16, MAX_LIST_WIDTH_PERCENT as i16)
+            as u16;
+        if percent == self.list_width_percent {
+            return;
+        }
+        self.list_width_percent = percent;
+        match crate::config::store_list_width(self.config_path.as_deref(), percent) {
+            Ok(()) => self.set_status_message(format!("List width {}%", percent)),
+            Err(err) => self.set_status_message(format!(
+                "List width {}% (not saved: {})",
+                percent,
+                summarize_error(&err)
+            )),
+        }
+    }
+
+    fn copy_branch_name(&mut self) {
+        let Some(branch_name) = self
+            .get_selected_item()
+            .map(|item| item.branch_name(&self.branch_name_template))
+        else {
+            return;
+        };
+        match crate::clipboard::copy_to_clipboard(&branch_name) {
+            Ok(()) => self.set_status_message(format!("Copied {}", branch_name)),
+            Err(err) => self.set_status_message(format!("Failed to copy branch name: {}", err)),
+        }
+    }
+
+    /// Opens the local checkout of the selected item's first linked branch
+    /// in the configured editor, checking the branch out when enabled.
+    async fn open_linked_repo(&mut self) {
+        let Some(item) = self.get_selected_item().cloned() else {
+            return;
+        };
+        let Some(linked) = item.linked_branches.first() else {
+            self.set_status_message(format!("#{} has no linked branches", item.id));
+            return;
+        };
+
+        let (organization, project) = self.item_project(&item);
+        let (organization, project) = (organization.to_string(), project.to_string());
+        let name_key = (organization.clone(), linked.repository_id.clone());
+        let repository_name = match self.repository_names.get(&name_key) {
+            Some(name) => name.clone(),
+            None => {
+                match fetch_repository_name(&organization, &project, &linked.repository_id).await {
+                    Ok(name) => {
+                        self.repository_names.insert(name_key, name.clone());
+                        name
+                    }
+                    Err(err) => {
+                        let message = format!("Failed to look up repository: {}", err);
+                        self.log_error(message.clone());
+                        self.set_status_message(message);
+                        return;
+                    }
+                }
+            }
+        };
+
+        let Some(path) = self.repo_paths.get(&repository_name).cloned() else {
+            self.set_status_message(format!("No repo_paths entry for {}", repository_name));
+            return;
+        };
+
+        let mut warning = None;
+        if self.checkout_linked_branch {
+            match crate::repo::is_dirty(&path) {
+                Ok(true) => {
+                    warning = Some(format!(
+                        "working tree is dirty, {} not checked out",
+                        linked.branch
+                    ));
+                }
+                Ok(false) => {
+                    if let Err(err) = crate::repo::checkout(&path, &linked.branch) {
+                        warning = Some(err.to_string());
+                    }
+                }
+                Err(err) => warning = Some(err.to_string()),
+            }
+        }
+
+        if let Err(err) = crate::repo::open_in_editor(&self.repo_editor, &path) {
+            self.set_status_message(format!("Failed to start {}: {}", self.repo_editor, err));
+            return;
+        }
+        match warning {
+            Some(warning) => {
+                self.set_status_message(format!("Opened {} ({})", repository_name, warning))
+            }
+            None => self.set_status_message(format!("Opened {}", repository_name)),
+        }
+    }
+
+    pub fn has_unsaved_edits(&self) -> bool {
+        self.detail_view_state
+            .edit_state
+            .as_ref()
+            .is_some_and(|state| state.is_editing)
+    }
+
+    /// Closes one level of what is open over the list, innermost first: an
+    /// open picker or the hover, then the search and find queries, then the
+    /// "assigned to me" filter, then the opened item. Returns false when the
+    /// list was already clean, which is when quitting goes ahead.
+    fn close_innermost(&mut self) -> bool {
+        let state = &mut self.list_view_state;
+        if state.open_picker().is_some() {
+            self.close_picker();
+            return true;
+        }
+        if state.is_list_details_hover_visible {
+            state.is_list_details_hover_visible = false;
+            return true;
+        }
+        if !state.filter_query.is_empty() || !state.find_query.is_empty() {
+            state.filter_query.clear();
+            state.find_query.clear();
+            state.find_match = None;
+            self.clamp_selection();
+            return true;
+        }
+        if state.assigned_to_me_filter_on {
+            self.toggle_assigned_to_me_filter();
+            self.clamp_selection();
+            return true;
+        }
+        self.detail_view_state.edit_state.take().is_some()
+    }
+
+    /// Quits right away unless `confirm_quit` is set or an edit would be
+    /// lost, in which case a confirmation prompt is shown first.
+    fn request_quit(&mut self) -> CommandOutcome {
+        if self.confirm_quit || self.has_unsaved_edits() {
+            self.confirming_quit = true;
+            CommandOutcome::Continue
+        } else {
+            CommandOutcome::Return
+        }
+    }
+
+    fn show_hover(&mut self, content: HoverContent) {
+        self.list_view_state.hover_content = content;
+        self.list_view_state.is_list_details_hover_visible = true;
+    }
+
+    async fn activate_selected_item(&mut self) {
+        if self.toggle_selected_group() || self.get_selected_item().is_none() {
+            return;
+        }
+        self.mark_selected_seen();
+        match self.on_enter {
+            OnEnter::Detail => self.show_hover(HoverContent::Details),
+            OnEnter::Edit => {
+                self.list_view_state.is_list_details_hover_visible = false;
+                self.ensure_detail_state_for_selected_item().await;
+                self.begin_edit();
+            }
+            OnEnter::SplitFocus => {
+                self.list_view_state.is_list_details_hover_visible = false;
+                self.ensure_detail_state_for_selected_item().await;
+            }
+        }
+    }
+
+    async fn open_assign_picker(&mut self) {
+        let Some(item) = self.get_selected_item().cloned() else {
+            return;
+        };
+        let Some(source) = self.selected_item_source() else {
+            return;
+        };
+        let cache_key = (
+            source.organization.clone(),
+            source.project.clone(),
+            source.team.clone(),
+        );
+
+        let members = match self.team_members_cache.get(&cache_key) {
+            Some(members) => members.clone(),
+            None => {
+                match fetch_team_members(&source.organization, &source.project, &source.team).await
+                {
+                    Ok(members) => {
+                        self.team_members_cache.insert(cache_key, members.clone());
+                        members
+                    }
+                    Err(err) => {
+                        let message = format!("Failed to load team members: {}", err);
+                        self.log_error(message.clone());
+                        self.detail_view_state.save_status = SaveStatus::Failed(message);
+                        return;
+                    }
+                }
+            }
+        };
+
+        let mut picker =
+            PickerState::from_options(members.iter().map(TeamMemberInfo::identity).collect());
+        if let Some(current) = members.iter().find(|member| {
+            member.unique_name == item.assigned_to_email
+                || (item.assigned_to_email.is_empty() && member.display_name == item.assigned_to)
+        }) {
+            picker.active.insert(current.identity());
+            picker.set_selected_to_value(&current.identity());
+        }
+        picker.is_open = true;
+
+        self.list_view_state.is_list_details_hover_visible = false;
+        self.detail_view_state.assign_picker = picker;
+    }
+
+    fn start_assign(&mut self) {
+        let picker = &self.detail_view_state.assign_picker;
+        let Some(assignee) = picker
+            .selected
+            .and_then(|idx| picker.options.get(idx).cloned())
+        else {
+            return;
+        };
+        self.detail_view_state.assign_picker.close();
+
+        let Some(item_id) = self.get_selected_item().map(|item| item.id) else {
+            return;
+        };
+        self.send_assignment(item_id, assignee);
+    }
+
+    /// Assigns the selected item to `me` right away, without the picker.
+    fn assign_to_me(&mut self) {
+        if self.detail_view_state.assign_receiver.is_some() {
+            return;
+        }
+        let Some(item_id) = self.get_selected_item().map(|item| item.id) else {
+            return;
+        };
+        let Some(source) = self.selected_item_source() else {
+            return;
+        };
+        let me = self.source_me(&source).to_string();
+        if me.is_empty() {
+            self.set_status_message("Set `me` in the configuration to assign items to yourself");
+            return;
+        }
+        if let Some(item) = self.items.iter_mut().find(|i| i.id == item_id) {
+            let previous_email = if me.contains('@') {
+                std::mem::replace(&mut item.assigned_to_email, me.clone())
+            } else {
+                item.assigned_to_email.clone()
+            };
+            let previous_name = std::mem::replace(&mut item.assigned_to, me.clone());
+            self.detail_view_state.assign_previous = Some((item_id, previous_name, previous_email));
+        }
+        self.select_item_by_id(item_id);
+        self.send_assignment(item_id, me);
+    }
+
+    fn send_assignment(&mut self, item_id: u32, assignee: String) {
+        let Some(source) = self.selected_item_source() else {
+            self.restore_previous_assignee();
+            return;
+        };
+        let (tx, rx) = oneshot::channel();
+        tokio::spawn(async move {
+            let result =
+                assign_work_item(&source.organization, &source.project, item_id, &assignee)
+                    .await
+                    .map(|_| (item_id, assignee));
+            let _ = tx.send(result);
+        });
+        self.detail_view_state.save_status = SaveStatus::Saving;
+        self.detail_view_state.assign_receiver = Some(rx);
+    }
+
+    fn poll_assign_completion(&mut self) {
+        if let Some(receiver) = self.detail_view_state.assign_receiver.as_mut() {
+            use tokio::sync::oneshot::error::TryRecvError;
+
+            match receiver.try_recv() {
+                Ok(Ok((item_id, assignee))) => {
+                    // Assigning to me already updated the item.
+                    let to_me = self.detail_view_state.assign_previous.take().is_some();
+                    let (display_name, email) = assignee
+                        .strip_suffix('>')
+                        .and_then(|identity| identity.rsplit_once(" <"))
+                        .map(|(name, email)| (name.to_string(), email.to_string()))
+                        .unwrap_or((assignee, String::new()));
+                    if let Some(item) = self.items.iter_mut().find(|i| i.id == item_id) {
+                        if !to_me {
+                            item.assigned_to = display_name.clone();
+                            item.assigned_to_email = email;
+                        }
+                        item.raw_fields.insert(
+                            "System.AssignedTo".to_string(),
+                            serde_json::Value::String(display_name.clone()),
+                        );
+                    }
+                    self.update_cached_item(item_id);
+                    self.detail_view_state.save_status = SaveStatus::Idle;
+                    self.detail_view_state.assign_receiver = None;
+                    if to_me {
+                        self.select_item_by_id(item_id);
+                        self.set_status_message("Assigned to you");
+                    } else {
+                        self.clamp_selection();
+                        self.set_status_message(format!(
+                            "Assigned #{} to {}",
+                            item_id, display_name
+                        ));
+                    }
+                }
+                Ok(Err(err)) => {
+                    self.log_error(format!("Failed to assign: {}", err));
+                    self.restore_previous_assignee();
+                    self.detail_view_state.save_status = SaveStatus::Failed(format!("{}", err));
+                    self.detail_view_state.assign_receiver = None;
+                }
+                Err(TryRecvError::Closed) => {
+                    self.restore_previous_assignee();
+                    self.detail_view_state.save_status =
+                        SaveStatus::Failed("Assignment was cancelled".to_string());
+                    self.detail_view_state.assign_receiver = None;
+                }
+                Err(TryRecvError::Empty) => {}
+            }
+        }
+    }
+
+    fn restore_previous_assignee(&mut self) {
+        let Some((item_id, name, email)) = self.detail_view_state.assign_previous.take() else {
+            return;
+        };
+        if let Some(item) = self.items.iter_mut().find(|i| i.id == item_id) {
+            item.assigned_to = name;
+            item.assigned_to_email = email;
+        }
+        self.clamp_selection();
+    }
+
+    /// Follows the selected item, or unfollows it when it is already followed.
+    /// An item whose follow state isn't cached yet is looked up first.
+    fn toggle_follow(&mut self) {
+        if self.detail_view_state.follow_receiver.is_some() {
+            return;
+        }
+        let Some(item) = self.get_selected_item() else {
+            return;
+        };
+        let item_id = item.id;
+        let organization = self.item_project(item).0.to_string();
+        let known = self
+            .follow_states
+            .get(&(organization.clone(), item_id))
+            .cloned();
+
+        let (tx, rx) = oneshot::channel();
+        tokio::spawn(async move {
+            let result = async {
+                let current = match known {
+                    Some(current) => current,
+                    None => fetch_follow_subscription(&organization, item_id).await?,
+                };
+                let subscription_id = match current {
+                    Some(subscription_id) => {
+                        unfollow_work_item(&organization, &subscription_id).await?;
+                        None
+                    }
+                    None => Some(follow_work_item(&organization, item_id).await?),
+                };
+                Ok(FollowToggle {
+                    organization,
+                    item_id,
+                    subscription_id,
+                })
+            }
+            .await;
+            let _ = tx.send(result);
+        });
+        self.detail_view_state.save_status = SaveStatus::Saving;
+        self.detail_view_state.follow_receiver = Some(rx);
+    }
+
+    fn poll_follow_completion(&mut self) {
+        if let Some(receiver) = self.detail_view_state.follow_receiver.as_mut() {
+            use tokio::sync::oneshot::error::TryRecvError;
+
+            match receiver.try_recv() {
+                Ok(Ok(toggle)) => {
+                    let message = if toggle.subscription_id.is_some() {
+                        format!("Following #{}", toggle.item_id)
+                    } else {
+                        format!("Stopped following #{}", toggle.item_id)
+                    };
+                    self.follow_states.insert(
+                        (toggle.organization, toggle.item_id),
+                        toggle.subscription_id,
+                    );
+                    self.detail_view_state.save_status = SaveStatus::Idle;
+                    self.detail_view_state.follow_receiver = None;
+                    self.set_status_message(message);
+                }
+                Ok(Err(err)) => {
+                    self.log_error(format!("Failed to follow / unfollow: {}", err));
+                    self.detail_view_state.save_status = SaveStatus::Failed(format!("{}", err));
+                    self.detail_view_state.follow_receiver = None;
+                }
+                Err(TryRecvError::Closed) => {
+                    self.detail_view_state.save_status =
+                        SaveStatus::Failed("Follow was cancelled".to_string());
+                    self.detail_view_state.follow_receiver = None;
+                }
+                Err(TryRecvError::Empty) => {}
+            }
+        }
+    }
+
+    /// Whether the selected item is known to be followed.
+    pub fn is_selected_item_followed(&self) -> bool {
+        self.get_selected_item().is_some_and(|item| {
+            self.follow_states
+                .get(&(self.item_project(item).0.to_string(), item.id))
+                .is_some_and(|subscription_id| subscription_id.is_some())
+        })
+    }
+
+    /// Searches every source for the typed query. The shown source is
+    /// searched in memory, the others through the work item cache.
+    fn start_global_search(&mut self) {
+        let query = self.global_search.query.trim().to_string();
+        if query.is_empty() {
+            return;
+        }
+        let current_items = (self.loaded_source_index == Some(self.current_source_index))
+            .then(|| self.items.clone());
+        let current_index = self.current_source_index;
+        let sources = self.sources.clone();
+
+        let (tx, rx) = oneshot::channel();
+        tokio::spawn(async move {
+            let handles: Vec<_> = sources
+                .into_iter()
+                .enumerate()
+                .map(|(index, source)| {
+                    let cached = (index == current_index)
+                        .then(|| current_items.clone())
+                        .flatten();
+                    tokio::spawn(async move {
+                        match cached {
+                            Some(items) => Ok(items),
+                            None => fetch_source_items(&source, &RefreshPolicy::Normal)
+                                .await
+                                .map(|(items, _, _)| items)
+                                .map_err(|err| summarize_error(&err)),
+                        }
+                    })
+                })
+                .collect();
+
+            let mut outcomes = Vec::new();
+            for (index, handle) in handles.into_iter().enumerate() {
+                let outcome =
+                    handle
+                        .await
+                        .unwrap_or_else(|err| Err(err.to_string()))
+                        .map(|items| {
+                            items
+                                .into_iter()
+                                .filter(|item| item.matches_query(&query))
+                                .collect()
+                        });
+                outcomes.push((index, outcome));
+            }
+            let _ = tx.send(outcomes);
+        });
+        self.global_search.searched_query = Some(self.global_search.query.clone());
+        self.global_search.results.clear();
+        self.global_search.failures.clear();
+        self.global_search.selected = 0;
+        self.global_search.receiver = Some(rx);
+    }
+
+    fn poll_global_search(&mut self) {
+        let Some(receiver) = self.global_search.receiver.as_mut() else {
+            return;
+        };
+        use tokio::sync::oneshot::error::TryRecvError;
+
+        match receiver.try_recv() {
+            Ok(outcomes) => {
+                for (source_index, outcome) in outcomes {
+                    match outcome {
+                        Ok(items) => self.global_search.results.extend(
+                            items
+                                .into_iter()
+                                .map(|item| GlobalSearchResult { source_index, item }),
+                        ),
+                        Err(err) => self
+                            .global_search
+                            .failures
+                            .push(format!("{}: {}", self.sources[source_index].title, err)),
+                    }
+                }
+                self.global_search.receiver = None;
+            }
+            Err(TryRecvError::Closed) => {
+                self.global_search
+                    .failures
+                    .push("Search was cancelled".to_string());
+                self.global_search.receiver = None;
+            }
+            Err(TryRecvError::Empty) => {}
+        }
+    }
+
+    /// Switches to the source of the selected search result and selects the
+    /// item there. Returns whether the source has to be loaded first.
+    fn open_global_search_result(&mut self) -> bool {
+        let Some(result) = self.global_search.results.get(self.global_search.selected) else {
+            return false;
+        };
+        let (source_index, item_id) = (result.source_index, result.item.id);
+        self.global_search.close();
+        self.list_view_state.is_list_details_hover_visible = false;
+
+        if source_index == self.current_source_index
+            && self.loaded_source_index == Some(source_index)
+        {
+            self.select_item_by_id(item_id);
+            return false;
+        }
+        self.current_source_index = source_index;
+        self.pending_selection = Some(item_id);
+        self.loading_state = LoadingState::Loading;
+        true
+    }
+
+    /// Collapses a detail field to one line, or expands it again. The choice
+    /// is saved to `collapsed_fields` so it applies to every item.
+    fn toggle_field_collapse(&mut self, reference: &str, label: &str) {
+        let collapsed = &mut self.detail_view_state.collapsed_fields;
+        let is_collapsed = !collapsed.remove(reference);
+        if is_collapsed {
+            collapsed.insert(reference.to_string());
+        }
+        let fields = collapsed.iter().cloned().collect();
+        let text = if is_collapsed {
+            format!("{} collapsed", label)
+        } else {
+            format!("{} expanded", label)
+        };
+        match crate::config::store_collapsed_fields(self.config_path.as_deref(), fields) {
+            Ok(()) => self.set_status_message(text),
+            Err(err) => {
+                self.set_status_message(format!("{} (not saved: {})", text, summarize_error(&err)))
+            }
+        }
+    }
+
+    /// Why the visible list can't be reordered right now, if it can't.
+    fn reorder_blocker(&self) -> Option<&'static str> {
+        if self.sort.primary != SortKey::None {
+            return Some("Reordering needs the backlog order; remove the sort key");
+        }
+        if self.list_view_state.group_by_assignee {
+            return Some("Reordering needs the backlog order; stop grouping first");
+        }
+        if matches!(self.current_source().kind, SourceKind::MyWork(_)) {
+            return Some("Reordering needs a single board; switch to its backlog");
+        }
+        if self
+            .get_filtered_items()
+            .iter()
+            .any(|item| self.is_pinned(item))
+        {
+            return Some("Reordering needs the backlog order; unpin items first");
+        }
+        None
+    }
+
+    fn toggle_move_mode(&mut self) {
+        if self.list_view_state.is_moving {
+            self.list_view_state.is_moving = false;
+            self.set_status_message("Move mode off");
+            return;
+        }
+        if let Some(reason) = self.reorder_blocker() {
+            self.set_status_message(reason);
+            return;
+        }
+        if self.list_view_state.list_state.selected().is_none() {
+            return;
+        }
+        self.list_view_state.is_moving = true;
+    }
+
+    /// Moves the selected item one place up (`-1`) or down (`1`) among the
+    /// shown items. The list is updated right away and ADO is asked to place
+    /// the item between its new neighbours.
+    fn move_rank(&mut self, delta: isize) {
+        if self.detail_view_state.rank_receiver.is_some() {
+            return;
+        }
+        if let Some(reason) = self.reorder_blocker() {
+            self.set_status_message(reason);
+            return;
+        }
+        let visible: Vec<(u32, Option<u32>)> = self
+            .get_filtered_items()
+            .iter()
+            .map(|item| (item.id, item.parent_id))
+            .collect();
+        let Some(selected) = self.list_view_state.list_state.selected() else {
+            return;
+        };
+        let Some(&(item_id, parent_id)) = visible.get(selected) else {
+            return;
+        };
+        let Some(target) = selected
+            .checked_add_signed(delta)
+            .filter(|target| *target < visible.len())
+        else {
+            return;
+        };
+        let neighbour_id = visible[target].0;
+        let placement = if delta < 0 {
+            RankPlacement {
+                previous_id: target.checked_sub(1).map_or(0, |idx| visible[idx].0),
+                next_id: neighbour_id,
+                parent_id: parent_id.unwrap_or(0),
+            }
+        } else {
+            RankPlacement {
+                previous_id: neighbour_id,
+                next_id: visible.get(target + 1).map_or(0, |(id, _)| *id),
+                parent_id: parent_id.unwrap_or(0),
+            }
+        };
+
+        let Some(original_index) = self.items.iter().position(|item| item.id == item_id) else {
+            return;
+        };
+        let moved = self.items.remove(original_index);
+        let anchor = self
+            .items
+            .iter()
+            .position(|item| item.id == neighbour_id)
+            .unwrap_or(original_index.min(self.items.len()));
+        let insert_at = if delta < 0 { anchor } else { anchor + 1 };
+        self.items.insert(insert_at.min(self.items.len()), moved);
+        self.list_view_state.list_state.select(Some(target));
+
+        let source = self.current_source().clone();
+        let (tx, rx) = oneshot::channel();
+        tokio::spawn(async move {
+            let result = async {
+                let iteration_id = match &source.kind {
+                    SourceKind::Iteration(iteration) => {
+                        Some(iteration_id_for(iteration, true).await?.id)
+                    }
+                    SourceKind::Backlog(_) | SourceKind::MyWork(_) => None,
+                };
+                reorder_work_item(
+                    &source.organization,
+                    &source.project,
+                    &source.team,
+                    iteration_id.as_deref(),
+                    item_id,
+                    placement,
+                )
+                .await?;
+                Ok(())
+            }
+            .await;
+            let _ = tx.send(result);
+        });
+        self.detail_view_state.save_status = SaveStatus::Saving;
+        self.detail_view_state.rank_receiver = Some(rx);
+        self.detail_view_state.rank_undo = Some((item_id, original_index));
+    }
+
+    fn poll_rank_completion(&mut self) {
+        if let Some(receiver) = self.detail_view_state.rank_receiver.as_mut() {
+            use tokio::sync::oneshot::error::TryRecvError;
+
+            let failure = match receiver.try_recv() {
+                Ok(Ok(())) => None,
+                Ok(Err(err)) => {
+                    self.log_error(format!("Failed to reorder: {}", err));
+                    Some(summarize_error(&err))
+                }
+                Err(TryRecvError::Closed) => Some("Reordering was cancelled".to_string()),
+                Err(TryRecvError::Empty) => return,
+            };
+            self.detail_view_state.rank_receiver = None;
+            let undo = self.detail_view_state.rank_undo.take();
+            match failure {
+                None => self.detail_view_state.save_status = SaveStatus::Idle,
+                Some(message) => {
+                    self.detail_view_state.save_status = SaveStatus::Failed(message);
+                    if let Some((item_id, original_index)) = undo
+                        && let Some(index) = self.items.iter().position(|item| item.id == item_id)
+                    {
+                        let item = self.items.remove(index);
+                        self.items
+                            .insert(original_index.min(self.items.len()), item);
+                        self.select_item_by_id(item_id);
                     }
                 }
             }
         }
     }
 
-    fn move_active_picker(&mut self, direction: isize) {
-        if let Some(state) = self.detail_view_state.edit_state.as_mut() {
-            if !state.is_editing {
+    /// Runs the configured command on the selected item. Background commands
+    /// are started here; for interactive ones the command line is returned,
+    /// as they need the terminal that only `run_app` holds.
+    fn start_external_command(&mut self, index: usize) -> Option<String> {
+        let config = self.external_commands.get(index)?.clone();
+        let Some(item) = self.get_selected_item() else {
+            self.set_status_message(format!("{}: no item selected", config.name));
+            return None;
+        };
+        let command_line =
+            crate::external::expand_command(&config.command, item, &self.item_url(item));
+        if config.interactive {
+            return Some(command_line);
+        }
+        if self.external_receiver.is_some() {
+            self.set_status_message("Another command is still running");
+            return None;
+        }
+
+        let name = config.name.clone();
+        let (tx, rx) = oneshot::channel();
+        tokio::spawn(async move {
+            let mut command =
+                tokio::process::Command::from(crate::external::shell_command(&command_line));
+            let result = command.stdin(std::process::Stdio::null()).output().await;
+            let _ = tx.send((config.name, result));
+        });
+        self.external_receiver = Some(rx);
+        self.set_status_message(format!("Running {}…", name));
+        None
+    }
+
+    fn poll_external_command(&mut self) {
+        let Some(receiver) = self.external_receiver.as_mut() else {
+            return;
+        };
+        let Ok((name, result)) = receiver.try_recv() else {
+            return;
+        };
+        self.external_receiver = None;
+        let output = match result {
+            Ok(output) => output,
+            Err(err) => {
+                self.set_status_message(format!("Failed to start {}: {}", name, err));
                 return;
             }
-            Self::clamp_active_field(state);
-            if let Some(picker) = App::active_picker_mut(state) {
-                picker.move_selection(direction);
-            }
+        };
+
+        let mut text = String::from_utf8_lossy(&output.stdout).into_owned();
+        text.push_str(&String::from_utf8_lossy(&output.stderr));
+        let text = text.trim_end().to_string();
+        if output.status.success() && text.is_empty() {
+            self.set_status_message(format!("{} finished", name));
+            return;
         }
+        let title = if output.status.success() {
+            name
+        } else {
+            format!("{} ({})", name, output.status)
+        };
+        self.command_output = Some(CommandOutputView {
+            title,
+            text,
+            scroll: 0,
+        });
     }
 
-    fn select_active_picker_value(&mut self) {
-        if let Some(state) = self.detail_view_state.edit_state.as_mut() {
-            if !state.is_editing {
-                return;
+    /// Lists every field of the selected item by reference name, with the
+    /// layout's label where it has one, to help configuring fields.
+    fn inspect_fields(&mut self) {
+        if !self.debug {
+            self.set_status_message("Start adoboards with --debug to inspect fields");
+            return;
+        }
+        let Some(item) = self.get_selected_item() else {
+            self.set_status_message("No item selected");
+            return;
+        };
+        let (organization, project) = self.item_project(item);
+        let layout_key = (
+            organization.to_string(),
+            project.to_string(),
+            item.work_item_type.clone(),
+        );
+        let labels: HashMap<&str, &str> = self
+            .layout_cache
+            .get(&layout_key)
+            .map(|controls| {
+                controls
+                    .iter()
+                    .map(|(reference, label)| (reference.as_str(), label.as_str()))
+                    .collect()
+            })
+            .unwrap_or_default();
+        let text = item
+            .fields
+            .iter()
+            .map(|(reference, value)| match labels.get(reference.as_str()) {
+                Some(label) => format!("{} ({}): {}", reference, label, value),
+                None => format!("{}: {}", reference, value),
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        let title = format!("Fields of #{}", item.id);
+        self.command_output = Some(CommandOutputView {
+            title,
+            text,
+            scroll: 0,
+        });
+    }
+
+    fn scroll_command_output(&mut self, delta: i16) {
+        if let Some(view) = self.command_output.as_mut() {
+            view.scroll = view.scroll.saturating_add_signed(delta);
+        }
+    }
+
+    fn request_clone(&mut self) {
+        if self.get_selected_item().is_some() {
+            self.list_view_state.is_list_details_hover_visible = false;
+            self.detail_view_state.confirming_clone = true;
+        }
+    }
+
+    /// Creates a new item of the same type from the selected one. Only the
+    /// content and the area / iteration are copied, not the state, assignee
+    /// or any other tracking fields.
+    fn start_clone(&mut self) {
+        let Some(item) = self.get_selected_item().cloned() else {
+            return;
+        };
+        let mut fields = vec![(
+            "System.Title".to_string(),
+            serde_json::Value::String(format!("Copy of {}", item.title)),
+        )];
+        fields.extend(CLONED_FIELDS.iter().filter_map(|reference| {
+            item.raw_fields
+                .get(*reference)
+                .map(|value| (reference.to_string(), value.clone()))
+        }));
+
+        let Some(source) = self.selected_item_source() else {
+            return;
+        };
+        let (tx, rx) = oneshot::channel();
+        tokio::spawn(async move {
+            let result = create_work_item(
+                &source.organization,
+                &source.project,
+                &item.work_item_type,
+                fields,
+            )
+            .await
+            .map(|mut created| {
+                // The copy lives on the same board as the original.
+                created.board = item.board.clone();
+                (item.id, created)
+            });
+            let _ = tx.send(result);
+        });
+        self.detail_view_state.save_status = SaveStatus::Saving;
+        self.detail_view_state.clone_receiver = Some(rx);
+    }
+
+    fn poll_clone_completion(&mut self) {
+        if let Some(receiver) = self.detail_view_state.clone_receiver.as_mut() {
+            use tokio::sync::oneshot::error::TryRecvError;
+
+            match receiver.try_recv() {
+                Ok(Ok((original_id, created))) => {
+                    let created_id = created.id;
+                    let position = self
+                        .items
+                        .iter()
+                        .position(|item| item.id == original_id)
+                        .map_or(self.items.len(), |index| index + 1);
+                    self.items.insert(position, created);
+                    self.refresh_picker_options();
+                    self.detail_view_state.save_status = SaveStatus::Idle;
+                    self.detail_view_state.clone_receiver = None;
+                    self.detail_view_state.edit_state = None;
+                    self.select_item_by_id(created_id);
+                    self.set_status_message(format!(
+                        "Created #{} as a copy of #{} — press {} to edit it",
+                        created_id, original_id, self.keys.edit_item
+                    ));
+                }
+                Ok(Err(err)) => {
+                    self.log_error(format!("Failed to create the copy: {}", err));
+                    self.detail_view_state.save_status = SaveStatus::Failed(format!("{}", err));
+                    self.detail_view_state.clone_receiver = None;
+                }
+                Err(TryRecvError::Closed) => {
+                    self.detail_view_state.save_status =
+                        SaveStatus::Failed("Clone was cancelled".to_string());
+                    self.detail_view_state.clone_receiver = None;
+                }
+                Err(TryRecvError::Empty) => {}
             }
-            Self::clamp_active_field(state);
-            App::apply_active_picker_selection(state);
         }
     }
 
-    fn start_save(&mut self) {
+    fn preview_patch(&mut self) {
+        let Some(state) = self.detail_view_state.edit_state.as_ref() else {
+            return;
+        };
+        let operations = build_update_operations(state, &self.field_formats);
+        let preview = serde_json::to_string_pretty(&operations)
+            .unwrap_or_else(|err| format!("Failed to serialize patch: {}", err));
+        self.detail_view_state.patch_preview = Some((preview, 0));
+    }
+
+    fn scroll_patch_preview(&mut self, delta: i16) {
+        if let Some((_, scroll)) = self.detail_view_state.patch_preview.as_mut() {
+            *scroll = scroll.saturating_add_signed(delta);
+        }
+    }
+
+    fn start_save(&mut self, after_save: AfterSave) {
         let selected_item = self.get_selected_item().cloned();
-        let source = self.current_source().clone();
+        let Some(source) = self.selected_item_source() else {
+            return;
+        };
         let state_for_save = self.detail_view_state.edit_state.clone();
+        let field_formats = self.field_formats.clone();
         if let (Some(item), Some(save_state)) = (selected_item, state_for_save) {
             if !save_state.is_editing {
                 return;
             }
+            if let Some(path) = self.invalid_iteration_path(&source, &save_state) {
+                self.detail_view_state.save_status =
+                    SaveStatus::Failed(format!("{} is not an iteration of the team", path));
+                return;
+            }
             let (tx, rx) = oneshot::channel();
             tokio::spawn(async move {
                 let result = update_work_item_in_ado(
                     &BoardConfig {
+                        title: None,
                         organization: source.organization,
                         project: source.project,
                         team: source.team,
+                        me: None,
+                        me_aliases: None,
+                        max_items: None,
+                        backlog_level: None,
                     },
                     &item,
                     &save_state,
+                    &field_formats,
                 )
                 .await
                 .map(|_| (item, save_state));
@@ -745,43 +3915,81 @@ impl App {
             });
             self.detail_view_state.save_status = SaveStatus::Saving;
             self.detail_view_state.save_receiver = Some(rx);
+            self.detail_view_state.after_save = after_save;
             if let Some(state) = self.detail_view_state.edit_state.as_mut() {
                 state.is_editing = false;
             }
         }
     }
 
+    /// Writes the item as it is now in the list to the board's cache.
+    fn update_cached_item(&mut self, item_id: u32) {
+        let Some(item) = self.items.iter().find(|item| item.id == item_id) else {
+            return;
+        };
+        if let Err(err) = update_cached_work_item(&self.current_source().cache_key(), item) {
+            self.set_status_message(format!("Failed to update cache: {}", err));
+        }
+    }
+
     fn poll_save_completion(&mut self) {
         if let Some(receiver) = self.detail_view_state.save_receiver.as_mut() {
             use tokio::sync::oneshot::error::TryRecvError;
 
             match receiver.try_recv() {
                 Ok(Ok((updated_item, mut updated_state))) => {
+                    let _ = remove_draft(&DraftKey {
+                        organization: self.item_project(&updated_item).0.to_string(),
+                        item_id: updated_item.id,
+                    });
                     if let Some(current_item) =
                         self.items.iter_mut().find(|i| i.id == updated_item.id)
                     {
                         current_item.title = updated_state.title.clone();
-                        for field in &updated_state.visible_fields {
+                        // Our own change shouldn't flag the item as unread.
+                        if !updated_item.changed_date.is_empty() {
+                            current_item.changed_date = updated_item.changed_date.clone();
+                        }
+                        for field in updated_state
+                            .visible_fields
+                            .iter()
+                            .filter(|f| f.is_modified())
+                        {
                             current_item
                                 .fields
                                 .insert(field.reference.clone(), field.value.clone());
+                            current_item.raw_fields.insert(
+                                field.reference.clone(),
+                                serde_json::Value::String(field.value.clone()),
+                            );
                         }
                     }
-                    updated_state.is_editing = false;
+                    self.update_cached_item(updated_item.id);
+                    self.mark_selected_seen();
+                    updated_state.is_editing =
+                        self.detail_view_state.after_save == AfterSave::KeepEditing;
+                    updated_state.mark_saved();
                     App::clamp_active_field(&mut updated_state);
-                    self.detail_view_state.edit_state = Some(updated_state);
+                    self.detail_view_state.edit_state = (self.detail_view_state.after_save
+                        != AfterSave::ReturnToList)
+                        .then_some(updated_state);
                     self.detail_view_state.save_status = SaveStatus::Idle;
                     self.detail_view_state.save_receiver = None;
                 }
                 Ok(Err(err)) => {
+                    self.log_error(format!("Failed to save: {}", err));
                     self.detail_view_state.save_status = SaveStatus::Failed(format!("{}", err));
                     self.detail_view_state.save_receiver = None;
-                    if let Some(item) = self.get_selected_item().cloned() {
-                        if let Some(state) = self.detail_view_state.edit_state.as_mut() {
-                            let existing_fields = state.visible_fields.clone();
-                            let reset = App::rebuild_edit_state_from_item(&item, &existing_fields);
-                            *state = reset;
-                        }
+                    if let Some(item) = self.get_selected_item().cloned()
+                        && let Some(state) = self.detail_view_state.edit_state.as_mut()
+                    {
+                        let existing_fields = state.visible_fields.clone();
+                        let reset = App::rebuild_edit_state_from_item(
+                            &item,
+                            &existing_fields,
+                            &self.field_formats,
+                        );
+                        *state = reset;
                     }
                 }
                 Err(TryRecvError::Closed) => {
@@ -795,11 +4003,61 @@ impl App {
     }
 }
 
+/// Percent-encodes a single URL path segment (organization, project, team...).
+fn encode_url_segment(segment: &str) -> String {
+    let mut encoded = String::with_capacity(segment.len());
+    for byte in segment.bytes() {
+        if byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'_' | b'.' | b'~') {
+            encoded.push(byte as char);
+        } else {
+            encoded.push_str(&format!("%{:02X}", byte));
+        }
+    }
+    encoded
+}
+
+/// Parses a binding with modifiers such as `ctrl+r`, `alt+j` or `shift+g`
+/// into the required Ctrl/Alt modifiers and the character. Shift is folded
+/// into the character, as terminals report shifted letters in upper case.
+fn parse_modified_binding(binding: &str) -> Option<(KeyModifiers, char)> {
+    let (modifier_names, key_name) = binding.rsplit_once('+')?;
+    let mut key_chars = key_name.chars();
+    let mut key_char = key_chars.next()?;
+    if key_chars.next().is_some() {
+        return None;
+    }
+
+    let mut modifiers = KeyModifiers::NONE;
+    for name in modifier_names.split('+') {
+        match name.to_ascii_lowercase().as_str() {
+            "ctrl" | "control" => modifiers |= KeyModifiers::CONTROL,
+            "alt" => modifiers |= KeyModifiers::ALT,
+            "shift" => key_char = key_char.to_ascii_uppercase(),
+            _ => return None,
+        }
+    }
+    Some((modifiers, key_char))
+}
+
 pub fn key_matches_sequence(
-    current_key: char,
+    key: KeyEvent,
     last_key: Option<KeyCode>,
     target_sequence: &str,
 ) -> bool {
+    let KeyCode::Char(current_key) = key.code else {
+        return false;
+    };
+    let pressed_modifiers = key.modifiers & (KeyModifiers::CONTROL | KeyModifiers::ALT);
+
+    if let Some((modifiers, binding_key)) = parse_modified_binding(target_sequence) {
+        return pressed_modifiers == modifiers && current_key == binding_key;
+    }
+
+    // Plain bindings never match while Ctrl or Alt is held.
+    if !pressed_modifiers.is_empty() {
+        return false;
+    }
+
     if target_sequence.len() == 2 {
         let first_char = target_sequence.chars().next().unwrap();
         let second_char = target_sequence.chars().nth(1).unwrap();
@@ -807,26 +4065,50 @@ pub fn key_matches_sequence(
     }
 
     if target_sequence.len() == 1 {
-        return target_sequence.chars().next() == Some(current_key);
+        return target_sequence.starts_with(current_key);
     }
 
     false
 }
 
+/// Bounds and step of the list's share of the screen width, keeping both the
+/// list and the details readable.
+const MIN_LIST_WIDTH_PERCENT: u16 = 20;
+const MAX_LIST_WIDTH_PERCENT: u16 = 80;
+const LIST_WIDTH_STEP: u16 = 5;
+
+/// Columns moved by one Left / Right press in the detail view.
+const HORIZONTAL_SCROLL_STEP: i16 = 4;
+
+pub(crate) const DESCRIPTION_FIELD: &str = "System.Description";
+pub(crate) const ACCEPTANCE_CRITERIA_FIELD: &str = "Microsoft.VSTS.Common.AcceptanceCriteria";
+const ITERATION_PATH_FIELD: &str = "System.IterationPath";
+const STATE_FIELD: &str = "System.State";
+pub(crate) const REASON_FIELD: &str = "System.Reason";
+
+/// Fields carried over when cloning an item, besides its title.
+const CLONED_FIELDS: [&str; 5] = [
+    DESCRIPTION_FIELD,
+    ACCEPTANCE_CRITERIA_FIELD,
+    "Microsoft.VSTS.TCM.ReproSteps",
+    "System.AreaPath",
+    ITERATION_PATH_FIELD,
+];
+
+/// Fields shown in the detail view whenever the work item type's layout has
+/// them, regardless of where the layout places them.
+const ALWAYS_SHOWN_FIELDS: [&str; 1] = ["Microsoft.VSTS.TCM.ReproSteps"];
+
 async fn fetch_visible_controls(
     organization: &str,
     process_id: &str,
     reference_name: &str,
 ) -> Result<Vec<(String, String)>> {
     let layout = fetch_work_item_layout(organization, process_id, reference_name).await?;
-    let page = layout
-        .pages
-        .into_iter()
-        .next()
-        .ok_or_else(|| anyhow!("No pages in layout"))?;
-    let section = page
-        .sections
-        .into_iter()
+    let mut pages = layout.pages.into_iter();
+    let page = pages.next().ok_or_else(|| anyhow!("No pages in layout"))?;
+    let mut sections = page.sections.into_iter();
+    let section = sections
         .next()
         .ok_or_else(|| anyhow!("No sections in layout"))?;
 
@@ -836,15 +4118,31 @@ async fn fetch_visible_controls(
             continue;
         }
         for control in group.controls.into_iter() {
-            if control.visible.unwrap_or(true) {
-                if let Some(id) = control.id {
-                    let label = control.label.unwrap_or_else(|| id.clone());
-                    controls.push((id, label));
-                }
+            if control.visible.unwrap_or(true)
+                && let Some(id) = control.id
+            {
+                let label = control.label.unwrap_or_else(|| id.clone());
+                controls.push((id, label));
             }
         }
     }
 
+    // Some templates place these outside the first section; show them anyway
+    // when the type has them.
+    let other_controls = sections
+        .chain(pages.flat_map(|page| page.sections))
+        .flat_map(|section| section.groups)
+        .flat_map(|group| group.controls);
+    for control in other_controls {
+        if let Some(id) = control.id
+            && ALWAYS_SHOWN_FIELDS.contains(&id.as_str())
+            && !controls.iter().any(|(existing, _)| *existing == id)
+        {
+            let label = control.label.unwrap_or_else(|| id.clone());
+            controls.push((id, label));
+        }
+    }
+
     Ok(controls)
 }
 
@@ -856,6 +4154,7 @@ pub async fn prefetch_layouts(
     refresh_policy: RefreshPolicy,
 ) -> HashMap<(String, String, String), Vec<(String, String)>> {
     let mut cache = HashMap::new();
+    let mut fetches = tokio::task::JoinSet::new();
     for (display_name, reference_name) in layouts {
         let key = (
             organization.to_string(),
@@ -885,7 +4184,22 @@ pub async fn prefetch_layouts(
             cache.insert(key, controls);
             continue;
         }
-        match fetch_visible_controls(organization, process_id, &reference_name).await {
+        let process_id = process_id.to_string();
+        fetches.spawn(async move {
+            let controls = limited(fetch_visible_controls(
+                &layout_key_ref.organization,
+                &process_id,
+                &reference_name,
+            ))
+            .await;
+            (key, layout_key_ref, display_name, reference_name, controls)
+        });
+    }
+    while let Some(joined) = fetches.join_next().await {
+        let Ok((key, layout_key_ref, display_name, reference_name, controls)) = joined else {
+            continue;
+        };
+        match controls {
             Ok(controls) => {
                 let _ = write_layout_cache(&layout_key_ref, &controls);
                 cache.insert(key, controls);
@@ -901,6 +4215,295 @@ pub async fn prefetch_layouts(
     cache
 }
 
+/// How long cached work items of a source are used before refetching.
+pub const WORK_ITEMS_MAX_AGE: Duration = Duration::from_secs(3600);
+
+/// Iteration ids of fixed paths do not change, so they are only re-resolved
+/// occasionally in case the iteration was recreated.
+const ITERATION_ID_MAX_AGE: Duration = Duration::from_secs(7 * 24 * 60 * 60);
+
+/// Id of the configured iteration, from the cache when `use_cache` is set
+/// and the cached id is still valid.
+fn iteration_id_cache_key(iteration: &IterationConfig) -> IterationIdCacheKey {
+    IterationIdCacheKey {
+        organization: iteration.organization.clone(),
+        project: iteration.project.clone(),
+        team: iteration.team.clone(),
+        iteration: iteration.iteration.clone(),
+    }
+}
+
+pub async fn iteration_id_for(
+    iteration: &IterationConfig,
+    use_cache: bool,
+) -> Result<ResolvedIteration> {
+    let iteration_id_key = iteration_id_cache_key(iteration);
+    if use_cache
+        && let Some(resolved) = read_iteration_id_cache(&iteration_id_key, ITERATION_ID_MAX_AGE)
+    {
+        return Ok(resolved);
+    }
+    let resolved = resolve_iteration_id(
+        &iteration.organization,
+        &iteration.project,
+        &iteration.team,
+        &iteration.iteration,
+    )
+    .await?;
+    let _ = write_iteration_id_cache(&iteration_id_key, &resolved);
+    Ok(resolved)
+}
+
+/// Warning for a list that loaded with items left out by `partial_loads`.
+fn partial_load_warning(listed: &ListedItems) -> Option<String> {
+    let error = listed.error.as_ref()?;
+    Some(format!(
+        "Loaded {} of {} items — some failed: {}",
+        listed.items.len(),
+        listed.items.len() + listed.missing,
+        error
+    ))
+}
+
+/// Work items of a source, read from the cache while it is fresh unless the
+/// refresh policy asks for new data. Also returns a warning when items were
+/// left out; such partial lists aren't cached, so the next load retries them.
+pub async fn fetch_source_items(
+    source: &SourceEntry,
+    refresh_policy: &RefreshPolicy,
+) -> Result<(Vec<WorkItem>, Option<ResolvedIteration>, Option<String>)> {
+    match &source.kind {
+        SourceKind::Backlog(backlog_level) => {
+            let cache_key = source.cache_key();
+            let cached = if matches!(refresh_policy, RefreshPolicy::Normal) {
+                read_work_items_cache(&cache_key, WORK_ITEMS_MAX_AGE, SystemTime::now())
+            } else {
+                None
+            };
+            if let Some(items) = cached {
+                Ok((items, None, None))
+            } else {
+                let ids = source.cap_ids(
+                    get_backlog_ids(
+                        &source.organization,
+                        &source.project,
+                        &source.team,
+                        backlog_level,
+                    )
+                    .await?,
+                );
+                let listed = get_list_items(&source.organization, &source.project, ids).await?;
+                let warning = partial_load_warning(&listed);
+                if warning.is_none() {
+                    let _ = write_work_items_cache(&cache_key, &listed.items);
+                }
+
+                Ok((listed.items, None, warning))
+            }
+        }
+        SourceKind::Iteration(iteration) => {
+            let cache_key = source.cache_key();
+            let cached = if matches!(refresh_policy, RefreshPolicy::Normal) {
+                read_work_items_cache(&cache_key, WORK_ITEMS_MAX_AGE, SystemTime::now())
+            } else {
+                None
+            };
+            if let Some(items) = cached {
+                // The dates are only shown, so a missing entry is not worth a request.
+                let resolved = read_iteration_id_cache(
+                    &iteration_id_cache_key(iteration),
+                    ITERATION_ID_MAX_AGE,
+                );
+                Ok((items, resolved, None))
+            } else {
+                let resolved =
+                    iteration_id_for(iteration, matches!(refresh_policy, RefreshPolicy::Normal))
+                        .await?;
+                let ids = source.cap_ids(
+                    get_iteration_ids(
+                        &iteration.organization,
+                        &iteration.project,
+                        &iteration.team,
+                        &resolved.id,
+                    )
+                    .await?,
+                );
+                let listed =
+                    get_list_items(&iteration.organization, &iteration.project, ids).await?;
+                let warning = partial_load_warning(&listed);
+                if warning.is_none() {
+                    let _ = write_work_items_cache(&cache_key, &listed.items);
+                }
+
+                Ok((listed.items, Some(resolved), warning))
+            }
+        }
+        SourceKind::MyWork(boards) => {
+            let cache_key = source.cache_key();
+            let cached = if matches!(refresh_policy, RefreshPolicy::Normal) {
+                read_work_items_cache(&cache_key, WORK_ITEMS_MAX_AGE, SystemTime::now())
+            } else {
+                None
+            };
+            if let Some(items) = cached {
+                return Ok((items, None, None));
+            }
+
+            let mut resolved_me: HashMap<String, String> = HashMap::new();
+            let mut seen: HashSet<(String, u32)> = HashSet::new();
+            let mut merged = Vec::new();
+            let mut warnings = Vec::new();
+            for board in boards {
+                let me = if board.me.is_empty() {
+                    match resolved_me.get(&board.organization) {
+                        Some(me) => me.clone(),
+                        None => match fetch_authenticated_user(&board.organization).await {
+                            Ok(user) => {
+                                resolved_me
+                                    .insert(board.organization.clone(), user.display_name.clone());
+                                user.display_name
+                            }
+                            Err(err) => {
+                                warnings.push(format!(
+                                    "{}: signed in user unavailable: {}",
+                                    board.title, err
+                                ));
+                                continue;
+                            }
+                        },
+                    }
+                } else {
+                    board.me.clone()
+                };
+                let (items, _, warning) =
+                    Box::pin(fetch_source_items(board, refresh_policy)).await?;
+                warnings.extend(warning.map(|warning| format!("{}: {}", board.title, warning)));
+                for mut item in items {
+                    if !is_assigned_to_any(&item, &me, &board.me_aliases) {
+                        continue;
+                    }
+                    if !seen.insert((board.organization.clone(), item.id)) {
+                        continue;
+                    }
+                    item.board = Some(BoardTag {
+                        title: board.title.clone(),
+                        organization: board.organization.clone(),
+                        project: board.project.clone(),
+                    });
+                    merged.push(item);
+                }
+            }
+            if warnings.is_empty() {
+                let _ = write_work_items_cache(&cache_key, &merged);
+                return Ok((merged, None, None));
+            }
+
+            Ok((merged, None, Some(warnings.join("; "))))
+        }
+    }
+}
+
+/// Hands the terminal back to the shell and stops the process like Ctrl-Z
+/// normally would; raw mode swallows the key, so the signal is raised here.
+/// Once resumed with `fg` the terminal is set up again and fully redrawn.
+#[cfg(unix)]
+fn suspend<B: ratatui::backend::Backend>(terminal: &mut Terminal<B>) -> io::Result<()> {
+    disable_raw_mode()?;
+    execute!(io::stdout(), LeaveAlternateScreen, DisableMouseCapture)?;
+    terminal.show_cursor()?;
+
+    // SAFETY: raising a signal for the current process has no memory safety
+    // requirements; execution continues here after SIGCONT.
+    unsafe {
+        libc::raise(libc::SIGTSTP);
+    }
+
+    enable_raw_mode()?;
+    execute!(io::stdout(), EnterAlternateScreen, EnableMouseCapture)?;
+    terminal.clear()?;
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn suspend<B: ratatui::backend::Backend>(_terminal: &mut Terminal<B>) -> io::Result<()> {
+    Ok(())
+}
+
+/// Hands the terminal to an interactive external command and waits for Enter
+/// once it exits, so its output can be read before the app is redrawn.
+fn run_interactive<B: ratatui::backend::Backend>(
+    terminal: &mut Terminal<B>,
+    command_line: &str,
+) -> io::Result<io::Result<std::process::ExitStatus>> {
+    disable_raw_mode()?;
+    execute!(io::stdout(), LeaveAlternateScreen, DisableMouseCapture)?;
+    terminal.show_cursor()?;
+
+    let status = crate::external::shell_command(command_line).status();
+    println!("\nPress Enter to return to adoboards");
+    let mut line = String::new();
+    let _ = io::stdin().read_line(&mut line);
+
+    enable_raw_mode()?;
+    execute!(io::stdout(), EnterAlternateScreen, EnableMouseCapture)?;
+    terminal.clear()?;
+    Ok(status)
+}
+
+/// Hands the terminal to the configuration editor until it exits.
+fn edit_config<B: ratatui::backend::Backend>(
+    terminal: &mut Terminal<B>,
+    config_path: Option<&std::path::Path>,
+) -> io::Result<anyhow::Result<()>> {
+    disable_raw_mode()?;
+    execute!(io::stdout(), LeaveAlternateScreen, DisableMouseCapture)?;
+    terminal.show_cursor()?;
+
+    let opened = crate::config::open_config(config_path);
+
+    enable_raw_mode()?;
+    execute!(io::stdout(), EnterAlternateScreen, EnableMouseCapture)?;
+    terminal.clear()?;
+    Ok(opened)
+}
+
+/// Shown at startup while the configuration can't be used: `c` opens it in an
+/// editor, `r` loads it again and `q` quits. Returns the configuration once it
+/// loads, or `None` when the user quits.
+pub fn run_config_setup<B: ratatui::backend::Backend>(
+    terminal: &mut Terminal<B>,
+    config_path: Option<&std::path::Path>,
+    mut problem: String,
+) -> io::Result<Option<AppConfig>> {
+    loop {
+        let details = [
+            problem.clone(),
+            "Press 'c' to edit the configuration, 'r' to load it again.".to_string(),
+        ];
+        terminal.draw(|f| draw_status_screen(f, "Configuration needs attention", &details))?;
+        let Event::Key(key) = event::read()? else {
+            continue;
+        };
+        match key.code {
+            KeyCode::Char('q') | KeyCode::Esc => return Ok(None),
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                return Ok(None);
+            }
+            KeyCode::Char('c') => {
+                problem = match edit_config(terminal, config_path)? {
+                    Ok(()) => "Configuration edited; press 'r' to load it.".to_string(),
+                    Err(err) => format!("Editor not opened: {:#}", err),
+                };
+            }
+            KeyCode::Char('r') => match load_startup_config(config_path) {
+                Ok(cfg) => return Ok(Some(cfg)),
+                Err(err) => problem = err,
+            },
+            _ => {}
+        }
+    }
+}
+
 pub async fn run_app<B: ratatui::backend::Backend>(
     terminal: &mut Terminal<B>,
     app: &mut App,
@@ -909,382 +4512,738 @@ pub async fn run_app<B: ratatui::backend::Backend>(
         return Ok(());
     }
     loop {
+        // Background work is picked up as soon as it finishes rather than on
+        // the next key, so progress indicators stop by themselves.
+        app.poll_save_completion();
+        app.poll_assign_completion();
+        app.poll_refresh_completion();
+        app.poll_follow_completion();
+        app.poll_clone_completion();
+        app.poll_rank_completion();
+        app.poll_global_search();
+        app.poll_external_command();
+
+        if app.config_edit_requested {
+            app.config_edit_requested = false;
+            match edit_config(terminal, app.config_path.as_deref())? {
+                Ok(()) => match app.reload_config() {
+                    Ok(()) => {
+                        app.set_status_message("Configuration reloaded");
+                        return Ok(());
+                    }
+                    Err(err) => {
+                        app.set_status_message(format!("Configuration not reloaded: {err:#}"))
+                    }
+                },
+                Err(err) => app.set_status_message(format!("Editor not opened: {err:#}")),
+            }
+        }
+
         terminal.draw(|f| match app.loading_state {
             LoadingState::Loaded => {
                 let main_chunks = ratatui::layout::Layout::default()
                     .direction(ratatui::layout::Direction::Horizontal)
                     .constraints([
-                        ratatui::layout::Constraint::Percentage(38),
-                        ratatui::layout::Constraint::Percentage(62),
+                        ratatui::layout::Constraint::Percentage(app.list_width_percent),
+                        ratatui::layout::Constraint::Percentage(100 - app.list_width_percent),
                     ])
                     .split(f.area());
 
                 draw_list_view(f, app, main_chunks[0]);
                 draw_detail_view(f, app, main_chunks[1]);
+                crate::ui::draw_assign_picker_popup(f, app);
+                crate::ui::draw_board_picker_popup(f, app);
+                crate::ui::draw_command_palette(f, app);
+                crate::ui::draw_global_search(f, app);
+                crate::ui::draw_patch_preview(f, app);
+                crate::ui::draw_command_output(f, app);
+                crate::ui::draw_error_log(f, app);
+                crate::ui::draw_draft_prompt(f, app);
+                crate::ui::draw_clone_prompt(f, app);
+                crate::ui::draw_quit_prompt(f, app);
                 crate::ui::draw_help_popup(f, app);
+                draw_status_message(f, app);
             }
             LoadingState::Loading => {}
-            LoadingState::Error(ref msg) => {
-                draw_status_screen(f, &format!("Failed to load data. {}", msg))
+            LoadingState::Error(ref report) => {
+                draw_error_screen(f, report, app.show_full_error, &app.keys)
             }
         })?;
 
-        if event::poll(Duration::from_millis(100))? {
-                    if let Event::Key(key) = event::read()? {
-                        match app.loading_state {
-                            LoadingState::Loading | LoadingState::Error(_) => match key.code {
-                                KeyCode::Char('q') | KeyCode::Esc => return Ok(()),
-                                _ => {}
-                            },
-                            _ => {
-                                if app.showing_help {
-                                    match key.code {
-                                        KeyCode::Esc => {
-                                            app.showing_help = false;
-                                            app.last_key_press = None;
-                                        }
-                                        KeyCode::Char(c) => {
-                                            let last_key = app.last_key_press;
-                                            if key_matches_sequence(c, last_key, &app.keys.help)
-                                                || key_matches_sequence(c, last_key, &app.keys.quit)
-                                            {
-                                                app.showing_help = false;
-                                                app.last_key_press = None;
-                                            } else {
-                                                app.last_key_press = Some(key.code);
-                                            }
-                                        }
-                                        _ => {}
-                                    }
-                                    continue;
-                                }
+        if event::poll(Duration::from_millis(100))?
+            && let Event::Key(key) = event::read()?
+        {
+            app.refresh_summary = None;
 
-                                if app.list_view_state.is_filtering {
-                                    match key.code {
-                                        KeyCode::Enter | KeyCode::Esc => {
-                                            app.list_view_state.is_filtering = false;
-                                            if key.code == KeyCode::Esc {
-                                        app.list_view_state.filter_query.clear();
-                                        app.clamp_selection();
-                                    }
+            if key.code == KeyCode::Char('z') && key.modifiers.contains(KeyModifiers::CONTROL) {
+                suspend(terminal)?;
+                app.last_key_press = None;
+                continue;
+            }
+
+            // Raw mode delivers Ctrl-C as a key instead of a signal, so it is
+            // handled here and quits through the normal terminal teardown.
+            if key.code == KeyCode::Char('c') && key.modifiers.contains(KeyModifiers::CONTROL) {
+                app.last_key_press = None;
+                let quit = match app.loading_state {
+                    LoadingState::Loading | LoadingState::Error(_) => true,
+                    _ => {
+                        app.confirming_quit || matches!(app.request_quit(), CommandOutcome::Return)
+                    }
+                };
+                if quit {
+                    return Ok(());
+                }
+                continue;
+            }
+
+            match app.loading_state {
+                LoadingState::Loading | LoadingState::Error(_) => match key.code {
+                    KeyCode::Char('q') | KeyCode::Esc => return Ok(()),
+                    KeyCode::Char(_)
+                        if key_matches_sequence(
+                            key,
+                            app.last_key_press,
+                            &app.keys.error_details,
+                        ) =>
+                    {
+                        app.show_full_error = !app.show_full_error;
+                    }
+                    KeyCode::Char(_)
+                        if matches!(app.loading_state, LoadingState::Error(_))
+                            && key_matches_sequence(key, app.last_key_press, &app.keys.refresh) =>
+                    {
+                        app.loading_state = LoadingState::Loading;
+                        return Ok(());
+                    }
+                    _ => {}
+                },
+                _ => {
+                    if app.confirming_quit {
+                        app.confirming_quit = false;
+                        app.last_key_press = None;
+                        if matches!(key.code, KeyCode::Char('y') | KeyCode::Char('Y')) {
+                            return Ok(());
+                        }
+                        continue;
+                    }
+
+                    if app.detail_view_state.confirming_clone {
+                        app.detail_view_state.confirming_clone = false;
+                        app.last_key_press = None;
+                        if matches!(key.code, KeyCode::Char('y') | KeyCode::Char('Y')) {
+                            app.start_clone();
+                        }
+                        continue;
+                    }
+
+                    if app.detail_view_state.draft_prompt.is_some() {
+                        app.last_key_press = None;
+                        match key.code {
+                            KeyCode::Char('y') | KeyCode::Char('Y') => {
+                                app.resolve_draft_prompt(true)
+                            }
+                            KeyCode::Char('n') | KeyCode::Char('N') => {
+                                app.resolve_draft_prompt(false)
+                            }
+                            KeyCode::Esc => {
+                                app.detail_view_state.draft_prompt = None;
+                                app.cancel_edit();
+                            }
+                            _ => {}
+                        }
+                        continue;
+                    }
+
+                    if app.showing_help {
+                        match key.code {
+                            KeyCode::Esc => {
+                                app.showing_help = false;
+                                app.last_key_press = None;
+                            }
+                            KeyCode::Char(_) => {
+                                let last_key = app.last_key_press;
+                                if key_matches_sequence(key, last_key, &app.keys.help)
+                                    || key_matches_sequence(key, last_key, &app.keys.quit)
+                                {
+                                    app.showing_help = false;
+                                    app.last_key_press = None;
+                                } else {
+                                    app.last_key_press = Some(key.code);
                                 }
-                                KeyCode::Backspace => {
-                                    app.list_view_state.filter_query.pop();
+                            }
+                            _ => {}
+                        }
+                        continue;
+                    }
+
+                    if app.list_view_state.is_filtering {
+                        match key.code {
+                            KeyCode::Enter | KeyCode::Esc => {
+                                app.list_view_state.is_filtering = false;
+                                if key.code == KeyCode::Esc {
+                                    app.list_view_state.filter_query.clear();
                                     app.clamp_selection();
                                 }
-                                KeyCode::Char(c) => {
-                                    if c != '/' {
-                                        app.list_view_state.filter_query.push(c);
-                                        app.clamp_selection();
-                                    }
-                                }
-                                _ => {}
                             }
-                        } else if app.list_view_state.type_picker.is_open {
-                            match key.code {
-                                KeyCode::Esc => {
-                                    app.list_view_state.type_picker.close();
+                            KeyCode::Backspace => {
+                                app.list_view_state.filter_query.pop();
+                                app.clamp_selection();
+                            }
+                            KeyCode::Char(c) if c != '/' => {
+                                app.list_view_state.filter_query.push(c);
+                                app.clamp_selection();
+                            }
+                            _ => {}
+                        }
+                    } else if app.list_view_state.is_finding {
+                        match key.code {
+                            KeyCode::Enter => app.list_view_state.is_finding = false,
+                            KeyCode::Esc => {
+                                app.list_view_state.is_finding = false;
+                                app.list_view_state.find_query.clear();
+                                app.list_view_state.find_match = None;
+                            }
+                            KeyCode::Backspace => {
+                                app.list_view_state.find_query.pop();
+                                app.jump_to_match(0);
+                            }
+                            KeyCode::Char(c) => {
+                                app.list_view_state.find_query.push(c);
+                                app.jump_to_match(0);
+                            }
+                            _ => {}
+                        }
+                    } else if app.list_view_state.is_moving {
+                        match key.code {
+                            KeyCode::Up => app.move_rank(-1),
+                            KeyCode::Down => app.move_rank(1),
+                            KeyCode::Esc | KeyCode::Enter => app.toggle_move_mode(),
+                            KeyCode::Char(_)
+                                if key_matches_sequence(
+                                    key,
+                                    app.last_key_press,
+                                    &app.keys.next,
+                                ) =>
+                            {
+                                app.move_rank(1)
+                            }
+                            KeyCode::Char(_)
+                                if key_matches_sequence(
+                                    key,
+                                    app.last_key_press,
+                                    &app.keys.previous,
+                                ) =>
+                            {
+                                app.move_rank(-1)
+                            }
+                            KeyCode::Char(_)
+                                if key_matches_sequence(
+                                    key,
+                                    app.last_key_press,
+                                    &app.keys.move_mode,
+                                ) =>
+                            {
+                                app.toggle_move_mode()
+                            }
+                            _ => {}
+                        }
+                        app.last_key_press = None;
+                    } else if app.detail_view_state.patch_preview.is_some() {
+                        match key.code {
+                            KeyCode::Up => app.scroll_patch_preview(-1),
+                            KeyCode::Down => app.scroll_patch_preview(1),
+                            KeyCode::Char(_)
+                                if key_matches_sequence(
+                                    key,
+                                    app.last_key_press,
+                                    &app.keys.next,
+                                ) =>
+                            {
+                                app.scroll_patch_preview(1)
+                            }
+                            KeyCode::Char(_)
+                                if key_matches_sequence(
+                                    key,
+                                    app.last_key_press,
+                                    &app.keys.previous,
+                                ) =>
+                            {
+                                app.scroll_patch_preview(-1)
+                            }
+                            _ => app.detail_view_state.patch_preview = None,
+                        }
+                        app.last_key_press = None;
+                    } else if app.command_output.is_some() {
+                        match key.code {
+                            KeyCode::Up => app.scroll_command_output(-1),
+                            KeyCode::Down => app.scroll_command_output(1),
+                            KeyCode::Char(_)
+                                if key_matches_sequence(
+                                    key,
+                                    app.last_key_press,
+                                    &app.keys.next,
+                                ) =>
+                            {
+                                app.scroll_command_output(1)
+                            }
+                            KeyCode::Char(_)
+                                if key_matches_sequence(
+                                    key,
+                                    app.last_key_press,
+                                    &app.keys.previous,
+                                ) =>
+                            {
+                                app.scroll_command_output(-1)
+                            }
+                            _ => app.command_output = None,
+                        }
+                        app.last_key_press = None;
+                    } else if app.error_log.is_open {
+                        match key.code {
+                            KeyCode::Up => app.scroll_error_log(-1),
+                            KeyCode::Down => app.scroll_error_log(1),
+                            KeyCode::Char('c') => {
+                                app.error_log.entries.clear();
+                                app.error_log.scroll = 0;
+                            }
+                            KeyCode::Char(_)
+                                if key_matches_sequence(
+                                    key,
+                                    app.last_key_press,
+                                    &app.keys.next,
+                                ) =>
+                            {
+                                app.scroll_error_log(1)
+                            }
+                            KeyCode::Char(_)
+                                if key_matches_sequence(
+                                    key,
+                                    app.last_key_press,
+                                    &app.keys.previous,
+                                ) =>
+                            {
+                                app.scroll_error_log(-1)
+                            }
+                            _ => app.error_log.is_open = false,
+                        }
+                        app.last_key_press = None;
+                    } else if app.global_search.is_open {
+                        match key.code {
+                            KeyCode::Esc => app.global_search.close(),
+                            KeyCode::Enter => {
+                                if !app.global_search.has_current_results() {
+                                    app.start_global_search();
+                                } else if app.open_global_search_result() {
+                                    return Ok(());
                                 }
-                                KeyCode::Char('c') => {
-                                    app.clear_type_filters();
-                                    app.list_view_state.type_picker.close();
+                            }
+                            KeyCode::Up => app.global_search.move_selection(-1),
+                            KeyCode::Down => app.global_search.move_selection(1),
+                            KeyCode::Backspace => {
+                                app.global_search.query.pop();
+                            }
+                            KeyCode::Char(c) => app.global_search.query.push(c),
+                            _ => {}
+                        }
+                        app.last_key_press = None;
+                    } else if app.command_palette.is_open {
+                        match key.code {
+                            KeyCode::Esc => app.command_palette.close(),
+                            KeyCode::Enter => {
+                                let command = app.command_palette.selected_command();
+                                app.command_palette.close();
+                                if let Some(command) = command
+                                    && app.execute_command(command).await == CommandOutcome::Return
+                                {
+                                    return Ok(());
                                 }
-                                KeyCode::Enter | KeyCode::Char(' ') => {
-                                    app.toggle_type_selection();
+                            }
+                            KeyCode::Up => app.command_palette.move_selection(-1),
+                            KeyCode::Down => app.command_palette.move_selection(1),
+                            KeyCode::Backspace => {
+                                app.command_palette.query.pop();
+                                app.command_palette.selected = 0;
+                            }
+                            KeyCode::Char(c) => {
+                                app.command_palette.query.push(c);
+                                app.command_palette.selected = 0;
+                            }
+                            _ => {}
+                        }
+                        app.last_key_press = None;
+                    } else if app.detail_view_state.assign_picker.is_open {
+                        match key.code {
+                            KeyCode::Esc => {
+                                app.detail_view_state.assign_picker.close();
+                            }
+                            KeyCode::Enter => {
+                                app.start_assign();
+                            }
+                            KeyCode::Up => {
+                                app.detail_view_state.assign_picker.move_selection(-1);
+                            }
+                            KeyCode::Down => {
+                                app.detail_view_state.assign_picker.move_selection(1);
+                            }
+                            KeyCode::Char(_) => {
+                                let last_key = app.last_key_press;
+                                if key_matches_sequence(key, last_key, &app.keys.quit) {
+                                    app.detail_view_state.assign_picker.close();
+                                    app.last_key_press = None;
+                                } else if key_matches_sequence(key, last_key, &app.keys.next) {
+                                    app.detail_view_state.assign_picker.move_selection(1);
+                                    app.last_key_press = Some(key.code);
+                                } else if key_matches_sequence(key, last_key, &app.keys.previous) {
+                                    app.detail_view_state.assign_picker.move_selection(-1);
+                                    app.last_key_press = Some(key.code);
+                                } else {
+                                    app.last_key_press = None;
                                 }
-                                KeyCode::Up => {
-                                    app.move_type_selection(-1);
+                            }
+                            _ => {}
+                        }
+                    } else if app.board_picker.is_open {
+                        match key.code {
+                            KeyCode::Esc => app.board_picker.close(),
+                            KeyCode::Enter => {
+                                if app.switch_to_picked_board() {
+                                    return Ok(());
                                 }
-                                KeyCode::Down => {
-                                    app.move_type_selection(1);
+                                app.last_key_press = None;
+                            }
+                            KeyCode::Up => app.board_picker.move_selection(-1),
+                            KeyCode::Down => app.board_picker.move_selection(1),
+                            KeyCode::Char(_) => {
+                                let last_key = app.last_key_press;
+                                if key_matches_sequence(key, last_key, &app.keys.quit) {
+                                    app.board_picker.close();
+                                    app.last_key_press = None;
+                                } else if key_matches_sequence(key, last_key, &app.keys.next) {
+                                    app.board_picker.move_selection(1);
+                                    app.last_key_press = Some(key.code);
+                                } else if key_matches_sequence(key, last_key, &app.keys.previous) {
+                                    app.board_picker.move_selection(-1);
+                                    app.last_key_press = Some(key.code);
+                                } else {
+                                    app.last_key_press = None;
                                 }
-                                KeyCode::Char(c) => {
-                                    let last_key = app.last_key_press;
-                                    if key_matches_sequence(c, last_key, &app.keys.quit) {
-                                        app.list_view_state.type_picker.close();
-                                        app.last_key_press = None;
-                                    } else if key_matches_sequence(c, last_key, &app.keys.next) {
-                                        app.move_type_selection(1);
-                                        app.last_key_press = Some(key.code);
-                                    } else if key_matches_sequence(c, last_key, &app.keys.previous)
-                                    {
-                                        app.move_type_selection(-1);
-                                        app.last_key_press = Some(key.code);
-                                    } else {
-                                        app.last_key_press = None;
-                                    }
+                            }
+                            _ => {}
+                        }
+                    } else if app.list_view_state.open_picker().is_some() {
+                        match key.code {
+                            KeyCode::Esc => {
+                                app.close_picker();
+                            }
+                            KeyCode::Char('c') => {
+                                app.clear_picker_filters();
+                                app.close_picker();
+                            }
+                            KeyCode::Enter | KeyCode::Char(' ') => {
+                                app.toggle_picker_selection();
+                            }
+                            KeyCode::Tab
+                                if app.list_view_state.open_picker() == Some(ListPicker::Tag) =>
+                            {
+                                app.toggle_tag_match_mode();
+                            }
+                            KeyCode::Up => {
+                                app.move_picker_selection(-1);
+                            }
+                            KeyCode::Down => {
+                                app.move_picker_selection(1);
+                            }
+                            KeyCode::Char(_) => {
+                                let last_key = app.last_key_press;
+                                if key_matches_sequence(key, last_key, &app.keys.quit) {
+                                    app.close_picker();
+                                    app.last_key_press = None;
+                                } else if key_matches_sequence(key, last_key, &app.keys.next) {
+                                    app.move_picker_selection(1);
+                                    app.last_key_press = Some(key.code);
+                                } else if key_matches_sequence(key, last_key, &app.keys.previous) {
+                                    app.move_picker_selection(-1);
+                                    app.last_key_press = Some(key.code);
+                                } else {
+                                    app.last_key_press = None;
                                 }
-                                _ => {}
                             }
-                        } else {
-                            app.poll_save_completion();
+                            _ => {}
+                        }
+                    } else {
+                        if matches!(app.detail_view_state.save_status, SaveStatus::Saving) {
+                            app.last_key_press = None;
+                            continue;
+                        }
 
-                            if matches!(app.detail_view_state.save_status, SaveStatus::Saving) {
-                                app.last_key_press = None;
+                        let editing_active = app
+                            .detail_view_state
+                            .edit_state
+                            .as_ref()
+                            .is_some_and(|s| s.is_editing);
+
+                        if editing_active
+                            && key.code == KeyCode::Char('p')
+                            && key.modifiers.contains(KeyModifiers::CONTROL)
+                        {
+                            app.preview_patch();
+                            continue;
+                        }
+
+                        if editing_active
+                            && key.code == KeyCode::Char('d')
+                            && key.modifiers.contains(KeyModifiers::CONTROL)
+                        {
+                            app.save_draft();
+                            continue;
+                        }
+
+                        if editing_active && key.modifiers.contains(KeyModifiers::CONTROL) {
+                            let after_save = match key.code {
+                                KeyCode::Char('s') => Some(AfterSave::KeepEditing),
+                                KeyCode::Char('x') => Some(AfterSave::ReturnToList),
+                                _ => None,
+                            };
+                            if let Some(after_save) = after_save {
+                                app.select_active_picker_value();
+                                app.start_save(after_save);
                                 continue;
                             }
+                        }
 
-                            let editing_active = app
-                                .detail_view_state
-                                .edit_state
-                                .as_ref()
-                                .is_some_and(|s| s.is_editing);
+                        let current_char = match key.code {
+                            KeyCode::Char(c) => Some(c),
+                            _ => None,
+                        };
 
-                                    let current_char = match key.code {
-                                        KeyCode::Char(c) => Some(c),
-                                        _ => None,
-                                    };
+                        if let Some(c) = current_char {
+                            let last_key = app.last_key_press;
 
-                                    if let Some(c) = current_char {
-                                        let last_key = app.last_key_press;
+                            if key_matches_sequence(key, last_key, &app.keys.quit) {
+                                app.last_key_press = None;
+                                if !editing_active && app.close_innermost() {
+                                    continue;
+                                }
+                                if app.request_quit() == CommandOutcome::Return {
+                                    return Ok(());
+                                }
+                                continue;
+                            }
 
-                                        if key_matches_sequence(c, last_key, &app.keys.quit) {
-                                            return Ok(());
-                                        }
+                            if key_matches_sequence(key, last_key, &app.keys.help) {
+                                app.showing_help = !app.showing_help;
+                                app.last_key_press = None;
+                                continue;
+                            }
 
-                                        if key_matches_sequence(c, last_key, &app.keys.help) {
-                                            app.showing_help = !app.showing_help;
-                                            app.last_key_press = None;
+                            if editing_active {
+                                if let Some(state) = app.detail_view_state.edit_state.as_mut() {
+                                    App::clamp_active_field(state);
+                                    if App::active_picker(state).is_some() {
+                                        if key_matches_sequence(key, last_key, &app.keys.next) {
+                                            app.move_active_picker(1);
+                                            app.last_key_press = Some(key.code);
+                                            continue;
+                                        } else if key_matches_sequence(
+                                            key,
+                                            last_key,
+                                            &app.keys.previous,
+                                        ) {
+                                            app.move_active_picker(-1);
+                                            app.last_key_press = Some(key.code);
                                             continue;
-                                        }
-
-                                        if editing_active {
-                                            if let Some(state) = app.detail_view_state.edit_state.as_mut() {
-                                                App::clamp_active_field(state);
-                                                if App::active_picker(state).is_some() {
-                                            if key_matches_sequence(c, last_key, &app.keys.next) {
-                                                app.move_active_picker(1);
-                                                app.last_key_press = Some(key.code);
-                                                continue;
-                                            } else if key_matches_sequence(
-                                                c,
-                                                last_key,
-                                                &app.keys.previous,
-                                            ) {
-                                                app.move_active_picker(-1);
-                                                app.last_key_press = Some(key.code);
-                                                continue;
-                                            }
                                         }
                                     }
-
-                                    app.apply_typing(c);
-                                    app.last_key_press = None;
-                                    continue;
                                 }
 
-                                if key_matches_sequence(c, last_key, &app.keys.jump_to_top) {
-                                    app.list_view_state.is_list_details_hover_visible = false;
-                                    app.jump_to_start();
-                                } else if key_matches_sequence(c, last_key, &app.keys.jump_to_end) {
-                                    app.list_view_state.is_list_details_hover_visible = false;
-                                    app.jump_to_end();
-                                } else if key_matches_sequence(c, last_key, &app.keys.search) {
-                                    app.list_view_state.is_list_details_hover_visible = false;
-                                    app.list_view_state.is_filtering = true;
-                                    app.list_view_state.filter_query.clear();
-                                    app.clamp_selection();
-                                } else if key_matches_sequence(c, last_key, &app.keys.next) {
-                                    app.list_view_state.is_list_details_hover_visible = false;
-                                    app.navigate_list(1);
-                                } else if key_matches_sequence(c, last_key, &app.keys.previous) {
-                                    app.list_view_state.is_list_details_hover_visible = false;
-                                    app.navigate_list(-1);
-                                } else if key_matches_sequence(c, last_key, &app.keys.next_board) {
-                                    app.list_view_state.is_list_details_hover_visible = false;
-                                    app.next_source();
-                                    return Ok(());
-                                } else if key_matches_sequence(c, last_key, &app.keys.previous_board) {
-                                    app.list_view_state.is_list_details_hover_visible = false;
-                                    app.previous_source();
-                                    return Ok(());
-                                } else if key_matches_sequence(c, last_key, &app.keys.hover) {
-                                    app.list_view_state.is_list_details_hover_visible = true;
-                                } else if key_matches_sequence(c, last_key, &app.keys.open) {
-                                    app.open_item();
-                                } else if key_matches_sequence(
-                                    c,
-                                    last_key,
-                                    &app.keys.assigned_to_me_filter,
-                                ) {
-                                    app.toggle_assigned_to_me_filter()
-                                } else if key_matches_sequence(
-                                    c,
-                                    last_key,
-                                    &app.keys.work_item_type_filter,
-                                ) {
-                                    app.toggle_type_filter_menu();
-                                } else if key_matches_sequence(c, last_key, &app.keys.refresh) {
-                                    app.refresh_policy = RefreshPolicy::Normal;
-                                    app.loading_state = LoadingState::Loading;
-                                    return Ok(());
-                                } else if key_matches_sequence(c, last_key, &app.keys.full_refresh) {
-                                    app.refresh_policy = RefreshPolicy::Full;
-                                    app.loading_state = LoadingState::Loading;
-                                    return Ok(());
-                                } else if key_matches_sequence(c, last_key, &app.keys.edit_config) {
-                                    let _ = crate::config::open_config();
-                                    eprintln!("Reopen adoboards for changes to take effect");
-                                    return Ok(());
-                                } else if key_matches_sequence(c, last_key, &app.keys.edit_item) {
-                                    app.ensure_detail_state_for_selected_item().await;
-                                    app.begin_edit();
-                                        }
+                                app.apply_typing(c);
+                                app.last_key_press = None;
+                                continue;
+                            }
+
+                            if c == ' ' && app.toggle_selected_group() {
+                                app.last_key_press = None;
+                                continue;
+                            }
+
+                            if key_matches_sequence(key, last_key, &app.keys.command_palette) {
+                                app.list_view_state.is_list_details_hover_visible = false;
+                                app.command_palette.open();
+                                app.last_key_press = None;
+                                continue;
+                            }
+
+                            let command = Command::ALL.into_iter().find(|command| {
+                                key_matches_sequence(key, last_key, command.binding(&app.keys))
+                            });
+                            if let Some(command) = command
+                                && app.execute_command(command).await == CommandOutcome::Return
+                            {
+                                return Ok(());
+                            }
+
+                            if command.is_none()
+                                && let Some(index) =
+                                    app.external_commands.iter().position(|external| {
+                                        key_matches_sequence(key, last_key, &external.key)
+                                    })
+                                && let Some(command_line) = app.start_external_command(index)
+                            {
+                                match run_interactive(terminal, &command_line)? {
+                                    Ok(status) if !status.success() => app.set_status_message(
+                                        format!("{} exited with {}", command_line, status),
+                                    ),
+                                    Ok(_) => {}
+                                    Err(err) => app.set_status_message(format!(
+                                        "Failed to run {}: {}",
+                                        command_line, err
+                                    )),
+                                }
+                                app.last_key_press = None;
+                                continue;
+                            }
 
-                                        app.last_key_press = Some(key.code);
+                            app.last_key_press = Some(key.code);
+                        } else {
+                            match key.code {
+                                KeyCode::Esc => {
+                                    if editing_active {
+                                        app.cancel_edit();
                                     } else {
-                                        match key.code {
-                                            KeyCode::Esc => {
-                                                if editing_active {
-                                            app.cancel_edit();
-                                        } else {
-                                            if app.list_view_state.assigned_to_me_filter_on {
-                                                app.toggle_assigned_to_me_filter()
-                                            }
-                                            app.list_view_state.is_list_details_hover_visible =
-                                                false;
-                                            if !app.list_view_state.filter_query.is_empty() {
-                                                app.list_view_state.filter_query.clear();
-                                                app.clamp_selection();
-                                            }
-                                            if app.list_view_state.type_picker.is_open {
-                                                app.toggle_type_filter_menu();
-                                            }
-                                            app.detail_view_state.edit_state = None;
-                                        }
-                                    }
-                                    KeyCode::Up => {
-                                        if editing_active {
-                                            app.move_active_picker(-1);
-                                        } else {
-                                            app.list_view_state.is_list_details_hover_visible =
-                                                false;
-                                            app.navigate_list(-1);
-                                        }
+                                        app.close_innermost();
                                     }
-                                    KeyCode::Down => {
-                                        if editing_active {
-                                            app.move_active_picker(1);
-                                        } else {
-                                            app.list_view_state.is_list_details_hover_visible =
-                                                false;
-                                            app.navigate_list(1);
-                                        }
+                                }
+                                KeyCode::Up => {
+                                    if editing_active {
+                                        app.move_active_picker(-1);
+                                    } else {
+                                        app.list_view_state.is_list_details_hover_visible = false;
+                                        app.navigate_list(-1);
                                     }
-                                    KeyCode::Enter => {
-                                        if editing_active {
-                                            app.select_active_picker_value();
-                                            app.start_save();
-                                        }
+                                }
+                                KeyCode::Down => {
+                                    if editing_active {
+                                        app.move_active_picker(1);
+                                    } else {
+                                        app.list_view_state.is_list_details_hover_visible = false;
+                                        app.navigate_list(1);
                                     }
-                                    KeyCode::Tab => {
-                                        if let Some(state) =
-                                            app.detail_view_state.edit_state.as_mut()
-                                        {
-                                            if state.is_editing {
-                                                let total_fields = state.visible_fields.len();
-                                                let next = match state.active_field {
-                                                    DetailField::Title => {
-                                                        if total_fields == 0 {
-                                                            DetailField::Title
-                                                        } else {
-                                                            DetailField::Dynamic(0)
-                                                        }
-                                                    }
-                                                    DetailField::Dynamic(idx) => {
-                                                        if idx + 1 < total_fields {
-                                                            DetailField::Dynamic(idx + 1)
-                                                        } else {
-                                                            DetailField::Title
-                                                        }
-                                                    }
-                                                };
-                                                state.active_field = next;
-                                                App::clamp_active_field(state);
+                                }
+                                KeyCode::Left if !editing_active => {
+                                    app.scroll_detail_horizontally(-HORIZONTAL_SCROLL_STEP)
+                                }
+                                KeyCode::Right if !editing_active => {
+                                    app.scroll_detail_horizontally(HORIZONTAL_SCROLL_STEP)
+                                }
+                                KeyCode::Enter if editing_active => {
+                                    app.select_active_picker_value();
+                                    app.start_save(AfterSave::Stay);
+                                }
+                                KeyCode::Enter => app.activate_selected_item().await,
+                                KeyCode::Tab => {
+                                    if let Some(state) = app.detail_view_state.edit_state.as_mut()
+                                        && state.is_editing
+                                    {
+                                        let total_fields = state.visible_fields.len();
+                                        let next = match state.active_field {
+                                            DetailField::Title => {
+                                                if total_fields == 0 {
+                                                    DetailField::Title
+                                                } else {
+                                                    DetailField::Dynamic(0)
+                                                }
                                             }
-                                        }
+                                            DetailField::Dynamic(idx) => {
+                                                if idx + 1 < total_fields {
+                                                    DetailField::Dynamic(idx + 1)
+                                                } else {
+                                                    DetailField::Title
+                                                }
+                                            }
+                                        };
+                                        state.active_field = next;
+                                        App::clamp_active_field(state);
                                     }
-                                    KeyCode::BackTab => {
-                                        if let Some(state) =
-                                            app.detail_view_state.edit_state.as_mut()
-                                        {
-                                            if state.is_editing {
-                                                let total_fields = state.visible_fields.len();
-                                                let prev = match state.active_field {
-                                                    DetailField::Title => {
-                                                        if total_fields == 0 {
-                                                            DetailField::Title
-                                                        } else {
-                                                            DetailField::Dynamic(total_fields - 1)
-                                                        }
-                                                    }
-                                                    DetailField::Dynamic(idx) => {
-                                                        if idx == 0 {
-                                                            DetailField::Title
-                                                        } else {
-                                                            DetailField::Dynamic(idx - 1)
-                                                        }
-                                                    }
-                                                };
-                                                state.active_field = prev;
-                                                App::clamp_active_field(state);
+                                }
+                                KeyCode::BackTab => {
+                                    if let Some(state) = app.detail_view_state.edit_state.as_mut()
+                                        && state.is_editing
+                                    {
+                                        let total_fields = state.visible_fields.len();
+                                        let prev = match state.active_field {
+                                            DetailField::Title => {
+                                                if total_fields == 0 {
+                                                    DetailField::Title
+                                                } else {
+                                                    DetailField::Dynamic(total_fields - 1)
+                                                }
                                             }
-                                        }
+                                            DetailField::Dynamic(idx) => {
+                                                if idx == 0 {
+                                                    DetailField::Title
+                                                } else {
+                                                    DetailField::Dynamic(idx - 1)
+                                                }
+                                            }
+                                        };
+                                        state.active_field = prev;
+                                        App::clamp_active_field(state);
                                     }
-                                    KeyCode::Delete => {
-                                        if let Some(state) =
-                                            app.detail_view_state.edit_state.as_mut()
-                                        {
-                                            if state.is_editing {
-                                                App::clamp_active_field(state);
-                                                match state.active_field {
-                                                    DetailField::Title => state.title.clear(),
-                                                    DetailField::Dynamic(idx) => {
-                                                        if let Some(field) = state.visible_fields.get_mut(idx) {
-                                                            let picker_has_options = field
-                                                                .picker
-                                                                .as_ref()
-                                                                .map(|p| !p.options.is_empty())
-                                                                .unwrap_or(false);
-                                                            if !picker_has_options {
-                                                                field.value.clear();
-                                                            }
-                                                        }
+                                }
+                                KeyCode::Delete => {
+                                    if let Some(state) = app.detail_view_state.edit_state.as_mut()
+                                        && state.is_editing
+                                    {
+                                        App::clamp_active_field(state);
+                                        match state.active_field {
+                                            DetailField::Title => state.title.clear(),
+                                            DetailField::Dynamic(idx) => {
+                                                if let Some(field) =
+                                                    state.visible_fields.get_mut(idx)
+                                                {
+                                                    let picker_has_options = field
+                                                        .picker
+                                                        .as_ref()
+                                                        .map(|p| !p.options.is_empty())
+                                                        .unwrap_or(false);
+                                                    if !picker_has_options {
+                                                        field.value.clear();
                                                     }
                                                 }
                                             }
                                         }
                                     }
-                                    KeyCode::Backspace => {
-                                        if let Some(state) =
-                                            app.detail_view_state.edit_state.as_mut()
-                                        {
-                                            if state.is_editing {
-                                                App::clamp_active_field(state);
-                                                match state.active_field {
-                                                    DetailField::Title => {
-                                                        state.title.pop();
-                                                    }
-                                                    DetailField::Dynamic(idx) => {
-                                                        if let Some(field) = state.visible_fields.get_mut(idx) {
-                                                            let picker_has_options = field
-                                                                .picker
-                                                                .as_ref()
-                                                                .map(|p| !p.options.is_empty())
-                                                                .unwrap_or(false);
-                                                            if !picker_has_options {
-                                                                field.value.pop();
-                                                            }
-                                                        }
+                                }
+                                KeyCode::Backspace => {
+                                    if let Some(state) = app.detail_view_state.edit_state.as_mut()
+                                        && state.is_editing
+                                    {
+                                        App::clamp_active_field(state);
+                                        match state.active_field {
+                                            DetailField::Title => {
+                                                state.title.pop();
+                                            }
+                                            DetailField::Dynamic(idx) => {
+                                                if let Some(field) =
+                                                    state.visible_fields.get_mut(idx)
+                                                {
+                                                    let picker_has_options = field
+                                                        .picker
+                                                        .as_ref()
+                                                        .map(|p| !p.options.is_empty())
+                                                        .unwrap_or(false);
+                                                    if !picker_has_options {
+                                                        field.value.pop();
                                                     }
                                                 }
                                             }
                                         }
                                     }
-                                    _ => {}
                                 }
-                                app.last_key_press = None;
+                                _ => {}
                             }
+                            app.last_key_press = None;
                         }
                     }
                 }
@@ -1292,3 +5251,224 @@ pub async fn run_app<B: ratatui::backend::Backend>(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn press(code: KeyCode, modifiers: KeyModifiers) -> KeyEvent {
+        KeyEvent::new(code, modifiers)
+    }
+
+    #[test]
+    fn modified_bindings_parse() {
+        assert_eq!(
+            parse_modified_binding("ctrl+r"),
+            Some((KeyModifiers::CONTROL, 'r'))
+        );
+        assert_eq!(
+            parse_modified_binding("alt+j"),
+            Some((KeyModifiers::ALT, 'j'))
+        );
+        assert_eq!(
+            parse_modified_binding("shift+G"),
+            Some((KeyModifiers::NONE, 'G'))
+        );
+        assert_eq!(
+            parse_modified_binding("shift+g"),
+            Some((KeyModifiers::NONE, 'G'))
+        );
+        assert_eq!(parse_modified_binding("r"), None);
+        assert_eq!(parse_modified_binding("meta+r"), None);
+    }
+
+    #[test]
+    fn modified_bindings_match_only_with_their_modifiers() {
+        let ctrl_r = press(KeyCode::Char('r'), KeyModifiers::CONTROL);
+        assert!(key_matches_sequence(ctrl_r, None, "ctrl+r"));
+        assert!(!key_matches_sequence(
+            press(KeyCode::Char('r'), KeyModifiers::NONE),
+            None,
+            "ctrl+r"
+        ));
+
+        let alt_j = press(KeyCode::Char('j'), KeyModifiers::ALT);
+        assert!(key_matches_sequence(alt_j, None, "alt+j"));
+        assert!(!key_matches_sequence(alt_j, None, "ctrl+j"));
+
+        let shift_g = press(KeyCode::Char('G'), KeyModifiers::SHIFT);
+        assert!(key_matches_sequence(shift_g, None, "shift+G"));
+        assert!(key_matches_sequence(shift_g, None, "G"));
+    }
+
+    #[test]
+    fn plain_bindings_do_not_match_with_ctrl_held() {
+        let ctrl_r = press(KeyCode::Char('r'), KeyModifiers::CONTROL);
+        assert!(!key_matches_sequence(ctrl_r, None, "r"));
+        assert!(key_matches_sequence(
+            press(KeyCode::Char('r'), KeyModifiers::NONE),
+            None,
+            "r"
+        ));
+    }
+
+    #[test]
+    fn two_key_sequences_need_the_previous_key() {
+        let g = press(KeyCode::Char('g'), KeyModifiers::NONE);
+        assert!(key_matches_sequence(g, Some(KeyCode::Char('g')), "gg"));
+        assert!(!key_matches_sequence(g, None, "gg"));
+    }
+
+    fn app_with_opened_item() -> App {
+        let item: WorkItem = serde_json::from_value(serde_json::json!({
+            "id": 1,
+            "title": "Item",
+            "assigned_to": "",
+            "state": "New",
+            "work_item_type": "Task",
+            "description": "",
+            "acceptance_criteria": "",
+            "fields": {},
+        }))
+        .unwrap();
+        let mut app = App::new(AppConfig::default(), None);
+        app.detail_view_state.edit_state = Some(DetailEditState::new_from_item(&item));
+        app.items = vec![item];
+        app.loading_state = LoadingState::Loaded;
+        app
+    }
+
+    #[test]
+    fn escape_closes_the_picker_or_popup_first() {
+        let mut app = app_with_opened_item();
+        app.list_view_state.filter_query = "item".to_string();
+        app.list_view_state.type_picker.is_open = true;
+        app.list_view_state.is_list_details_hover_visible = true;
+
+        assert!(app.close_innermost());
+        assert!(app.list_view_state.open_picker().is_none());
+        assert!(app.list_view_state.is_list_details_hover_visible);
+
+        assert!(app.close_innermost());
+        assert!(!app.list_view_state.is_list_details_hover_visible);
+        assert_eq!(app.list_view_state.filter_query, "item");
+    }
+
+    #[test]
+    fn escape_clears_the_search_before_filters() {
+        let mut app = app_with_opened_item();
+        app.list_view_state.filter_query = "item".to_string();
+        app.list_view_state.find_query = "it".to_string();
+        app.list_view_state.assigned_to_me_filter_on = true;
+
+        assert!(app.close_innermost());
+        assert!(app.list_view_state.filter_query.is_empty());
+        assert!(app.list_view_state.find_query.is_empty());
+        assert!(app.list_view_state.assigned_to_me_filter_on);
+    }
+
+    #[test]
+    fn escape_turns_the_filter_off_before_closing_the_item() {
+        let mut app = app_with_opened_item();
+        app.list_view_state.assigned_to_me_filter_on = true;
+
+        assert!(app.close_innermost());
+        assert!(!app.list_view_state.assigned_to_me_filter_on);
+        assert!(app.detail_view_state.edit_state.is_some());
+    }
+
+    #[test]
+    fn escape_closes_the_opened_item() {
+        let mut app = app_with_opened_item();
+
+        assert!(app.close_innermost());
+        assert!(app.detail_view_state.edit_state.is_none());
+    }
+
+    #[test]
+    fn escape_on_a_clean_list_lets_quitting_go_ahead() {
+        let mut app = app_with_opened_item();
+        app.detail_view_state.edit_state = None;
+
+        assert!(!app.close_innermost());
+    }
+
+    #[test]
+    fn my_work_items_act_on_their_own_board() {
+        let mut config = AppConfig::default();
+        config.boards[0].organization = "org-a".to_string();
+        config.boards[0].project = "alpha".to_string();
+        let mut app = App::new(config, None);
+        app.enable_my_work();
+        let mut item: WorkItem = serde_json::from_value(serde_json::json!({
+            "id": 7,
+            "title": "Item",
+            "assigned_to": "",
+            "state": "New",
+            "work_item_type": "Task",
+            "description": "",
+            "acceptance_criteria": "",
+            "fields": {},
+        }))
+        .unwrap();
+        item.board = Some(BoardTag {
+            title: app.sources[1].title.clone(),
+            organization: "org-a".to_string(),
+            project: "alpha".to_string(),
+        });
+
+        let source = app.item_source(&item).unwrap();
+        assert_eq!(source.project, "alpha");
+        assert_eq!(app.item_project(&item), ("org-a", "alpha"));
+
+        item.board.as_mut().unwrap().title = "Removed board".to_string();
+        assert!(app.item_source(&item).is_none());
+        app.items = vec![item];
+        app.loading_state = LoadingState::Loaded;
+        app.list_view_state.list_state.select(Some(0));
+        assert!(app.selected_item_source().is_none());
+        assert_eq!(
+            app.status_message.map(|message| message.text).as_deref(),
+            Some("The board of #7 is no longer configured")
+        );
+    }
+
+    #[test]
+    fn date_field_survives_an_unrelated_edit() {
+        const DUE_DATE: &str = "Microsoft.VSTS.Scheduling.DueDate";
+        let stored = "2024-01-05T10:22:33.12Z";
+        let item: WorkItem = serde_json::from_value(serde_json::json!({
+            "id": 1,
+            "title": "Item",
+            "assigned_to": "",
+            "state": "New",
+            "work_item_type": "Task",
+            "description": "",
+            "acceptance_criteria": "",
+            "fields": { DUE_DATE: stored, STATE_FIELD: "New" },
+            "raw_fields": { DUE_DATE: stored, STATE_FIELD: "New" },
+        }))
+        .unwrap();
+        let field_formats = BTreeMap::from([(DUE_DATE.to_string(), FieldFormat::Date)]);
+        let fields = [
+            VisibleField::with_value("Due".to_string(), DUE_DATE.to_string(), String::new(), None),
+            VisibleField::with_value(
+                "State".to_string(),
+                STATE_FIELD.to_string(),
+                String::new(),
+                None,
+            ),
+        ];
+
+        let mut state = App::rebuild_edit_state_from_item(&item, &fields, &field_formats);
+        assert_eq!(state.visible_fields[0].value, stored);
+        state.visible_fields[1].value = "Active".to_string();
+
+        let paths: Vec<String> = build_update_operations(&state, &field_formats)
+            .into_iter()
+            .filter_map(|operation| operation.path)
+            .collect();
+        assert!(paths.contains(&format!("/fields/{}", STATE_FIELD)));
+        assert!(!paths.contains(&format!("/fields/{}", DUE_DATE)));
+    }
+}
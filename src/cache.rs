@@ -1,13 +1,14 @@
+use std::collections::{BTreeMap, BTreeSet};
 use std::fs;
 use std::path::{Path, PathBuf};
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
-use anyhow::{anyhow, Context, Result};
+use anyhow::{Context, Result, anyhow};
 use serde::{Deserialize, Serialize};
 
-use crate::config::APPNAME;
+use crate::config::{APPNAME, DEFAULT_BACKLOG_LEVEL};
 use crate::models::WorkItem;
-use crate::services::WorkItemFieldInfo;
+use crate::services::{ResolvedIteration, WorkItemFieldInfo};
 
 #[derive(Clone, Debug)]
 pub enum WorkItemsCacheKey {
@@ -15,6 +16,7 @@ pub enum WorkItemsCacheKey {
         organization: String,
         project: String,
         team: String,
+        backlog_level: String,
     },
     Iteration {
         organization: String,
@@ -22,6 +24,8 @@ pub enum WorkItemsCacheKey {
         team: String,
         iteration: String,
     },
+    /// Items assigned to `me` across every configured board.
+    MyWork,
 }
 
 #[derive(Clone, Debug)]
@@ -38,6 +42,28 @@ pub struct FieldMetaCacheKey {
     pub work_item_type: String,
 }
 
+#[derive(Clone, Debug)]
+pub struct IterationIdCacheKey {
+    pub organization: String,
+    pub project: String,
+    pub team: String,
+    pub iteration: String,
+}
+
+#[derive(Clone, Debug)]
+pub struct DraftKey {
+    pub organization: String,
+    pub item_id: u32,
+}
+
+/// Unsent edits of a work item: the title and the values of its fields by
+/// reference name.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct EditDraft {
+    pub title: String,
+    pub fields: Vec<(String, String)>,
+}
+
 #[derive(Serialize, Deserialize)]
 struct WorkItemsCacheEntry {
     updated_at: u64,
@@ -56,6 +82,22 @@ struct FieldMetaCacheEntry {
     fields: Vec<WorkItemFieldInfo>,
 }
 
+#[derive(Serialize, Deserialize)]
+struct IterationIdCacheEntry {
+    updated_at: u64,
+    iteration_id: String,
+    #[serde(default)]
+    valid_until: Option<u64>,
+    #[serde(default)]
+    name: Option<String>,
+    #[serde(default)]
+    path: Option<String>,
+    #[serde(default)]
+    start_date: Option<u64>,
+    #[serde(default)]
+    finish_date: Option<u64>,
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct LayoutControlEntry {
     pub id: String,
@@ -63,8 +105,11 @@ pub struct LayoutControlEntry {
 }
 
 fn now_secs() -> u64 {
-    SystemTime::now()
-        .duration_since(UNIX_EPOCH)
+    unix_secs(SystemTime::now())
+}
+
+fn unix_secs(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH)
         .unwrap_or_default()
         .as_secs()
 }
@@ -86,17 +131,36 @@ fn cache_root() -> Result<PathBuf> {
 
 fn work_items_cache_path(key: &WorkItemsCacheKey) -> Result<PathBuf> {
     let base = cache_root()?.join("work_items");
-    let name = match key {
+    Ok(base.join(source_file_name(key)))
+}
+
+/// `backlog_…` or `iteration_…` file name identifying the source of a list.
+fn source_file_name(key: &WorkItemsCacheKey) -> String {
+    match key {
+        // The default level keeps the name used before levels were configurable.
         WorkItemsCacheKey::Backlog {
             organization,
             project,
             team,
-        } => format!(
+            backlog_level,
+        } if backlog_level == DEFAULT_BACKLOG_LEVEL => format!(
             "backlog_{}_{}_{}.json",
             sanitize_component(organization),
             sanitize_component(project),
             sanitize_component(team)
         ),
+        WorkItemsCacheKey::Backlog {
+            organization,
+            project,
+            team,
+            backlog_level,
+        } => format!(
+            "backlog_{}_{}_{}_{}.json",
+            sanitize_component(organization),
+            sanitize_component(project),
+            sanitize_component(team),
+            sanitize_component(backlog_level)
+        ),
         WorkItemsCacheKey::Iteration {
             organization,
             project,
@@ -109,8 +173,8 @@ fn work_items_cache_path(key: &WorkItemsCacheKey) -> Result<PathBuf> {
             sanitize_component(team),
             sanitize_component(iteration)
         ),
-    };
-    Ok(base.join(name))
+        WorkItemsCacheKey::MyWork => "my_work.json".to_string(),
+    }
 }
 
 fn layout_cache_path(key: &LayoutCacheKey) -> Result<PathBuf> {
@@ -135,13 +199,50 @@ fn field_meta_cache_path(key: &FieldMetaCacheKey) -> Result<PathBuf> {
     Ok(base.join(name))
 }
 
-fn is_fresh(updated_at: u64, max_age: Duration) -> bool {
-    if let Some(updated) = UNIX_EPOCH.checked_add(Duration::from_secs(updated_at)) {
-        if let Ok(elapsed) = SystemTime::now().duration_since(updated) {
-            return elapsed <= max_age;
-        }
-    }
-    false
+fn iteration_id_cache_path(key: &IterationIdCacheKey) -> Result<PathBuf> {
+    let base = cache_root()?.join("iteration_id");
+    let name = format!(
+        "iterationid_{}_{}_{}_{}.json",
+        sanitize_component(&key.organization),
+        sanitize_component(&key.project),
+        sanitize_component(&key.team),
+        sanitize_component(&key.iteration)
+    );
+    Ok(base.join(name))
+}
+
+/// Drafts hold edits that exist nowhere else, so pruning leaves them alone.
+const DRAFTS_DIR: &str = "drafts";
+
+fn draft_path(key: &DraftKey) -> Result<PathBuf> {
+    let base = cache_root()?.join(DRAFTS_DIR);
+    let name = format!(
+        "draft_{}_{}.json",
+        sanitize_component(&key.organization),
+        key.item_id
+    );
+    Ok(base.join(name))
+}
+
+/// The seen state only lives here, so pruning leaves it alone like drafts.
+const SEEN_DIR: &str = "seen";
+
+fn seen_path(key: &WorkItemsCacheKey) -> Result<PathBuf> {
+    Ok(cache_root()?.join(SEEN_DIR).join(source_file_name(key)))
+}
+
+/// Pinned items are only kept here too, so pruning leaves them alone.
+const PINNED_DIR: &str = "pinned";
+
+fn pinned_path(key: &WorkItemsCacheKey) -> Result<PathBuf> {
+    Ok(cache_root()?.join(PINNED_DIR).join(source_file_name(key)))
+}
+
+/// Whether an entry written at `updated_at` is at most `max_age` old at
+/// `now` (both in unix seconds). Entries from the future are never fresh.
+fn is_fresh(updated_at: u64, max_age: Duration, now: u64) -> bool {
+    now.checked_sub(updated_at)
+        .is_some_and(|elapsed| elapsed <= max_age.as_secs())
 }
 
 fn ensure_parent_dir(path: &Path) -> Result<()> {
@@ -152,7 +253,80 @@ fn ensure_parent_dir(path: &Path) -> Result<()> {
     Ok(())
 }
 
-pub fn read_work_items_cache(key: &WorkItemsCacheKey, max_age: Duration) -> Option<Vec<WorkItem>> {
+/// Deletes cache files last written more than `max_age` ago, then removes the
+/// oldest remaining files until the cache fits within `max_bytes`. A `None`
+/// limit is not enforced. Returns the number of removed files.
+pub fn prune_cache(max_age: Option<Duration>, max_bytes: Option<u64>) -> Result<usize> {
+    let root = cache_root()?;
+    let subdirs = match fs::read_dir(&root) {
+        Ok(entries) => entries,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(0),
+        Err(err) => {
+            return Err(err)
+                .with_context(|| format!("Failed to read cache directory: {}", root.display()));
+        }
+    };
+
+    let mut files: Vec<(PathBuf, SystemTime, u64)> = Vec::new();
+    for subdir in subdirs.flatten() {
+        let dir_path = subdir.path();
+        let kept = [DRAFTS_DIR, SEEN_DIR, PINNED_DIR];
+        if !dir_path.is_dir() || kept.iter().any(|dir| subdir.file_name() == *dir) {
+            continue;
+        }
+        let entries = fs::read_dir(&dir_path)
+            .with_context(|| format!("Failed to read cache directory: {}", dir_path.display()))?;
+        for entry in entries.flatten() {
+            let Ok(metadata) = entry.metadata() else {
+                continue;
+            };
+            if !metadata.is_file() {
+                continue;
+            }
+            let modified = metadata.modified().unwrap_or(UNIX_EPOCH);
+            files.push((entry.path(), modified, metadata.len()));
+        }
+    }
+
+    // Oldest first, so the size cap evicts the least recently written files.
+    files.sort_by_key(|(_, modified, _)| *modified);
+
+    let now = SystemTime::now();
+    let mut removed = 0;
+    let mut kept = Vec::new();
+    for (path, modified, len) in files {
+        let age = now.duration_since(modified).unwrap_or_default();
+        let expired = max_age.is_some_and(|max_age| age > max_age);
+        if expired && fs::remove_file(&path).is_ok() {
+            removed += 1;
+        } else {
+            kept.push((path, len));
+        }
+    }
+
+    if let Some(max_bytes) = max_bytes {
+        let mut total: u64 = kept.iter().map(|(_, len)| len).sum();
+        for (path, len) in kept {
+            if total <= max_bytes {
+                break;
+            }
+            if fs::remove_file(&path).is_ok() {
+                total = total.saturating_sub(len);
+                removed += 1;
+            }
+        }
+    }
+
+    Ok(removed)
+}
+
+/// Cached items of a list, unless they were written more than `max_age`
+/// before `now`.
+pub fn read_work_items_cache(
+    key: &WorkItemsCacheKey,
+    max_age: Duration,
+    now: SystemTime,
+) -> Option<Vec<WorkItem>> {
     let path = match work_items_cache_path(key) {
         Ok(p) => p,
         Err(_) => {
@@ -166,13 +340,17 @@ pub fn read_work_items_cache(key: &WorkItemsCacheKey, max_age: Duration) -> Opti
             return None;
         }
     };
-    let entry: WorkItemsCacheEntry = match serde_json::from_slice(&data) {
+    fresh_work_items(&data, max_age, unix_secs(now))
+}
+
+fn fresh_work_items(data: &[u8], max_age: Duration, now: u64) -> Option<Vec<WorkItem>> {
+    let entry: WorkItemsCacheEntry = match serde_json::from_slice(data) {
         Ok(v) => v,
         Err(_) => {
             return None;
         }
     };
-    if is_fresh(entry.updated_at, max_age) {
+    if is_fresh(entry.updated_at, max_age, now) {
         Some(entry.items)
     } else {
         None
@@ -192,6 +370,27 @@ pub fn write_work_items_cache(key: &WorkItemsCacheKey, items: &[WorkItem]) -> Re
     Ok(())
 }
 
+/// Replaces the cached copy of `item` after a change was saved, so the cache
+/// doesn't show the old values on the next start. The cache keeps its age;
+/// without a cache file, or when the item isn't in it, nothing is written.
+pub fn update_cached_work_item(key: &WorkItemsCacheKey, item: &WorkItem) -> Result<()> {
+    let path = work_items_cache_path(key)?;
+    let data = match fs::read(&path) {
+        Ok(d) => d,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(()),
+        Err(err) => return Err(err.into()),
+    };
+    let mut entry: WorkItemsCacheEntry = serde_json::from_slice(&data)?;
+    let Some(cached) = entry.items.iter_mut().find(|cached| cached.id == item.id) else {
+        return Ok(());
+    };
+    *cached = item.clone();
+    let json = serde_json::to_vec_pretty(&entry)?;
+    fs::write(&path, json)
+        .with_context(|| format!("Failed to write work item cache: {}", path.display()))?;
+    Ok(())
+}
+
 pub fn read_layout_cache(key: &LayoutCacheKey) -> Option<Vec<(String, String)>> {
     let path = match layout_cache_path(key) {
         Ok(p) => p,
@@ -272,3 +471,143 @@ pub fn write_field_meta_cache(key: &FieldMetaCacheKey, fields: &[WorkItemFieldIn
         .with_context(|| format!("Failed to write field meta cache: {}", path.display()))?;
     Ok(())
 }
+
+pub fn read_iteration_id_cache(
+    key: &IterationIdCacheKey,
+    max_age: Duration,
+) -> Option<ResolvedIteration> {
+    let path = iteration_id_cache_path(key).ok()?;
+    let data = fs::read(&path).ok()?;
+    let entry: IterationIdCacheEntry = serde_json::from_slice(&data).ok()?;
+    let now = now_secs();
+    let expired = entry
+        .valid_until
+        .is_some_and(|valid_until| now >= valid_until);
+    if is_fresh(entry.updated_at, max_age, now) && !expired {
+        Some(ResolvedIteration {
+            id: entry.iteration_id,
+            name: entry.name,
+            path: entry.path,
+            start_date: entry.start_date,
+            finish_date: entry.finish_date,
+            valid_until: entry.valid_until,
+        })
+    } else {
+        None
+    }
+}
+
+pub fn write_iteration_id_cache(
+    key: &IterationIdCacheKey,
+    iteration: &ResolvedIteration,
+) -> Result<()> {
+    let path = iteration_id_cache_path(key)?;
+    ensure_parent_dir(&path)?;
+    let entry = IterationIdCacheEntry {
+        updated_at: now_secs(),
+        iteration_id: iteration.id.clone(),
+        valid_until: iteration.valid_until,
+        name: iteration.name.clone(),
+        path: iteration.path.clone(),
+        start_date: iteration.start_date,
+        finish_date: iteration.finish_date,
+    };
+    let json = serde_json::to_vec_pretty(&entry)?;
+    fs::write(&path, json)
+        .with_context(|| format!("Failed to write iteration id cache: {}", path.display()))?;
+    Ok(())
+}
+
+pub fn read_draft(key: &DraftKey) -> Option<EditDraft> {
+    let path = draft_path(key).ok()?;
+    let data = fs::read(&path).ok()?;
+    serde_json::from_slice(&data).ok()
+}
+
+pub fn write_draft(key: &DraftKey, draft: &EditDraft) -> Result<()> {
+    let path = draft_path(key)?;
+    ensure_parent_dir(&path)?;
+    let json = serde_json::to_vec_pretty(draft)?;
+    fs::write(&path, json).with_context(|| format!("Failed to write draft: {}", path.display()))?;
+    Ok(())
+}
+
+pub fn remove_draft(key: &DraftKey) -> Result<()> {
+    let path = draft_path(key)?;
+    match fs::remove_file(&path) {
+        Err(err) if err.kind() != std::io::ErrorKind::NotFound => {
+            Err(err).with_context(|| format!("Failed to remove draft: {}", path.display()))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Changed date of every item of a source as it was when last opened, by id.
+pub fn read_seen(key: &WorkItemsCacheKey) -> Option<BTreeMap<u32, String>> {
+    let path = seen_path(key).ok()?;
+    let data = fs::read(&path).ok()?;
+    serde_json::from_slice(&data).ok()
+}
+
+pub fn write_seen(key: &WorkItemsCacheKey, seen: &BTreeMap<u32, String>) -> Result<()> {
+    let path = seen_path(key)?;
+    ensure_parent_dir(&path)?;
+    let json = serde_json::to_vec_pretty(seen)?;
+    fs::write(&path, json)
+        .with_context(|| format!("Failed to write seen items: {}", path.display()))?;
+    Ok(())
+}
+
+/// Ids of the items pinned to the top of a source's list.
+pub fn read_pinned(key: &WorkItemsCacheKey) -> Option<BTreeSet<u32>> {
+    let path = pinned_path(key).ok()?;
+    let data = fs::read(&path).ok()?;
+    serde_json::from_slice(&data).ok()
+}
+
+pub fn write_pinned(key: &WorkItemsCacheKey, pinned: &BTreeSet<u32>) -> Result<()> {
+    let path = pinned_path(key)?;
+    ensure_parent_dir(&path)?;
+    let json = serde_json::to_vec_pretty(pinned)?;
+    fs::write(&path, json)
+        .with_context(|| format!("Failed to write pinned items: {}", path.display()))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MAX_AGE: Duration = Duration::from_secs(60);
+
+    fn entry_written_at(updated_at: u64) -> Vec<u8> {
+        serde_json::to_vec(&WorkItemsCacheEntry {
+            updated_at,
+            items: Vec::new(),
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn entry_within_max_age_is_fresh() {
+        assert!(is_fresh(1_000, MAX_AGE, 1_030));
+        assert!(fresh_work_items(&entry_written_at(1_000), MAX_AGE, 1_030).is_some());
+    }
+
+    #[test]
+    fn entry_exactly_max_age_old_is_fresh() {
+        assert!(is_fresh(1_000, MAX_AGE, 1_060));
+        assert!(fresh_work_items(&entry_written_at(1_000), MAX_AGE, 1_060).is_some());
+    }
+
+    #[test]
+    fn entry_past_max_age_is_expired() {
+        assert!(!is_fresh(1_000, MAX_AGE, 1_061));
+        assert!(fresh_work_items(&entry_written_at(1_000), MAX_AGE, 1_061).is_none());
+    }
+
+    #[test]
+    fn entry_from_the_future_is_not_fresh() {
+        assert!(!is_fresh(1_000, MAX_AGE, 999));
+    }
+}
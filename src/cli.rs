@@ -0,0 +1,49 @@
+use std::path::PathBuf;
+
+/// Environment variable that points adoboards at a specific configuration file.
+pub const CONFIG_ENV_VAR: &str = "ADO_CONFIG";
+
+#[derive(Clone, Debug, Default)]
+pub struct CliArgs {
+    pub config_path: Option<PathBuf>,
+    /// Start on a merged view of the items assigned to `me` across all boards.
+    pub my_work: bool,
+    /// Enable troubleshooting helpers such as the field inspector.
+    pub debug: bool,
+    /// Print how the HTML cleaner renders a file (stdin without a path or
+    /// with `-`) instead of starting the app.
+    pub clean_html: Option<Option<PathBuf>>,
+}
+
+impl CliArgs {
+    pub fn parse() -> Self {
+        let mut cli_args = CliArgs::default();
+        let mut args = std::env::args().skip(1).peekable();
+        while let Some(arg) = args.next() {
+            if arg == "--config" {
+                cli_args.config_path = args.next().map(PathBuf::from);
+            } else if let Some(path) = arg.strip_prefix("--config=") {
+                cli_args.config_path = Some(PathBuf::from(path));
+            } else if arg == "--my-work" {
+                cli_args.my_work = true;
+            } else if arg == "--debug" {
+                cli_args.debug = true;
+            } else if arg == "--clean-html" {
+                let path = args
+                    .next_if(|path| !path.starts_with("--"))
+                    .filter(|path| path != "-")
+                    .map(PathBuf::from);
+                cli_args.clean_html = Some(path);
+            }
+        }
+
+        if cli_args.config_path.is_none() {
+            cli_args.config_path = std::env::var(CONFIG_ENV_VAR)
+                .ok()
+                .filter(|path| !path.is_empty())
+                .map(PathBuf::from);
+        }
+
+        cli_args
+    }
+}
@@ -0,0 +1,14 @@
+use std::io::Write;
+
+use anyhow::Result;
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+
+/// Copies text to the system clipboard with the OSC 52 terminal escape
+/// sequence, which also works over SSH and inside tmux (with `set-clipboard on`).
+pub fn copy_to_clipboard(text: &str) -> Result<()> {
+    let mut stdout = std::io::stdout();
+    write!(stdout, "\x1b]52;c;{}\x07", STANDARD.encode(text))?;
+    stdout.flush()?;
+    Ok(())
+}
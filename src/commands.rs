@@ -0,0 +1,336 @@
+use crate::config::KeysConfig;
+
+/// A named action that can be triggered by its key binding or from the
+/// command palette.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Command {
+    JumpToTop,
+    JumpToEnd,
+    Search,
+    Find,
+    FindNext,
+    FindPrevious,
+    GlobalSearch,
+    Next,
+    Previous,
+    NextBoard,
+    PreviousBoard,
+    PickBoard,
+    Hover,
+    PeekTitle,
+    PinDetails,
+    ToggleSummary,
+    Open,
+    OpenBoard,
+    CopyBranchName,
+    OpenRepo,
+    OpenPullRequest,
+    AssignedToMeFilter,
+    WorkItemTypeFilter,
+    CycleTypeFilter,
+    CurrentSprintFilter,
+    BoardColumnFilter,
+    TagFilter,
+    HideDone,
+    GroupByAssignee,
+    CollapseAllGroups,
+    ExpandAllGroups,
+    BypassFilters,
+    Refresh,
+    HardRefresh,
+    FullRefresh,
+    EditConfig,
+    ReloadConfig,
+    EditItem,
+    RefreshItem,
+    RetryFieldMetadata,
+    Reassign,
+    AssignToMe,
+    Follow,
+    CloneItem,
+    RankUp,
+    RankDown,
+    MoveMode,
+    CycleSort,
+    ReverseSort,
+    NarrowList,
+    WidenList,
+    CollapseDescription,
+    CollapseAcceptanceCriteria,
+    RawDescription,
+    InspectFields,
+    ErrorLog,
+    TogglePin,
+    Help,
+    Quit,
+}
+
+/// What `run_app` should do after a command ran.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CommandOutcome {
+    /// Keep handling input.
+    Continue,
+    /// Return to the main loop, e.g. to reload or to exit.
+    Return,
+}
+
+impl Command {
+    /// Every command, in the order their key bindings are matched.
+    pub const ALL: [Command; 59] = [
+        Command::JumpToTop,
+        Command::JumpToEnd,
+        Command::Search,
+        Command::Find,
+        Command::FindNext,
+        Command::FindPrevious,
+        Command::GlobalSearch,
+        Command::Next,
+        Command::Previous,
+        Command::NextBoard,
+        Command::PreviousBoard,
+        Command::PickBoard,
+        Command::Hover,
+        Command::PeekTitle,
+        Command::PinDetails,
+        Command::ToggleSummary,
+        Command::Open,
+        Command::OpenBoard,
+        Command::CopyBranchName,
+        Command::OpenRepo,
+        Command::OpenPullRequest,
+        Command::AssignedToMeFilter,
+        Command::WorkItemTypeFilter,
+        Command::CycleTypeFilter,
+        Command::CurrentSprintFilter,
+        Command::BoardColumnFilter,
+        Command::TagFilter,
+        Command::HideDone,
+        Command::GroupByAssignee,
+        Command::CollapseAllGroups,
+        Command::ExpandAllGroups,
+        Command::BypassFilters,
+        Command::Refresh,
+        Command::HardRefresh,
+        Command::FullRefresh,
+        Command::EditConfig,
+        Command::ReloadConfig,
+        Command::EditItem,
+        Command::RefreshItem,
+        Command::RetryFieldMetadata,
+        Command::Reassign,
+        Command::AssignToMe,
+        Command::Follow,
+        Command::CloneItem,
+        Command::RankUp,
+        Command::RankDown,
+        Command::MoveMode,
+        Command::CycleSort,
+        Command::ReverseSort,
+        Command::NarrowList,
+        Command::WidenList,
+        Command::CollapseDescription,
+        Command::CollapseAcceptanceCriteria,
+        Command::RawDescription,
+        Command::InspectFields,
+        Command::ErrorLog,
+        Command::TogglePin,
+        Command::Help,
+        Command::Quit,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Command::JumpToTop => "Jump to first item",
+            Command::JumpToEnd => "Jump to last item",
+            Command::Search => "Search by id / title",
+            Command::Find => "Find in list without filtering",
+            Command::FindNext => "Jump to next match",
+            Command::FindPrevious => "Jump to previous match",
+            Command::GlobalSearch => "Search all boards",
+            Command::Next => "Next item",
+            Command::Previous => "Previous item",
+            Command::NextBoard => "Next board",
+            Command::PreviousBoard => "Previous board",
+            Command::PickBoard => "Switch to a board",
+            Command::Hover => "Show item details popup",
+            Command::PeekTitle => "Show full title",
+            Command::PinDetails => "Toggle details pane next to the list",
+            Command::ToggleSummary => "Toggle item counts above the list",
+            Command::Open => "Open item in browser",
+            Command::OpenBoard => "Open board in browser",
+            Command::CopyBranchName => "Copy branch name",
+            Command::OpenRepo => "Open linked repository in editor",
+            Command::OpenPullRequest => "Open linked pull request in browser",
+            Command::AssignedToMeFilter => "Toggle assigned-to-me filter",
+            Command::WorkItemTypeFilter => "Filter by work item type",
+            Command::CycleTypeFilter => "Cycle through single work item types",
+            Command::CurrentSprintFilter => "Show only items in the current sprint",
+            Command::BoardColumnFilter => "Filter by board column",
+            Command::TagFilter => "Filter by tag",
+            Command::HideDone => "Toggle hiding done items",
+            Command::GroupByAssignee => "Group by assignee",
+            Command::CollapseAllGroups => "Collapse all groups",
+            Command::ExpandAllGroups => "Expand all groups",
+            Command::BypassFilters => "Toggle all filters off / back on",
+            Command::Refresh => "Reload board",
+            Command::HardRefresh => "Reload board and clear filters",
+            Command::FullRefresh => "Reload board, layouts and field information",
+            Command::EditConfig => "Edit configuration",
+            Command::ReloadConfig => "Reload configuration",
+            Command::EditItem => "Edit item",
+            Command::RefreshItem => "Refresh selected item",
+            Command::RetryFieldMetadata => "Retry loading allowed values",
+            Command::Reassign => "Assign to team member",
+            Command::AssignToMe => "Assign to me",
+            Command::Follow => "Follow / unfollow item",
+            Command::CloneItem => "Create a copy of the item",
+            Command::RankUp => "Move item up in the backlog",
+            Command::RankDown => "Move item down in the backlog",
+            Command::MoveMode => "Start / stop moving the item with j / k",
+            Command::CycleSort => "Sort by the next key",
+            Command::ReverseSort => "Reverse the sort order",
+            Command::NarrowList => "Narrow the list",
+            Command::WidenList => "Widen the list",
+            Command::CollapseDescription => "Collapse / expand description",
+            Command::CollapseAcceptanceCriteria => "Collapse / expand acceptance criteria",
+            Command::RawDescription => "Show raw / cleaned description",
+            Command::InspectFields => "Inspect field reference names",
+            Command::ErrorLog => "Show recent errors",
+            Command::TogglePin => "Pin / unpin item to the top",
+            Command::Help => "Show help",
+            Command::Quit => "Quit",
+        }
+    }
+
+    pub fn binding(self, keys: &KeysConfig) -> &str {
+        match self {
+            Command::JumpToTop => &keys.jump_to_top,
+            Command::JumpToEnd => &keys.jump_to_end,
+            Command::Search => &keys.search,
+            Command::Find => &keys.find,
+            Command::FindNext => &keys.find_next,
+            Command::FindPrevious => &keys.find_previous,
+            Command::GlobalSearch => &keys.global_search,
+            Command::Next => &keys.next,
+            Command::Previous => &keys.previous,
+            Command::NextBoard => &keys.next_board,
+            Command::PreviousBoard => &keys.previous_board,
+            Command::PickBoard => &keys.pick_board,
+            Command::Hover => &keys.hover,
+            Command::PeekTitle => &keys.peek_title,
+            Command::PinDetails => &keys.pin_details,
+            Command::ToggleSummary => &keys.toggle_summary,
+            Command::Open => &keys.open,
+            Command::OpenBoard => &keys.open_board,
+            Command::CopyBranchName => &keys.copy_branch_name,
+            Command::OpenRepo => &keys.open_repo,
+            Command::OpenPullRequest => &keys.open_pull_request,
+            Command::AssignedToMeFilter => &keys.assigned_to_me_filter,
+            Command::WorkItemTypeFilter => &keys.work_item_type_filter,
+            Command::CycleTypeFilter => &keys.cycle_type_filter,
+            Command::CurrentSprintFilter => &keys.current_sprint_filter,
+            Command::BoardColumnFilter => &keys.board_column_filter,
+            Command::TagFilter => &keys.tag_filter,
+            Command::HideDone => &keys.hide_done,
+            Command::GroupByAssignee => &keys.group_by_assignee,
+            Command::CollapseAllGroups => &keys.collapse_all_groups,
+            Command::ExpandAllGroups => &keys.expand_all_groups,
+            Command::BypassFilters => &keys.bypass_filters,
+            Command::Refresh => &keys.refresh,
+            Command::HardRefresh => &keys.hard_refresh,
+            Command::FullRefresh => &keys.full_refresh,
+            Command::EditConfig => &keys.edit_config,
+            Command::ReloadConfig => &keys.reload_config,
+            Command::EditItem => &keys.edit_item,
+            Command::RefreshItem => &keys.refresh_item,
+            Command::RetryFieldMetadata => &keys.retry_field_metadata,
+            Command::Reassign => &keys.reassign,
+            Command::AssignToMe => &keys.assign_to_me,
+            Command::Follow => &keys.follow,
+            Command::CloneItem => &keys.clone_item,
+            Command::RankUp => &keys.rank_up,
+            Command::RankDown => &keys.rank_down,
+            Command::MoveMode => &keys.move_mode,
+            Command::CycleSort => &keys.cycle_sort,
+            Command::ReverseSort => &keys.reverse_sort,
+            Command::NarrowList => &keys.narrow_list,
+            Command::WidenList => &keys.widen_list,
+            Command::CollapseDescription => &keys.collapse_description,
+            Command::CollapseAcceptanceCriteria => &keys.collapse_acceptance_criteria,
+            Command::RawDescription => &keys.raw_description,
+            Command::InspectFields => &keys.inspect_fields,
+            Command::ErrorLog => &keys.error_log,
+            Command::TogglePin => &keys.toggle_pin,
+            Command::Help => &keys.help,
+            Command::Quit => &keys.quit,
+        }
+    }
+}
+
+/// Score of `query` as a case-insensitive subsequence of `text`; lower is a
+/// better match. `None` when not every query character appears in order.
+pub fn fuzzy_score(query: &str, text: &str) -> Option<usize> {
+    let text: Vec<char> = text.to_lowercase().chars().collect();
+    let mut score = 0;
+    let mut position = 0;
+    let mut previous_match: Option<usize> = None;
+
+    for query_char in query.to_lowercase().chars().filter(|c| !c.is_whitespace()) {
+        let offset = text[position..].iter().position(|c| *c == query_char)?;
+        let matched = position + offset;
+        score += match previous_match {
+            Some(previous) => matched - previous - 1,
+            None => matched,
+        };
+        previous_match = Some(matched);
+        position = matched + 1;
+    }
+
+    Some(score)
+}
+
+#[derive(Default)]
+pub struct CommandPaletteState {
+    pub is_open: bool,
+    pub query: String,
+    pub selected: usize,
+}
+
+impl CommandPaletteState {
+    pub fn open(&mut self) {
+        self.is_open = true;
+        self.query.clear();
+        self.selected = 0;
+    }
+
+    pub fn close(&mut self) {
+        self.is_open = false;
+        self.query.clear();
+        self.selected = 0;
+    }
+
+    /// Commands matching the query, best match first.
+    pub fn matches(&self) -> Vec<Command> {
+        let mut scored: Vec<(usize, Command)> = Command::ALL
+            .into_iter()
+            .filter_map(|command| {
+                fuzzy_score(&self.query, command.name()).map(|score| (score, command))
+            })
+            .collect();
+        scored.sort_by_key(|(score, _)| *score);
+        scored.into_iter().map(|(_, command)| command).collect()
+    }
+
+    pub fn move_selection(&mut self, delta: isize) {
+        let count = self.matches().len();
+        if count == 0 {
+            self.selected = 0;
+            return;
+        }
+        self.selected = (self.selected as isize + delta).clamp(0, count as isize - 1) as usize;
+    }
+
+    pub fn selected_command(&self) -> Option<Command> {
+        self.matches().get(self.selected).copied()
+    }
+}
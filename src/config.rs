@@ -1,49 +1,391 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
 use std::process::Command;
 
-use anyhow::Result;
+use anyhow::{Result, anyhow};
+use lazy_static::lazy_static;
+use regex::{Captures, Regex};
 use serde::{Deserialize, Serialize};
 
 pub const APPNAME: &str = "adoboards";
 
+/// Seconds a request to ADO may take unless `request_timeout_seconds` is set.
+pub const DEFAULT_REQUEST_TIMEOUT_SECONDS: u64 = 30;
+
+/// Requests bulk loads keep in flight unless `max_concurrent_requests` is set.
+pub const DEFAULT_MAX_CONCURRENT_REQUESTS: usize = 4;
+
+/// Backlog level shown by boards that don't set `backlog_level`.
+pub const DEFAULT_BACKLOG_LEVEL: &str = "Microsoft.RequirementCategory";
+
 #[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
 pub struct BoardConfig {
+    /// Shown instead of the generated `<team> Backlog` title.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
     pub organization: String,
     pub project: String,
     pub team: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub me: Option<String>,
+    /// Replaces `common.me_aliases` for this board.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub me_aliases: Option<Vec<String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_items: Option<usize>,
+    /// Backlog category to list, e.g. `Microsoft.FeatureCategory`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub backlog_level: Option<String>,
 }
 
 impl Default for BoardConfig {
     fn default() -> Self {
         BoardConfig {
+            title: None,
             organization: "<organization>".to_string(),
             project: "<project>".to_string(),
             team: "<team>".to_string(),
+            me: None,
+            me_aliases: None,
+            max_items: None,
+            backlog_level: None,
         }
     }
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
 pub struct IterationConfig {
+    /// Shown instead of the generated `<team> Iteration: <path>` title.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
     pub organization: String,
     pub project: String,
     pub team: String,
     pub iteration: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub me: Option<String>,
+    /// Replaces `common.me_aliases` for this board.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub me_aliases: Option<Vec<String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_items: Option<usize>,
 }
 
 impl Default for IterationConfig {
     fn default() -> Self {
         IterationConfig {
+            title: None,
             organization: "<organization>".to_string(),
             project: "<project>".to_string(),
             team: "<team>".to_string(),
             iteration: "<iteration path>".to_string(),
+            me: None,
+            me_aliases: None,
+            max_items: None,
+        }
+    }
+}
+
+/// How a field value is rendered in the detail view.
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum FieldFormat {
+    Identity,
+    Date,
+    Html,
+    Url,
+    Raw,
+    /// Multiline field stored as markdown; saved as-is with the markdown format flag.
+    Markdown,
+}
+
+/// What pressing Enter on a list item does.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum OnEnter {
+    #[default]
+    Detail,
+    Edit,
+    SplitFocus,
+}
+
+/// How long lines of text fields are shown in the detail view.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum DetailWrap {
+    #[default]
+    Wrap,
+    /// Lines are cut at the edge of the field.
+    Truncate,
+    /// Lines are cut at the edge and Left / Right scroll them.
+    Scroll,
+}
+
+/// How assignees are shown.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+pub enum AssigneeDisplay {
+    #[default]
+    #[serde(rename = "name")]
+    Name,
+    #[serde(rename = "email")]
+    Email,
+    #[serde(rename = "name (email)")]
+    NameAndEmail,
+}
+
+/// Work item property the list can be ordered by.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum SortKey {
+    /// Keep the order returned by ADO (backlog / iteration order).
+    #[default]
+    None,
+    Id,
+    State,
+    Type,
+    Title,
+    AssignedTo,
+    Changed,
+    /// `Microsoft.VSTS.Common.Priority`, 1 first; items without one last.
+    Priority,
+}
+
+impl SortKey {
+    /// Order `cycle_sort` steps through.
+    const ALL: [SortKey; 8] = [
+        SortKey::None,
+        SortKey::Id,
+        SortKey::State,
+        SortKey::Type,
+        SortKey::Title,
+        SortKey::AssignedTo,
+        SortKey::Changed,
+        SortKey::Priority,
+    ];
+
+    pub fn next(self) -> SortKey {
+        let index = SortKey::ALL
+            .iter()
+            .position(|key| *key == self)
+            .unwrap_or(0);
+        SortKey::ALL[(index + 1) % SortKey::ALL.len()]
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            SortKey::None => "backlog order",
+            SortKey::Id => "id",
+            SortKey::State => "state",
+            SortKey::Type => "type",
+            SortKey::Title => "title",
+            SortKey::AssignedTo => "assignee",
+            SortKey::Changed => "changed",
+            SortKey::Priority => "priority",
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(default)]
+pub struct SortConfig {
+    pub primary: SortKey,
+    /// Breaks ties between items with an equal primary key.
+    pub secondary: SortKey,
+    /// Reverses the primary key.
+    pub descending: bool,
+}
+
+impl Default for SortConfig {
+    fn default() -> Self {
+        SortConfig {
+            primary: SortKey::None,
+            secondary: SortKey::Id,
+            descending: false,
         }
     }
 }
 
-#[derive(Default, Clone, Debug, Deserialize, Serialize)]
+pub const DEFAULT_BASE_URL: &str = "https://dev.azure.com";
+
+fn default_base_url() -> String {
+    DEFAULT_BASE_URL.to_string()
+}
+
+fn default_open_url_template() -> String {
+    "{project_url}/_workitems/edit/{id}".to_string()
+}
+
+fn default_done_states() -> Vec<String> {
+    vec![
+        "Done".to_string(),
+        "Closed".to_string(),
+        "Removed".to_string(),
+    ]
+}
+
+fn default_branch_name_template() -> String {
+    "feature/{id}-{title}".to_string()
+}
+
+fn default_list_title_format() -> String {
+    "{board}{assignee}{sprint}{work}{filters}".to_string()
+}
+
+fn default_remaining_work_field() -> String {
+    "Microsoft.VSTS.Scheduling.RemainingWork".to_string()
+}
+
+fn default_completed_work_field() -> String {
+    "Microsoft.VSTS.Scheduling.CompletedWork".to_string()
+}
+
+fn default_startup_banner() -> bool {
+    true
+}
+
+fn default_request_timeout_seconds() -> u64 {
+    DEFAULT_REQUEST_TIMEOUT_SECONDS
+}
+
+fn default_max_concurrent_requests() -> usize {
+    DEFAULT_MAX_CONCURRENT_REQUESTS
+}
+
+fn default_list_width_percent() -> u16 {
+    38
+}
+
+fn default_details_pane_width() -> u16 {
+    40
+}
+
+fn default_preserve_filters_on_refresh() -> bool {
+    true
+}
+
+fn default_unread_markers() -> bool {
+    true
+}
+
+fn default_repo_editor() -> String {
+    "code".to_string()
+}
+
+fn default_cache_max_age_days() -> u64 {
+    30
+}
+
+fn default_cache_max_size_mb() -> u64 {
+    100
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct CommonConfig {
     pub me: String,
+    #[serde(default)]
+    pub age_heatmap: bool,
+    #[serde(default)]
+    pub full_errors: bool,
+    #[serde(default = "default_base_url")]
+    pub base_url: String,
+    #[serde(default = "default_open_url_template")]
+    pub open_url_template: String,
+    #[serde(default = "default_cache_max_age_days")]
+    pub cache_max_age_days: u64,
+    #[serde(default = "default_cache_max_size_mb")]
+    pub cache_max_size_mb: u64,
+    #[serde(default)]
+    pub on_enter: OnEnter,
+    #[serde(default)]
+    pub hide_done: bool,
+    #[serde(default = "default_done_states")]
+    pub done_states: Vec<String>,
+    #[serde(default = "default_branch_name_template")]
+    pub branch_name_template: String,
+    #[serde(default)]
+    pub max_items: usize,
+    #[serde(default = "default_repo_editor")]
+    pub repo_editor: String,
+    #[serde(default)]
+    pub checkout_linked_branch: bool,
+    #[serde(default)]
+    pub confirm_quit: bool,
+    #[serde(default = "default_list_title_format")]
+    pub list_title_format: String,
+    #[serde(default = "default_remaining_work_field")]
+    pub remaining_work_field: String,
+    #[serde(default = "default_completed_work_field")]
+    pub completed_work_field: String,
+    #[serde(default)]
+    pub assignee_display: AssigneeDisplay,
+    #[serde(default = "default_startup_banner")]
+    pub startup_banner: bool,
+    #[serde(default = "default_list_width_percent")]
+    pub list_width_percent: u16,
+    #[serde(default = "default_unread_markers")]
+    pub unread_markers: bool,
+    #[serde(default)]
+    pub max_title_width: usize,
+    #[serde(default)]
+    pub wrap_titles: bool,
+    #[serde(default = "default_preserve_filters_on_refresh")]
+    pub preserve_filters_on_refresh: bool,
+    #[serde(default = "default_details_pane_width")]
+    pub details_pane_width: u16,
+    #[serde(default)]
+    pub detail_wrap: DetailWrap,
+    #[serde(default)]
+    pub summary_header: bool,
+    #[serde(default)]
+    pub collapsed_fields: Vec<String>,
+    #[serde(default)]
+    pub me_aliases: Vec<String>,
+    #[serde(default = "default_request_timeout_seconds")]
+    pub request_timeout_seconds: u64,
+    #[serde(default = "default_max_concurrent_requests")]
+    pub max_concurrent_requests: usize,
+    #[serde(default)]
+    pub partial_loads: bool,
+}
+
+impl Default for CommonConfig {
+    fn default() -> Self {
+        CommonConfig {
+            me: "".to_string(),
+            age_heatmap: false,
+            full_errors: false,
+            base_url: default_base_url(),
+            open_url_template: default_open_url_template(),
+            cache_max_age_days: default_cache_max_age_days(),
+            cache_max_size_mb: default_cache_max_size_mb(),
+            on_enter: OnEnter::default(),
+            hide_done: false,
+            done_states: default_done_states(),
+            branch_name_template: default_branch_name_template(),
+            max_items: 0,
+            repo_editor: default_repo_editor(),
+            checkout_linked_branch: false,
+            confirm_quit: false,
+            list_title_format: default_list_title_format(),
+            remaining_work_field: default_remaining_work_field(),
+            completed_work_field: default_completed_work_field(),
+            assignee_display: AssigneeDisplay::default(),
+            startup_banner: default_startup_banner(),
+            list_width_percent: default_list_width_percent(),
+            unread_markers: default_unread_markers(),
+            max_title_width: 0,
+            wrap_titles: false,
+            preserve_filters_on_refresh: default_preserve_filters_on_refresh(),
+            details_pane_width: default_details_pane_width(),
+            detail_wrap: DetailWrap::default(),
+            summary_header: false,
+            collapsed_fields: Vec::new(),
+            me_aliases: Vec::new(),
+            request_timeout_seconds: default_request_timeout_seconds(),
+            max_concurrent_requests: default_max_concurrent_requests(),
+            partial_loads: false,
+        }
+    }
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
@@ -53,19 +395,63 @@ pub struct KeysConfig {
     pub next: String,
     pub previous: String,
     pub hover: String,
+    pub peek_title: String,
+    pub pin_details: String,
+    pub toggle_summary: String,
     pub help: String,
     pub open: String,
     pub next_board: String,
     pub previous_board: String,
+    pub pick_board: String,
     pub search: String,
+    pub find: String,
+    pub find_next: String,
+    pub find_previous: String,
+    pub global_search: String,
     pub assigned_to_me_filter: String,
     pub work_item_type_filter: String,
+    pub cycle_type_filter: String,
+    pub current_sprint_filter: String,
     pub jump_to_top: String,
     pub jump_to_end: String,
     pub refresh: String,
+    pub hard_refresh: String,
     pub full_refresh: String,
     pub edit_config: String,
+    pub reload_config: String,
     pub edit_item: String,
+    pub refresh_item: String,
+    pub error_details: String,
+    pub open_board: String,
+    pub copy_branch_name: String,
+    pub open_repo: String,
+    pub open_pull_request: String,
+    pub retry_field_metadata: String,
+    pub board_column_filter: String,
+    pub tag_filter: String,
+    pub reassign: String,
+    pub assign_to_me: String,
+    pub follow: String,
+    pub clone_item: String,
+    pub rank_up: String,
+    pub rank_down: String,
+    pub move_mode: String,
+    pub cycle_sort: String,
+    pub reverse_sort: String,
+    pub narrow_list: String,
+    pub widen_list: String,
+    pub collapse_description: String,
+    pub collapse_acceptance_criteria: String,
+    pub raw_description: String,
+    pub inspect_fields: String,
+    pub error_log: String,
+    pub toggle_pin: String,
+    pub hide_done: String,
+    pub group_by_assignee: String,
+    pub collapse_all_groups: String,
+    pub expand_all_groups: String,
+    pub bypass_filters: String,
+    pub command_palette: String,
 }
 
 impl Default for KeysConfig {
@@ -75,23 +461,79 @@ impl Default for KeysConfig {
             next: "j".to_string(),
             previous: "k".to_string(),
             hover: "K".to_string(),
+            peek_title: "p".to_string(),
+            pin_details: "w".to_string(),
+            toggle_summary: "#".to_string(),
             help: "?".to_string(),
             open: "o".to_string(),
             next_board: ">".to_string(),
             previous_board: "<".to_string(),
+            pick_board: "B".to_string(),
             search: "/".to_string(),
+            find: "ctrl+f".to_string(),
+            find_next: "n".to_string(),
+            find_previous: "N".to_string(),
+            global_search: "S".to_string(),
             assigned_to_me_filter: "m".to_string(),
             work_item_type_filter: "t".to_string(),
+            cycle_type_filter: "ctrl+t".to_string(),
+            current_sprint_filter: "i".to_string(),
             jump_to_top: "gg".to_string(),
             jump_to_end: "G".to_string(),
             refresh: "r".to_string(),
+            hard_refresh: "ctrl+r".to_string(),
             full_refresh: "R".to_string(),
             edit_config: "c".to_string(),
+            reload_config: "L".to_string(),
             edit_item: "e".to_string(),
+            refresh_item: "u".to_string(),
+            error_details: "d".to_string(),
+            open_board: "O".to_string(),
+            copy_branch_name: "y".to_string(),
+            open_repo: "E".to_string(),
+            open_pull_request: "P".to_string(),
+            retry_field_metadata: "M".to_string(),
+            board_column_filter: "b".to_string(),
+            tag_filter: "T".to_string(),
+            reassign: "a".to_string(),
+            assign_to_me: "ctrl+a".to_string(),
+            follow: "f".to_string(),
+            clone_item: "C".to_string(),
+            rank_up: "ctrl+k".to_string(),
+            rank_down: "ctrl+j".to_string(),
+            move_mode: "v".to_string(),
+            cycle_sort: "s".to_string(),
+            reverse_sort: "~".to_string(),
+            narrow_list: "[".to_string(),
+            widen_list: "]".to_string(),
+            collapse_description: "z".to_string(),
+            collapse_acceptance_criteria: "Z".to_string(),
+            raw_description: "x".to_string(),
+            inspect_fields: "I".to_string(),
+            error_log: "X".to_string(),
+            toggle_pin: "*".to_string(),
+            hide_done: "H".to_string(),
+            group_by_assignee: "A".to_string(),
+            collapse_all_groups: "-".to_string(),
+            expand_all_groups: "+".to_string(),
+            bypass_filters: "F".to_string(),
+            command_palette: ":".to_string(),
         }
     }
 }
 
+/// Shell command run on the selected item from its key, see `[[commands]]`.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct ExternalCommandConfig {
+    pub name: String,
+    pub key: String,
+    pub command: String,
+    /// Runs in the terminal instead of in the background, for commands that
+    /// need input or a pager.
+    #[serde(default)]
+    pub interactive: bool,
+}
+
 #[derive(Debug, Deserialize, Serialize)]
 pub struct AppConfig {
     #[serde(default)]
@@ -102,21 +544,67 @@ pub struct AppConfig {
     pub iterations: Vec<IterationConfig>,
     #[serde(default)]
     pub keys: KeysConfig,
+    #[serde(default)]
+    pub field_formats: BTreeMap<String, FieldFormat>,
+    /// Browser URL per work item type, for types not opened with
+    /// `_workitems/edit`. `{project_url}` and `{id}` are filled in.
+    #[serde(default)]
+    pub item_urls: BTreeMap<String, String>,
+    #[serde(default)]
+    pub sort: SortConfig,
+    #[serde(default)]
+    pub repo_paths: BTreeMap<String, PathBuf>,
+    #[serde(default)]
+    pub commands: Vec<ExternalCommandConfig>,
 }
 
 impl Default for AppConfig {
     fn default() -> Self {
         AppConfig {
-            common: CommonConfig { me: "".to_string() },
+            common: CommonConfig::default(),
             boards: vec![BoardConfig::default()],
             iterations: Vec::new(),
             keys: KeysConfig::default(),
+            field_formats: BTreeMap::new(),
+            item_urls: BTreeMap::new(),
+            sort: SortConfig::default(),
+            repo_paths: BTreeMap::new(),
+            commands: Vec::new(),
         }
     }
 }
 
-pub fn open_config() -> Result<()> {
-    let file_path = confy::get_configuration_file_path(APPNAME, None)?;
+/// Path of the configuration file in use: the explicit one if given, confy's
+/// default location otherwise.
+pub fn config_file_path(config_path: Option<&Path>) -> Result<PathBuf> {
+    match config_path {
+        Some(path) => Ok(path.to_path_buf()),
+        None => Ok(confy::get_configuration_file_path(APPNAME, None)?),
+    }
+}
+
+/// Writes the list width back to the configuration file, keeping everything
+/// else as it was loaded.
+pub fn store_list_width(config_path: Option<&Path>, percent: u16) -> Result<()> {
+    let file_path = config_file_path(config_path)?;
+    let mut cfg: AppConfig = confy::load_path(&file_path)?;
+    cfg.common.list_width_percent = percent;
+    confy::store_path(&file_path, cfg)?;
+    Ok(())
+}
+
+/// Writes the collapsed detail fields back to the configuration file, keeping
+/// everything else as it was loaded.
+pub fn store_collapsed_fields(config_path: Option<&Path>, fields: Vec<String>) -> Result<()> {
+    let file_path = config_file_path(config_path)?;
+    let mut cfg: AppConfig = confy::load_path(&file_path)?;
+    cfg.common.collapsed_fields = fields;
+    confy::store_path(&file_path, cfg)?;
+    Ok(())
+}
+
+pub fn open_config(config_path: Option<&Path>) -> Result<()> {
+    let file_path = config_file_path(config_path)?;
     let editor = std::env::var("EDITOR").unwrap_or_else(|_| {
         if cfg!(target_os = "windows") {
             "notepad".to_string()
@@ -138,14 +626,132 @@ pub fn open_config() -> Result<()> {
     Ok(())
 }
 
-pub fn load_config_or_prompt() -> (AppConfig, bool) {
-    let cfg: AppConfig = match confy::load(APPNAME, None) {
-        Ok(conf) => conf,
-        Err(e) => {
-            eprintln!("Error loading configuration: {}", e);
-            AppConfig::default()
+lazy_static! {
+    static ref ENV_VAR_REGEX: Regex = Regex::new(r"\$\{([A-Za-z_][A-Za-z0-9_]*)\}").unwrap();
+}
+
+/// Replaces each `${NAME}` in `value` with the environment variable `NAME`.
+/// `field` names the setting in the error when a variable isn't set.
+fn expand_env_vars(value: &mut String, field: &str) -> Result<()> {
+    let mut missing = None;
+    let expanded = ENV_VAR_REGEX.replace_all(value, |caps: &Captures| {
+        std::env::var(&caps[1]).unwrap_or_else(|_| {
+            missing.get_or_insert_with(|| caps[1].to_string());
+            String::new()
+        })
+    });
+    if let Some(name) = missing {
+        return Err(anyhow!(
+            "Environment variable {name} used in `{field}` is not set"
+        ));
+    }
+    *value = expanded.into_owned();
+    Ok(())
+}
+
+impl AppConfig {
+    /// Rejects settings that would only fail once used.
+    fn validate(&self) -> Result<()> {
+        if !self.common.open_url_template.contains("{id}") {
+            return Err(anyhow!(
+                "`open_url_template` needs an {{id}} placeholder: {}",
+                self.common.open_url_template
+            ));
+        }
+        for (work_item_type, template) in &self.item_urls {
+            if !template.contains("{id}") {
+                return Err(anyhow!(
+                    "`item_urls` for {work_item_type} needs an {{id}} placeholder: {template}"
+                ));
+            }
         }
+        Ok(())
+    }
+
+    /// Expands `${NAME}` in the board, iteration and common settings, so a
+    /// shared configuration can take the organization or project from the
+    /// environment.
+    fn expand_env_vars(&mut self) -> Result<()> {
+        for (index, board) in self.boards.iter_mut().enumerate() {
+            let field = |name: &str| format!("boards[{index}].{name}");
+            if let Some(title) = &mut board.title {
+                expand_env_vars(title, &field("title"))?;
+            }
+            expand_env_vars(&mut board.organization, &field("organization"))?;
+            expand_env_vars(&mut board.project, &field("project"))?;
+            expand_env_vars(&mut board.team, &field("team"))?;
+            if let Some(me) = &mut board.me {
+                expand_env_vars(me, &field("me"))?;
+            }
+            for (alias_index, alias) in board.me_aliases.iter_mut().flatten().enumerate() {
+                expand_env_vars(alias, &field(&format!("me_aliases[{alias_index}]")))?;
+            }
+            if let Some(backlog_level) = &mut board.backlog_level {
+                expand_env_vars(backlog_level, &field("backlog_level"))?;
+            }
+        }
+        for (index, iteration) in self.iterations.iter_mut().enumerate() {
+            let field = |name: &str| format!("iterations[{index}].{name}");
+            if let Some(title) = &mut iteration.title {
+                expand_env_vars(title, &field("title"))?;
+            }
+            expand_env_vars(&mut iteration.organization, &field("organization"))?;
+            expand_env_vars(&mut iteration.project, &field("project"))?;
+            expand_env_vars(&mut iteration.team, &field("team"))?;
+            expand_env_vars(&mut iteration.iteration, &field("iteration"))?;
+            if let Some(me) = &mut iteration.me {
+                expand_env_vars(me, &field("me"))?;
+            }
+            for (alias_index, alias) in iteration.me_aliases.iter_mut().flatten().enumerate() {
+                expand_env_vars(alias, &field(&format!("me_aliases[{alias_index}]")))?;
+            }
+        }
+        let common = &mut self.common;
+        for (name, value) in [
+            ("me", &mut common.me),
+            ("base_url", &mut common.base_url),
+            ("open_url_template", &mut common.open_url_template),
+            ("branch_name_template", &mut common.branch_name_template),
+            ("repo_editor", &mut common.repo_editor),
+            ("list_title_format", &mut common.list_title_format),
+            ("remaining_work_field", &mut common.remaining_work_field),
+            ("completed_work_field", &mut common.completed_work_field),
+        ] {
+            expand_env_vars(value, &format!("common.{name}"))?;
+        }
+        for (index, state) in common.done_states.iter_mut().enumerate() {
+            expand_env_vars(state, &format!("common.done_states[{index}]"))?;
+        }
+        for (index, alias) in common.me_aliases.iter_mut().enumerate() {
+            expand_env_vars(alias, &format!("common.me_aliases[{index}]"))?;
+        }
+        Ok(())
+    }
+}
+
+/// Loads the configuration again while running. Unlike at startup, a file
+/// that doesn't parse is an error instead of falling back to the defaults.
+pub fn reload_config(config_path: Option<&Path>) -> Result<AppConfig> {
+    let mut cfg: AppConfig = match config_path {
+        Some(path) => confy::load_path(path)?,
+        None => confy::load(APPNAME, None)?,
     };
+    cfg.expand_env_vars()?;
+    cfg.validate()?;
+    Ok(cfg)
+}
+
+/// Loads the configuration at startup. Fails with a description of what to
+/// fix when the file doesn't parse, refers to an unset environment variable
+/// with `${NAME}`, or sets up no board or iteration yet.
+pub fn load_startup_config(config_path: Option<&Path>) -> std::result::Result<AppConfig, String> {
+    let loaded = match config_path {
+        Some(path) => confy::load_path(path),
+        None => confy::load(APPNAME, None),
+    };
+    let mut cfg: AppConfig = loaded.map_err(|e| format!("Error loading configuration: {}", e))?;
+    cfg.expand_env_vars().map_err(|e| format!("{:#}", e))?;
+    cfg.validate().map_err(|e| format!("{:#}", e))?;
 
     let default_board = BoardConfig::default();
     let default_iteration = IterationConfig::default();
@@ -162,12 +768,8 @@ pub fn load_config_or_prompt() -> (AppConfig, bool) {
         _ => true,
     };
 
-    let config_ok = boards_ok || iterations_ok;
-
-    if !config_ok {
-        let _ = open_config();
-        eprintln!("Reopen {}", APPNAME);
+    if !(boards_ok || iterations_ok) {
+        return Err("No board or iteration is configured yet".to_string());
     }
-
-    (cfg, config_ok)
+    Ok(cfg)
 }
@@ -0,0 +1,133 @@
+use std::fmt;
+
+use azure_core::error::ErrorKind;
+
+pub type Result<T> = std::result::Result<T, AdoError>;
+
+/// Whether a request never reached ADO or gave up waiting for it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NetworkErrorKind {
+    Timeout,
+    Connect,
+}
+
+/// Failure returned by the service layer, categorized so callers can react to
+/// authentication, connectivity and server problems differently.
+#[derive(Clone, Debug)]
+pub enum AdoError {
+    Auth(String),
+    Network(NetworkErrorKind, String),
+    RateLimited(String),
+    NotFound(String),
+    Server(u16, String),
+    Parse(String),
+    Other(String),
+}
+
+impl fmt::Display for AdoError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AdoError::Auth(message) => {
+                write!(f, "Authentication failed: {}", first_line(message))
+            }
+            AdoError::Network(NetworkErrorKind::Timeout, message) => {
+                write!(f, "Request timed out: {}", first_line(message))
+            }
+            AdoError::Network(NetworkErrorKind::Connect, message) => {
+                write!(f, "Could not connect: {}", first_line(message))
+            }
+            AdoError::RateLimited(message) => {
+                write!(f, "Rate limited: {}", first_line(message))
+            }
+            AdoError::NotFound(message) => write!(f, "Not found: {}", first_line(message)),
+            AdoError::Server(status, message) if message.is_empty() => {
+                write!(f, "HTTP {}", status)
+            }
+            AdoError::Server(status, message) => {
+                write!(f, "HTTP {}: {}", status, first_line(message))
+            }
+            AdoError::Parse(message) => {
+                write!(f, "Unexpected response: {}", first_line(message))
+            }
+            AdoError::Other(message) => write!(f, "{}", first_line(message)),
+        }
+    }
+}
+
+impl std::error::Error for AdoError {}
+
+impl From<azure_core::Error> for AdoError {
+    fn from(err: azure_core::Error) -> Self {
+        match err.kind() {
+            ErrorKind::HttpResponse {
+                status,
+                error_code,
+                raw_response,
+            } => {
+                // ADO puts a readable explanation in the `message` of the JSON body.
+                let message = raw_response
+                    .as_ref()
+                    .and_then(|response| {
+                        serde_json::from_slice::<serde_json::Value>(response.body()).ok()
+                    })
+                    .and_then(|body| {
+                        body.get("message")
+                            .and_then(|message| message.as_str())
+                            .map(|message| message.to_string())
+                    })
+                    .or_else(|| error_code.clone())
+                    .unwrap_or_default();
+                match u16::from(*status) {
+                    401 | 403 => AdoError::Auth(message),
+                    404 => AdoError::NotFound(message),
+                    429 => AdoError::RateLimited(message),
+                    status => AdoError::Server(status, message),
+                }
+            }
+            ErrorKind::Credential => AdoError::Auth(err.to_string()),
+            ErrorKind::Io => {
+                let message = full_message(&err);
+                let kind = if message.to_lowercase().contains("timed out")
+                    || message.to_lowercase().contains("timeout")
+                {
+                    NetworkErrorKind::Timeout
+                } else {
+                    NetworkErrorKind::Connect
+                };
+                AdoError::Network(kind, message)
+            }
+            ErrorKind::DataConversion => AdoError::Parse(full_message(&err)),
+            _ => AdoError::Other(full_message(&err)),
+        }
+    }
+}
+
+impl From<serde_json::Error> for AdoError {
+    fn from(err: serde_json::Error) -> Self {
+        AdoError::Parse(err.to_string())
+    }
+}
+
+/// One-line, human readable cause of a failed request.
+pub fn summarize_error(err: &anyhow::Error) -> String {
+    err.chain()
+        .find_map(|cause| cause.downcast_ref::<AdoError>())
+        .map(|ado_error| ado_error.to_string())
+        .unwrap_or_else(|| first_line(&err.to_string()))
+}
+
+/// The error followed by all of its causes, separated by `: `.
+fn full_message(err: &dyn std::error::Error) -> String {
+    let mut message = err.to_string();
+    let mut source = err.source();
+    while let Some(cause) = source {
+        message.push_str(": ");
+        message.push_str(&cause.to_string());
+        source = cause.source();
+    }
+    message
+}
+
+fn first_line(text: &str) -> String {
+    text.lines().next().unwrap_or_default().trim().to_string()
+}
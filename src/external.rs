@@ -0,0 +1,49 @@
+use std::process::Command;
+
+use lazy_static::lazy_static;
+use regex::{Captures, Regex};
+
+use crate::models::WorkItem;
+
+lazy_static! {
+    static ref PLACEHOLDER_REGEX: Regex = Regex::new(r"\{(id|title|url|type)\}").unwrap();
+}
+
+/// Fills `{id}`, `{title}`, `{url}` and `{type}` in a configured command
+/// line. Values are quoted for the shell, so a title with spaces or quotes
+/// stays a single argument and can't run anything by itself.
+pub fn expand_command(template: &str, item: &WorkItem, url: &str) -> String {
+    PLACEHOLDER_REGEX
+        .replace_all(template, |caps: &Captures| match &caps[1] {
+            "id" => item.id.to_string(),
+            "title" => shell_quote(&item.title),
+            "url" => shell_quote(url),
+            _ => shell_quote(&item.work_item_type),
+        })
+        .to_string()
+}
+
+#[cfg(unix)]
+fn shell_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', r"'\''"))
+}
+
+/// cmd.exe still expands `%VAR%` between double quotes, so each `%` steps
+/// out of the quotes and is escaped with `^` there.
+#[cfg(not(unix))]
+fn shell_quote(value: &str) -> String {
+    format!("\"{}\"", value.replace('"', "\"\"").replace('%', "\"^%\""))
+}
+
+/// Runs `command_line` through the platform's shell.
+pub fn shell_command(command_line: &str) -> Command {
+    if cfg!(target_os = "windows") {
+        let mut command = Command::new("cmd");
+        command.arg("/C").arg(command_line);
+        command
+    } else {
+        let mut command = Command::new("sh");
+        command.arg("-c").arg(command_line);
+        command
+    }
+}
@@ -11,308 +11,361 @@ use ratatui::{Terminal, backend::CrosstermBackend};
 
 mod app;
 mod cache;
+mod cli;
+mod clipboard;
+mod commands;
 mod config;
+mod error;
+mod external;
 mod models;
+mod repo;
 mod services;
 mod ui;
 
-use crate::app::{App, LoadingState, RefreshPolicy, prefetch_layouts, run_app};
-use crate::cache::{
-    LayoutCacheKey, WorkItemsCacheKey, read_field_meta_cache, read_layout_cache,
-    read_work_items_cache, write_work_items_cache,
+use crate::app::{
+    App, ErrorReport, LoadingState, RefreshPolicy, SourceKind, fetch_source_items,
+    prefetch_layouts, run_app, run_config_setup,
 };
-use crate::config::load_config_or_prompt;
+use crate::cache::{LayoutCacheKey, prune_cache, read_field_meta_cache, read_layout_cache};
+use crate::cli::CliArgs;
+use crate::config::load_startup_config;
+use crate::error::summarize_error;
+use crate::models::clean_ado_text;
 use crate::services::{
-    build_field_metadata_cache, fetch_process_template_type, fetch_process_work_item_types,
-    fetch_project_id, get_backlog_ids, get_items, get_iteration_ids, resolve_iteration_id,
+    build_field_metadata_cache, fetch_authenticated_user, fetch_process_template_type,
+    fetch_process_work_item_types, fetch_project_id, set_max_concurrent_requests,
+    set_partial_loads, set_request_timeout,
 };
 use crate::ui::draw_status_screen;
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let cli_args = CliArgs::parse();
+    if let Some(path) = &cli_args.clean_html {
+        print_cleaned_html(path.as_deref())?;
+        return Ok(());
+    }
+    let startup_config = load_startup_config(cli_args.config_path.as_deref());
+
     enable_raw_mode()?;
     let mut stdout = io::stdout();
     execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend)?;
 
-    let (cfg, config_ok) = load_config_or_prompt();
-    let mut app = App::new(cfg);
-    let mut res = Ok(());
-
-    if config_ok {
-        while !matches!(app.loading_state, LoadingState::Error(_)) {
-            if matches!(app.loading_state, LoadingState::Loading) {
-                let source = app.current_source().clone();
-                let source_title = source.title.clone();
-                terminal
-                    .draw(|f| draw_status_screen(f, &format!("Loading {}...", source_title)))?;
-
-                let fetch_result: Result<Vec<_>, anyhow::Error> = async {
-                    let refresh_policy = app.refresh_policy.clone();
-                    let max_age = Duration::from_secs(3600);
-
-                    // Reset caches if explicitly refreshing
-                    if matches!(refresh_policy, RefreshPolicy::Full) {
-                        app.clear_layout_cache();
-                        app.field_meta_cache.clear();
+    let cfg = match startup_config {
+        Ok(cfg) => Some(cfg),
+        Err(problem) => run_config_setup(&mut terminal, cli_args.config_path.as_deref(), problem)?,
+    };
+    let Some(cfg) = cfg else {
+        restore_terminal(&mut terminal)?;
+        return Ok(());
+    };
+    set_request_timeout(cfg.common.request_timeout_seconds);
+    set_max_concurrent_requests(cfg.common.max_concurrent_requests);
+    set_partial_loads(cfg.common.partial_loads);
+
+    // Drop data of boards that are no longer used; a limit of 0 disables it.
+    let cache_max_age = (cfg.common.cache_max_age_days > 0)
+        .then(|| Duration::from_secs(cfg.common.cache_max_age_days * 24 * 60 * 60));
+    let cache_max_bytes =
+        (cfg.common.cache_max_size_mb > 0).then(|| cfg.common.cache_max_size_mb * 1024 * 1024);
+    let _ = prune_cache(cache_max_age, cache_max_bytes);
+    let mut app = App::new(cfg, cli_args.config_path.clone());
+    if cli_args.my_work {
+        app.enable_my_work();
+    }
+    app.debug = cli_args.debug;
+
+    let res = loop {
+        if matches!(app.loading_state, LoadingState::Loading) {
+            let source = app.current_source().clone();
+            let source_title = source.title.clone();
+            let loading_message = format!("Loading {}...", source_title);
+            let details = app.loading_details();
+            terminal.draw(|f| draw_status_screen(f, &loading_message, &details))?;
+            let needs_banner =
+                app.startup_banner && !app.signed_in_users.contains_key(&source.organization);
+            let needs_me =
+                source.me.is_empty() && !app.resolved_me.contains_key(&source.organization);
+            if needs_banner || needs_me {
+                let user = fetch_authenticated_user(&source.organization).await;
+                if needs_banner {
+                    let signed_in_as = match &user {
+                        Ok(user) => user.label(),
+                        Err(err) => format!("unknown ({})", err),
+                    };
+                    app.signed_in_users
+                        .insert(source.organization.clone(), signed_in_as);
+                    let details = app.loading_details();
+                    terminal.draw(|f| draw_status_screen(f, &loading_message, &details))?;
+                }
+                // Without a configured `me`, the signed in user is who
+                // "assigned to me" means; a failed lookup leaves it empty.
+                let me = user.map(|user| user.display_name).unwrap_or_default();
+                app.resolved_me.insert(source.organization.clone(), me);
+            }
+            // My Work acts as each board's `me`, so boards in other
+            // organizations need their signed in user too.
+            if let SourceKind::MyWork(boards) = &source.kind {
+                for board in boards {
+                    if board.me.is_empty() && !app.resolved_me.contains_key(&board.organization) {
+                        let user = fetch_authenticated_user(&board.organization).await;
+                        let me = user.map(|user| user.display_name).unwrap_or_default();
+                        app.resolved_me.insert(board.organization.clone(), me);
                     }
+                }
+            }
 
-                    // 1) Work items: try cache first
-                    let items_result = match source.kind {
-                        crate::app::SourceKind::Backlog => {
-                            let cache_key = WorkItemsCacheKey::Backlog {
-                                organization: source.organization.clone(),
-                                project: source.project.clone(),
-                                team: source.team.clone(),
-                            };
-                            let cached = if matches!(refresh_policy, RefreshPolicy::Normal) {
-                                read_work_items_cache(&cache_key, max_age)
-                            } else {
-                                None
-                            };
-                            if let Some(items) = cached {
-                                Ok::<_, anyhow::Error>(items)
-                            } else {
-                                let ids = get_backlog_ids(
-                                    &source.organization,
-                                    &source.project,
-                                    &source.team,
-                                )
-                                .await?;
-                                 let items = get_items(&source.organization, &source.project, ids).await?;
-                                 let _ = write_work_items_cache(&cache_key, &items);
-
-                                Ok::<_, anyhow::Error>(items)
-                            }
-                        }
-                        crate::app::SourceKind::Iteration(iteration) => {
-                            let cache_key = WorkItemsCacheKey::Iteration {
-                                organization: iteration.organization.clone(),
-                                project: iteration.project.clone(),
-                                team: iteration.team.clone(),
-                                iteration: iteration.iteration.clone(),
-                            };
-                            let cached = if matches!(refresh_policy, RefreshPolicy::Normal) {
-                                read_work_items_cache(&cache_key, max_age)
-                            } else {
-                                None
-                            };
-                             if let Some(items) = cached {
-                                 Ok::<_, anyhow::Error>(items)
-                             } else {
-
-                                let iteration_id = resolve_iteration_id(
-                                    &iteration.organization,
-                                    &iteration.project,
-                                    &iteration.team,
-                                    &iteration.iteration,
-                                )
-                                .await?;
-                                let ids = get_iteration_ids(
-                                    &iteration.organization,
-                                    &iteration.project,
-                                    &iteration.team,
-                                    &iteration_id,
-                                )
-                                .await?;
-                                 let items =
-                                     get_items(&iteration.organization, &iteration.project, ids)
-                                         .await?;
-                                 let _ = write_work_items_cache(&cache_key, &items);
-
-                                Ok::<_, anyhow::Error>(items)
-                            }
-                        }
-                    }?;
-
-                    let used_types: BTreeSet<String> = items_result
-                        .iter()
-                        .map(|item| item.work_item_type.clone())
-                        .collect();
-
-                    // 2) Determine which types need layout/field metadata
-                    let metadata_display_names: Vec<String> =
-                        used_types.iter().cloned().collect();
-                    let mut missing_layout_displays: Vec<String> = Vec::new();
-
-                    for display in &metadata_display_names {
-                        let cache_key = (
-                            source.organization.clone(),
-                            source.project.clone(),
-                            display.clone(),
-                        );
-                        let layout_key = LayoutCacheKey {
-                            organization: source.organization.clone(),
-                            project: source.project.clone(),
-                            work_item_type: display.clone(),
-                        };
-                        let in_memory = app.layout_cache.get(&cache_key).is_some();
-                        let on_disk = if matches!(refresh_policy, RefreshPolicy::Full) {
-                            None
-                        } else {
-                            read_layout_cache(&layout_key)
-                        };
-                        if matches!(refresh_policy, RefreshPolicy::Full)
-                            || (!in_memory && on_disk.is_none())
-                        {
-                            missing_layout_displays.push(display.clone());
-                        } else if !in_memory {
-                            if let Some(disk) = on_disk {
-                                app.layout_cache.insert(cache_key, disk);
-                            }
-                        }
-                    }
+            let fetch_result: Result<(Vec<_>, Option<String>), anyhow::Error> = async {
+                let refresh_policy = app.refresh_policy.clone();
 
-                    // 3) Determine if we need to fetch process/work item types
-                    let mut process_id = app.process_template_type.clone();
-                    let need_process_fetch =
-                        matches!(refresh_policy, RefreshPolicy::Full)
-                            || !missing_layout_displays.is_empty();
-
-                    let mut layout_pairs: Vec<(String, String)> = Vec::new();
-
-                    if need_process_fetch {
-                        let project_id =
-                            fetch_project_id(&source.organization, &source.project).await?;
-                        let fetched_process_id =
-                            fetch_process_template_type(&source.organization, &project_id).await?;
-                        let fetched_work_item_types = fetch_process_work_item_types(
-                            &source.organization,
-                            &fetched_process_id,
-                        )
-                        .await?;
-
-                        process_id = Some(fetched_process_id.clone());
-                        let map: BTreeMap<String, String> =
-                            fetched_work_item_types.iter().cloned().collect();
-                        app.set_process_template_type(fetched_process_id);
-                        app.set_work_item_types(map);
-
-                        for (display, reference) in fetched_work_item_types {
-                            if used_types.contains(&display)
-                                && (matches!(refresh_policy, RefreshPolicy::Full)
-                                    || missing_layout_displays.contains(&display))
-                            {
-                                layout_pairs.push((display.clone(), reference.clone()));
-                            }
-                        }
-                    }
+                // Reset caches if explicitly refreshing
+                if matches!(refresh_policy, RefreshPolicy::Full) {
+                    app.clear_layout_cache();
+                    app.field_meta_cache.clear();
+                }
 
-                    // If we already have work item types, fill layout_pairs without extra API calls
-                    if layout_pairs.is_empty() && !missing_layout_displays.is_empty() {
-                        for display in &missing_layout_displays {
-                            if let Some(reference) = app.work_item_types.get(display) {
-                                layout_pairs.push((display.clone(), reference.clone()));
-                            }
-                        }
-                    }
+                // 1) Work items: try cache first
+                let (items_result, iteration, warning) =
+                    fetch_source_items(&source, &refresh_policy).await?;
+                app.current_iteration = iteration;
+
+                // Items merged from other boards use their own project's
+                // process, layouts and fields.
+                let mut types_by_project: BTreeMap<(String, String), BTreeSet<String>> =
+                    BTreeMap::new();
+                for item in &items_result {
+                    let (organization, project) = app.item_project(item);
+                    types_by_project
+                        .entry((organization.to_string(), project.to_string()))
+                        .or_default()
+                        .insert(item.work_item_type.clone());
+                }
+                for ((organization, project), used_types) in types_by_project {
+                    load_type_metadata(
+                        &mut app,
+                        &organization,
+                        &project,
+                        used_types,
+                        &refresh_policy,
+                    )
+                    .await?;
+                }
 
-                    // 4) Kick off layout and field metadata fetches
-
-                    let organization = source.organization.clone();
-                    let project = source.project.clone();
-                    let fields_organization = organization.clone();
-                    let fields_project = project.clone();
-                    let layout_refresh_policy = refresh_policy.clone();
-                    let fields_refresh_policy = refresh_policy.clone();
-                    let missing_field_meta = metadata_display_names
-                        .iter()
-                        .filter(|display_name| {
-                            let cache_key = crate::cache::FieldMetaCacheKey {
-                                organization: fields_organization.clone(),
-                                project: fields_project.clone(),
-                                work_item_type: (*display_name).clone(),
-                            };
-                            matches!(fields_refresh_policy, RefreshPolicy::Full)
-                                || read_field_meta_cache(&cache_key).is_none()
-                        })
-                        .count();
-
-                    let layout_handle = if layout_pairs.is_empty() {
-                        tokio::spawn(async move { HashMap::new() })
-                    } else {
-                        let process_id_value = process_id.clone().unwrap_or_default();
-                        tokio::spawn(async move {
-                            prefetch_layouts(
-                                &organization,
-                                &project,
-                                &process_id_value,
-                                layout_pairs,
-                                layout_refresh_policy,
-                            )
-                            .await
-                        })
-                    };
-                    let fields_handle = tokio::spawn(async move {
-                        // If everything is cached and refresh is normal, skip fetch
-                        if missing_field_meta == 0
-                            && matches!(fields_refresh_policy, RefreshPolicy::Normal)
-                        {
-                            let mut cache = std::collections::HashMap::new();
-                            for display_name in metadata_display_names {
-                                let cache_key = crate::cache::FieldMetaCacheKey {
-                                    organization: fields_organization.clone(),
-                                    project: fields_project.clone(),
-                                    work_item_type: display_name.clone(),
-                                };
-                                if let Some(fields) = read_field_meta_cache(&cache_key) {
-                                    cache.insert(display_name.clone(), fields);
-                                }
-                            }
-                            return cache;
-                        }
-
-                        build_field_metadata_cache(
-                            &fields_organization,
-                            &fields_project,
-                            metadata_display_names,
-                            fields_refresh_policy,
-                        )
-                        .await
-                    });
+                if matches!(app.refresh_policy, RefreshPolicy::Full) {
+                    app.refresh_policy = RefreshPolicy::Normal;
+                }
 
-                    if let Ok(prefetched) = layout_handle.await {
-                        if !prefetched.is_empty() {
-                            app.layout_cache.extend(prefetched);
-                        }
-                    }
-                    if let Ok(meta) = fields_handle.await {
-                        app.field_meta_cache = meta;
+                Ok((items_result, warning))
+            }
+            .await;
+
+            match fetch_result {
+                Ok((items, warning)) => {
+                    app.load_data(items);
+                    if let Some(warning) = warning {
+                        app.log_error(format!("{}: {}", source_title, warning));
+                        app.set_status_message(warning);
                     }
+                }
+                Err(e) => {
+                    app.log_error(format!("Failed to load {}: {e:#}", source_title));
+                    app.loading_state = LoadingState::Error(ErrorReport {
+                        summary: summarize_error(&e),
+                        details: format!("Failed to fetch data: {e:?}"),
+                    });
+                }
+            }
+            continue;
+        }
 
-                    if matches!(app.refresh_policy, RefreshPolicy::Full) {
-                        app.refresh_policy = RefreshPolicy::Normal;
-                    }
+        let res = run_app(&mut terminal, &mut app).await;
+        if res.is_err() || !matches!(app.loading_state, LoadingState::Loading) {
+            break res;
+        }
+    };
 
-                    Ok(items_result)
+    restore_terminal(&mut terminal)?;
 
-                }
-                .await;
-
-                match fetch_result {
-                    Ok(items) => app.load_data(items),
-                    Err(e) => {
-                        let error_msg = format!("Failed to fetch data: {e:?}");
-                        eprintln!("\n--- FATAL FETCH ERROR ---\n{}", error_msg);
-                        app.loading_state = LoadingState::Error(error_msg);
-                    }
-                }
-                continue;
+    if let Err(err) = res {
+        println!("{:?}", err);
+    }
+
+    Ok(())
+}
+
+/// Loads the layouts and field metadata of the work item types used in one
+/// project, from the caches where possible.
+async fn load_type_metadata(
+    app: &mut App,
+    organization: &str,
+    project: &str,
+    used_types: BTreeSet<String>,
+    refresh_policy: &RefreshPolicy,
+) -> anyhow::Result<()> {
+    // 2) Determine which types need layout/field metadata
+    let metadata_display_names: Vec<String> = used_types.iter().cloned().collect();
+    let mut missing_layout_displays: Vec<String> = Vec::new();
+
+    for display in &metadata_display_names {
+        let cache_key = (
+            organization.to_string(),
+            project.to_string(),
+            display.clone(),
+        );
+        let layout_key = LayoutCacheKey {
+            organization: organization.to_string(),
+            project: project.to_string(),
+            work_item_type: display.clone(),
+        };
+        let in_memory = app.layout_cache.contains_key(&cache_key);
+        let on_disk = if matches!(refresh_policy, RefreshPolicy::Full) {
+            None
+        } else {
+            read_layout_cache(&layout_key)
+        };
+        if matches!(refresh_policy, RefreshPolicy::Full) || (!in_memory && on_disk.is_none()) {
+            missing_layout_displays.push(display.clone());
+        } else if !in_memory && let Some(disk) = on_disk {
+            app.layout_cache.insert(cache_key, disk);
+        }
+    }
+
+    // 3) Determine if we need to fetch process/work item types
+    let project_key = (organization.to_string(), project.to_string());
+    let mut process_id = app.process_template_types.get(&project_key).cloned();
+    let need_process_fetch =
+        matches!(refresh_policy, RefreshPolicy::Full) || !missing_layout_displays.is_empty();
+
+    let mut layout_pairs: Vec<(String, String)> = Vec::new();
+
+    if need_process_fetch {
+        let project_id = fetch_project_id(organization, project).await?;
+        let fetched_process_id = fetch_process_template_type(organization, &project_id).await?;
+        let fetched_work_item_types =
+            fetch_process_work_item_types(organization, &fetched_process_id).await?;
+
+        process_id = Some(fetched_process_id.clone());
+        let map: BTreeMap<String, String> = fetched_work_item_types.iter().cloned().collect();
+        app.set_process_metadata(organization, project, fetched_process_id, map);
+
+        for (display, reference) in fetched_work_item_types {
+            if used_types.contains(&display)
+                && (matches!(refresh_policy, RefreshPolicy::Full)
+                    || missing_layout_displays.contains(&display))
+            {
+                layout_pairs.push((display.clone(), reference.clone()));
             }
+        }
+    }
 
-            res = run_app(&mut terminal, &mut app).await;
-            if res.is_err() {
-                break;
+    // If we already have work item types, fill layout_pairs without extra API calls
+    if layout_pairs.is_empty() && !missing_layout_displays.is_empty() {
+        for display in &missing_layout_displays {
+            if let Some(reference) = app
+                .work_item_types
+                .get(&project_key)
+                .and_then(|types| types.get(display))
+            {
+                layout_pairs.push((display.clone(), reference.clone()));
             }
+        }
+    }
 
-            if matches!(app.loading_state, LoadingState::Loading) {
-                continue;
+    // 4) Kick off layout and field metadata fetches
+
+    let organization = organization.to_string();
+    let project = project.to_string();
+    let fields_organization = organization.clone();
+    let fields_project = project.clone();
+    let layout_refresh_policy = refresh_policy.clone();
+    let fields_refresh_policy = refresh_policy.clone();
+    let missing_field_meta = metadata_display_names
+        .iter()
+        .filter(|display_name| {
+            let cache_key = crate::cache::FieldMetaCacheKey {
+                organization: fields_organization.clone(),
+                project: fields_project.clone(),
+                work_item_type: (*display_name).clone(),
+            };
+            matches!(fields_refresh_policy, RefreshPolicy::Full)
+                || read_field_meta_cache(&cache_key).is_none()
+        })
+        .count();
+
+    let layout_handle = if layout_pairs.is_empty() {
+        tokio::spawn(async move { HashMap::new() })
+    } else {
+        let process_id_value = process_id.clone().unwrap_or_default();
+        tokio::spawn(async move {
+            prefetch_layouts(
+                &organization,
+                &project,
+                &process_id_value,
+                layout_pairs,
+                layout_refresh_policy,
+            )
+            .await
+        })
+    };
+    let fields_handle = tokio::spawn(async move {
+        // If everything is cached and refresh is normal, skip fetch
+        if missing_field_meta == 0 && matches!(fields_refresh_policy, RefreshPolicy::Normal) {
+            let mut cache = std::collections::HashMap::new();
+            for display_name in metadata_display_names {
+                let cache_key = crate::cache::FieldMetaCacheKey {
+                    organization: fields_organization.clone(),
+                    project: fields_project.clone(),
+                    work_item_type: display_name.clone(),
+                };
+                if let Some(fields) = read_field_meta_cache(&cache_key) {
+                    cache.insert(display_name.clone(), fields);
+                }
             }
-            break;
+            return cache;
         }
+
+        build_field_metadata_cache(
+            &fields_organization,
+            &fields_project,
+            metadata_display_names,
+            fields_refresh_policy,
+        )
+        .await
+    });
+
+    if let Ok(prefetched) = layout_handle.await
+        && !prefetched.is_empty()
+    {
+        app.layout_cache.extend(prefetched);
+    }
+    if let Ok(meta) = fields_handle.await {
+        app.field_meta_cache
+            .extend(meta.into_iter().map(|(display_name, fields)| {
+                (
+                    (project_key.0.clone(), project_key.1.clone(), display_name),
+                    fields,
+                )
+            }));
     }
+    Ok(())
+}
+
+/// Prints what the description cleaner makes of raw ADO HTML, read from the
+/// file or from stdin.
+fn print_cleaned_html(path: Option<&std::path::Path>) -> io::Result<()> {
+    let html = match path {
+        Some(path) => std::fs::read_to_string(path)?,
+        None => io::read_to_string(io::stdin())?,
+    };
+    println!("{}", clean_ado_text(&html));
+    Ok(())
+}
 
+fn restore_terminal(
+    terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
+) -> Result<(), Box<dyn std::error::Error>> {
     disable_raw_mode()?;
     execute!(
         terminal.backend_mut(),
@@ -320,10 +373,5 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         DisableMouseCapture
     )?;
     terminal.show_cursor()?;
-
-    if let Err(err) = res {
-        println!("{:?}", err);
-    }
-
     Ok(())
 }
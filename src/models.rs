@@ -1,20 +1,337 @@
 use std::collections::BTreeMap;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 use html_escape::decode_html_entities;
 use lazy_static::lazy_static;
 use regex::Regex;
 use serde::{Deserialize, Serialize};
 
+use std::cmp::Ordering;
+
+use crate::config::{AssigneeDisplay, FieldFormat, SortConfig, SortKey};
+
+const PRIORITY_FIELD: &str = "Microsoft.VSTS.Common.Priority";
+
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct WorkItem {
     pub id: u32,
     pub title: String,
     pub assigned_to: String,
+    /// Unique name (usually the email) of the assignee; empty when unassigned.
+    #[serde(default)]
+    pub assigned_to_email: String,
     pub state: String,
     pub work_item_type: String,
     pub description: String,
     pub acceptance_criteria: String,
+    #[serde(default)]
+    pub changed_date: String,
+    #[serde(default)]
+    pub board_column: String,
+    #[serde(default)]
+    pub board_lane: String,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub linked_branches: Vec<LinkedBranch>,
+    #[serde(default)]
+    pub linked_artifacts: Vec<LinkedArtifact>,
+    #[serde(default)]
+    pub parent_id: Option<u32>,
     pub fields: BTreeMap<String, String>,
+    #[serde(default)]
+    pub raw_fields: BTreeMap<String, serde_json::Value>,
+    /// Board the item was fetched from when it is shown in a merged view.
+    #[serde(default)]
+    pub board: Option<BoardTag>,
+}
+
+/// Identifies the board an item came from in the merged "my work" view.
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+pub struct BoardTag {
+    pub title: String,
+    pub organization: String,
+    pub project: String,
+}
+
+/// Git branch linked to a work item from its Development section.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+pub struct LinkedBranch {
+    pub repository_id: String,
+    pub branch: String,
+}
+
+impl LinkedBranch {
+    /// Parses an artifact link such as
+    /// `vstfs:///Git/Ref/<project id>%2F<repository id>%2FGB<branch>`.
+    pub fn from_artifact_url(url: &str) -> Option<LinkedBranch> {
+        let reference = percent_decode(url.strip_prefix("vstfs:///Git/Ref/")?);
+        let mut parts = reference.splitn(3, '/');
+        let _project_id = parts.next()?;
+        let repository_id = parts.next()?.to_string();
+        let branch = parts.next()?.strip_prefix("GB")?.to_string();
+        Some(LinkedBranch {
+            repository_id,
+            branch,
+        })
+    }
+}
+
+/// Pull request or commit linked to a work item from its Development section.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+pub struct LinkedArtifact {
+    pub repository_id: String,
+    pub kind: ArtifactKind,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+pub enum ArtifactKind {
+    PullRequest(u32),
+    Commit(String),
+}
+
+impl LinkedArtifact {
+    /// Parses an artifact link such as
+    /// `vstfs:///Git/PullRequestId/<project id>%2F<repository id>%2F<id>` or
+    /// `vstfs:///Git/Commit/<project id>%2F<repository id>%2F<commit id>`.
+    pub fn from_artifact_url(url: &str) -> Option<LinkedArtifact> {
+        let (kind, reference) = url.strip_prefix("vstfs:///Git/")?.split_once('/')?;
+        let reference = percent_decode(reference);
+        let mut parts = reference.splitn(3, '/');
+        let _project_id = parts.next()?;
+        let repository_id = parts.next()?.to_string();
+        let id = parts.next()?;
+        let kind = match kind {
+            "PullRequestId" => ArtifactKind::PullRequest(id.parse().ok()?),
+            "Commit" => ArtifactKind::Commit(id.to_string()),
+            _ => return None,
+        };
+        Some(LinkedArtifact {
+            repository_id,
+            kind,
+        })
+    }
+
+    /// `PR !123` or `Commit 1a2b3c4`.
+    pub fn label(&self) -> String {
+        match &self.kind {
+            ArtifactKind::PullRequest(id) => format!("PR !{}", id),
+            ArtifactKind::Commit(commit_id) => {
+                format!("Commit {}", commit_id.get(..7).unwrap_or(commit_id))
+            }
+        }
+    }
+
+    /// Path of the artifact's web page below the project URL.
+    pub fn web_path(&self) -> String {
+        match &self.kind {
+            ArtifactKind::PullRequest(id) => {
+                format!("_git/{}/pullrequest/{}", self.repository_id, id)
+            }
+            ArtifactKind::Commit(commit_id) => {
+                format!("_git/{}/commit/{}", self.repository_id, commit_id)
+            }
+        }
+    }
+}
+
+/// Id of the work item a relation url such as
+/// `https://dev.azure.com/<org>/_apis/wit/workItems/<id>` points to.
+pub fn work_item_id_from_url(url: &str) -> Option<u32> {
+    url.rsplit('/').next()?.parse().ok()
+}
+
+fn percent_decode(input: &str) -> String {
+    let bytes = input.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%'
+            && let Some(byte) = input
+                .get(i + 1..i + 3)
+                .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+        {
+            decoded.push(byte);
+            i += 3;
+        } else {
+            decoded.push(bytes[i]);
+            i += 1;
+        }
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+fn non_empty_or<'a>(value: &'a str, fallback: &'a str) -> &'a str {
+    if value.trim().is_empty() {
+        fallback
+    } else {
+        value
+    }
+}
+
+impl WorkItem {
+    /// Value of a numeric field such as remaining work. ADO returns numbers,
+    /// but values stored as text are accepted as well.
+    pub fn numeric_field(&self, reference: &str) -> Option<f64> {
+        match self.raw_fields.get(reference)? {
+            serde_json::Value::Number(number) => number.as_f64(),
+            serde_json::Value::String(text) => text.trim().parse().ok(),
+            _ => None,
+        }
+    }
+
+    /// Assignee as configured by `assignee_display`, falling back to the
+    /// display name when the email isn't known.
+    pub fn assignee_label(&self, display: AssigneeDisplay) -> String {
+        match display {
+            _ if self.assigned_to_email.is_empty() => self.assigned_to.clone(),
+            AssigneeDisplay::Name => self.assigned_to.clone(),
+            AssigneeDisplay::Email => self.assigned_to_email.clone(),
+            AssigneeDisplay::NameAndEmail => {
+                format!("{} ({})", self.assigned_to, self.assigned_to_email)
+            }
+        }
+    }
+
+    /// Whether the assignee's display name or email contains `name`.
+    pub fn is_assigned_to(&self, name: &str) -> bool {
+        self.assigned_to.contains(name) || self.assigned_to_email.contains(name)
+    }
+
+    /// Title for display; items returned without one (e.g. when fields are
+    /// hidden by permissions) get a placeholder so the row stays identifiable.
+    pub fn display_title(&self) -> String {
+        if self.title.trim().is_empty() {
+            format!("(untitled #{})", self.id)
+        } else {
+            self.title.clone()
+        }
+    }
+
+    /// Work item type for display, `Unknown type` when ADO didn't return one.
+    pub fn display_type(&self) -> &str {
+        non_empty_or(&self.work_item_type, "Unknown type")
+    }
+
+    /// State for display, `Unknown state` when ADO didn't return one.
+    pub fn display_state(&self) -> &str {
+        non_empty_or(&self.state, "Unknown state")
+    }
+
+    /// Whether the id or the displayed title contains the query, ignoring case.
+    pub fn matches_query(&self, query: &str) -> bool {
+        let query = query.to_lowercase();
+        self.id.to_string().contains(&query) || self.display_title().to_lowercase().contains(&query)
+    }
+
+    /// Text of a field exactly as ADO returned it, before any HTML cleaning.
+    pub fn raw_text(&self, reference: &str) -> Option<&str> {
+        self.raw_fields.get(reference)?.as_str()
+    }
+
+    /// Value of a field as shown in the detail view. Fields with a configured
+    /// format are rendered from the raw ADO value, everything else falls back
+    /// to the cleaned text.
+    pub fn display_field(
+        &self,
+        reference: &str,
+        formats: &BTreeMap<String, FieldFormat>,
+    ) -> Option<String> {
+        match (formats.get(reference), self.raw_fields.get(reference)) {
+            (Some(format), Some(raw)) => format_field_value(*format, raw),
+            _ => self.fields.get(reference).cloned(),
+        }
+    }
+
+    /// Value of a field to start editing from. Formats only change how a
+    /// field is shown, so formatted fields are edited as ADO stores them and
+    /// saving can't write the formatted text back.
+    pub fn edit_value(
+        &self,
+        reference: &str,
+        formats: &BTreeMap<String, FieldFormat>,
+    ) -> Option<String> {
+        if !formats.contains_key(reference) {
+            return self.fields.get(reference).cloned();
+        }
+        match self.raw_fields.get(reference)? {
+            serde_json::Value::String(text) => Some(text.clone()),
+            serde_json::Value::Null => None,
+            serde_json::Value::Object(identity) => {
+                let field = |name: &str| identity.get(name).and_then(|value| value.as_str());
+                match (field("displayName"), field("uniqueName")) {
+                    (Some(name), Some(unique)) => Some(format!("{} <{}>", name, unique)),
+                    (name, unique) => name.or(unique).map(|text| text.to_string()),
+                }
+            }
+            other => Some(other.to_string()),
+        }
+    }
+
+    /// Ordering of two items by a single sort key. Recently changed items
+    /// come first when sorting by change date.
+    pub fn compare_by(&self, other: &WorkItem, key: SortKey) -> Ordering {
+        match key {
+            SortKey::None => Ordering::Equal,
+            SortKey::Id => self.id.cmp(&other.id),
+            SortKey::State => self.state.to_lowercase().cmp(&other.state.to_lowercase()),
+            SortKey::Type => self
+                .work_item_type
+                .to_lowercase()
+                .cmp(&other.work_item_type.to_lowercase()),
+            SortKey::Title => self.title.to_lowercase().cmp(&other.title.to_lowercase()),
+            SortKey::AssignedTo => self
+                .assigned_to
+                .to_lowercase()
+                .cmp(&other.assigned_to.to_lowercase()),
+            SortKey::Changed => parse_ado_timestamp(&other.changed_date)
+                .cmp(&parse_ado_timestamp(&self.changed_date)),
+            SortKey::Priority => {
+                let priority = |item: &WorkItem| item.numeric_field(PRIORITY_FIELD);
+                match (priority(self), priority(other)) {
+                    (Some(a), Some(b)) => a.total_cmp(&b),
+                    (Some(_), None) => Ordering::Less,
+                    (None, Some(_)) => Ordering::Greater,
+                    (None, None) => Ordering::Equal,
+                }
+            }
+        }
+    }
+
+    /// Ordering of two items in a sorted list: the primary key, possibly
+    /// reversed, then the secondary key and finally the id, so items with
+    /// equal keys always come out in the same order.
+    pub fn compare_sorted(&self, other: &WorkItem, sort: &SortConfig) -> Ordering {
+        let by_primary = self.compare_by(other, sort.primary);
+        let by_primary = if sort.descending {
+            by_primary.reverse()
+        } else {
+            by_primary
+        };
+        by_primary
+            .then_with(|| self.compare_by(other, sort.secondary))
+            .then_with(|| self.id.cmp(&other.id))
+    }
+
+    /// Branch name from a template with `{id}`, `{type}` and `{title}`
+    /// placeholders, with the title slugified and capped in length.
+    pub fn branch_name(&self, template: &str) -> String {
+        template
+            .replace("{id}", &self.id.to_string())
+            .replace(
+                "{type}",
+                &slugify(&self.work_item_type, BRANCH_SLUG_MAX_LEN),
+            )
+            .replace("{title}", &slugify(&self.title, BRANCH_SLUG_MAX_LEN))
+    }
+
+    /// Time since the item was last changed, if the changed date is known.
+    pub fn age(&self, now: SystemTime) -> Option<Duration> {
+        let changed = UNIX_EPOCH.checked_add(Duration::from_secs(parse_ado_timestamp(
+            &self.changed_date,
+        )?))?;
+        Some(now.duration_since(changed).unwrap_or_default())
+    }
 }
 
 #[derive(Clone, Copy, PartialEq, Eq)]
@@ -23,30 +340,279 @@ pub enum DetailField {
     Dynamic(usize),
 }
 
+/// Longest slug a single placeholder expands to in a branch name.
+const BRANCH_SLUG_MAX_LEN: usize = 50;
+
+/// Lowercases and replaces runs of non-alphanumeric characters with a single
+/// dash, without leading or trailing dashes.
+fn slugify(input: &str, max_len: usize) -> String {
+    let mut slug = String::new();
+    for c in input.chars() {
+        if c.is_ascii_alphanumeric() {
+            slug.push(c.to_ascii_lowercase());
+        } else if !slug.is_empty() && !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+    slug.truncate(max_len);
+    slug.trim_end_matches('-').to_string()
+}
+
 lazy_static! {
-    /// Regex to strip HTML tags; use replacement logic to preserve <img>
-    static ref HTML_TAG_REGEX: Regex = Regex::new(r"<[^>]*>").unwrap();
+    static ref HREF_REGEX: Regex = Regex::new(r#"href\s*=\s*["']([^"']*)["']"#).unwrap();
+}
+
+pub fn format_field_value(format: FieldFormat, value: &serde_json::Value) -> Option<String> {
+    match format {
+        FieldFormat::Identity => match value {
+            serde_json::Value::Object(identity) => identity
+                .get("displayName")
+                .or_else(|| identity.get("uniqueName"))
+                .and_then(|name| name.as_str())
+                .map(|name| name.to_string()),
+            serde_json::Value::String(name) => Some(name.clone()),
+            _ => None,
+        },
+        FieldFormat::Date => value.as_str().map(format_ado_date),
+        FieldFormat::Html => value.as_str().map(clean_ado_text),
+        FieldFormat::Url => value.as_str().map(|text| {
+            let decoded = decode_html_entities(text).to_string();
+            HREF_REGEX
+                .captures(&decoded)
+                .map(|caps| caps[1].to_string())
+                .unwrap_or_else(|| clean_ado_text(&decoded))
+        }),
+        FieldFormat::Raw | FieldFormat::Markdown => match value {
+            serde_json::Value::String(text) => Some(text.clone()),
+            serde_json::Value::Null => None,
+            other => Some(other.to_string()),
+        },
+    }
+}
+
+/// Seconds since the Unix epoch for an ADO timestamp such as `2024-01-05T10:22:33.12Z`.
+pub fn parse_ado_timestamp(input: &str) -> Option<u64> {
+    let (date, time) = input.trim().split_once('T')?;
+
+    let mut date_parts = date.splitn(3, '-');
+    let year: i64 = date_parts.next()?.parse().ok()?;
+    let month: i64 = date_parts.next()?.parse().ok()?;
+    let day: i64 = date_parts.next()?.parse().ok()?;
+    if !(1..=12).contains(&month) || !(1..=31).contains(&day) {
+        return None;
+    }
+
+    let time = time.trim_end_matches('Z');
+    let mut time_parts = time.splitn(3, ':');
+    let hour: i64 = time_parts.next()?.parse().ok()?;
+    let minute: i64 = time_parts.next()?.parse().ok()?;
+    let second: i64 = time_parts
+        .next()
+        .and_then(|s| s.split(['.', '+', '-']).next())
+        .and_then(|s| s.parse().ok())
+        .unwrap_or(0);
+
+    // Days since epoch for a proleptic Gregorian date (Howard Hinnant's algorithm).
+    let y = if month <= 2 { year - 1 } else { year };
+    let era = if y >= 0 { y } else { y - 399 } / 400;
+    let year_of_era = y - era * 400;
+    let shifted_month = if month > 2 { month - 3 } else { month + 9 };
+    let day_of_year = (153 * shifted_month + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    let days = era * 146097 + day_of_era - 719468;
+
+    let seconds = days * 86_400 + hour * 3_600 + minute * 60 + second;
+    u64::try_from(seconds).ok()
+}
+
+/// ADO dates come as ISO 8601 timestamps (`2024-01-05T10:22:33.12Z`); show them
+/// as `2024-01-05 10:22`.
+fn format_ado_date(input: &str) -> String {
+    match input.split_once('T') {
+        Some((date, time)) => match time.get(..5) {
+            Some(hours_minutes) => format!("{} {}", date, hours_minutes),
+            None => date.to_string(),
+        },
+        None => input.to_string(),
+    }
 }
 
+/// Strips the markup from ADO's HTML, keeping `<img>` tags so images are not
+/// silently lost. Tags are tokenized rather than matched, so quoted attribute
+/// values may contain `>`; comments are dropped and CDATA is kept as text.
+/// Entities are decoded in text only, so an escaped `&lt;b&gt;` stays text.
 pub fn clean_ado_text(input: &str) -> String {
-    let decoded_text = decode_html_entities(input).to_string();
-    let stripped_text = HTML_TAG_REGEX
-        .replace_all(&decoded_text, |caps: &regex::Captures| {
-            let tag = &caps[0];
-            let trimmed = tag
-                .trim_start_matches('<')
-                .trim_start_matches('/')
-                .split(|c| c == ' ' || c == '>' || c == '/')
-                .next()
-                .unwrap_or("");
-
-            if trimmed.eq_ignore_ascii_case("img") {
-                tag.to_string()
-            } else {
-                String::new()
+    let mut output = String::with_capacity(input.len());
+    let mut text_start = 0;
+    let mut position = 0;
+
+    while let Some(offset) = input[position..].find('<') {
+        let tag_start = position + offset;
+        let rest = &input[tag_start..];
+        let markup = if rest.starts_with("<!--") {
+            let end = rest.find("-->").map_or(rest.len(), |end| end + "-->".len());
+            Some((end, Markup::Drop))
+        } else if let Some(cdata) = rest.strip_prefix("<![CDATA[") {
+            let content_end = cdata.find("]]>").unwrap_or(cdata.len());
+            let end = "<![CDATA[".len() + (content_end + "]]>".len()).min(cdata.len());
+            Some((end, Markup::Cdata(&cdata[..content_end])))
+        } else if rest[1..].starts_with(|c: char| c.is_ascii_alphabetic() || "/!?".contains(c)) {
+            tag_end(rest).map(|end| {
+                let name = rest[1..]
+                    .trim_start_matches('/')
+                    .split(|c: char| c.is_whitespace() || c == '>' || c == '/')
+                    .next()
+                    .unwrap_or("");
+                if name.eq_ignore_ascii_case("img") {
+                    (end, Markup::Keep)
+                } else {
+                    (end, Markup::Drop)
+                }
+            })
+        } else {
+            None
+        };
+
+        let Some((length, markup)) = markup else {
+            // A lone `<`, such as in `a < b`, is text.
+            position = tag_start + 1;
+            continue;
+        };
+        output.push_str(&decode_html_entities(&input[text_start..tag_start]));
+        match markup {
+            Markup::Drop => {}
+            Markup::Keep => {
+                output.push_str(&decode_html_entities(&input[tag_start..tag_start + length]))
             }
-        })
-        .to_string();
+            Markup::Cdata(content) => output.push_str(content),
+        }
+        position = tag_start + length;
+        text_start = position;
+    }
+    output.push_str(&decode_html_entities(&input[text_start..]));
+
+    output.trim().to_string()
+}
+
+/// What `clean_ado_text` does with a piece of markup.
+enum Markup<'a> {
+    Drop,
+    Keep,
+    Cdata(&'a str),
+}
+
+/// Length of the tag at the start of `input` up to and including its closing
+/// `>`, skipping over quoted attribute values. `None` if it is never closed.
+fn tag_end(input: &str) -> Option<usize> {
+    let mut quote = None;
+    let mut previous = '<';
+    for (index, c) in input.char_indices().skip(1) {
+        match quote {
+            Some(open) if c == open => quote = None,
+            Some(_) => {}
+            // Quotes only start a value right after `=`, as in `title="a > b"`.
+            None if (c == '"' || c == '\'') && previous == '=' => quote = Some(c),
+            None if c == '>' => return Some(index + 1),
+            None => {}
+        }
+        if !c.is_whitespace() {
+            previous = c;
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn clean_ado_text_skips_gt_inside_attribute_values() {
+        assert_eq!(clean_ado_text(r#"<p title="a > b">text</p>"#), "text");
+        assert_eq!(clean_ado_text("<p title='a > b'>text</p>"), "text");
+    }
+
+    #[test]
+    fn clean_ado_text_keeps_cdata_content() {
+        assert_eq!(
+            clean_ado_text("<div><![CDATA[a < b > c]]></div>"),
+            "a < b > c"
+        );
+    }
+
+    #[test]
+    fn clean_ado_text_drops_comments() {
+        assert_eq!(
+            clean_ado_text("before<!-- <b>hidden</b> x > y -->after"),
+            "beforeafter"
+        );
+    }
+
+    #[test]
+    fn clean_ado_text_handles_nested_quotes() {
+        assert_eq!(clean_ado_text(r#"<span title="say 'a > b'">x</span>"#), "x");
+        assert_eq!(clean_ado_text(r#"<span title='say "a > b"'>y</span>"#), "y");
+    }
+
+    #[test]
+    fn clean_ado_text_keeps_lone_lt_as_text() {
+        assert_eq!(clean_ado_text("a < b &amp; c"), "a < b & c");
+    }
+
+    fn item(id: u32, state: &str, title: &str) -> WorkItem {
+        serde_json::from_value(serde_json::json!({
+            "id": id,
+            "title": title,
+            "assigned_to": "",
+            "state": state,
+            "work_item_type": "Task",
+            "description": "",
+            "acceptance_criteria": "",
+            "fields": {},
+        }))
+        .unwrap()
+    }
+
+    fn sorted_ids(items: &[WorkItem], sort: &SortConfig) -> Vec<u32> {
+        let mut sorted: Vec<&WorkItem> = items.iter().collect();
+        sorted.sort_by(|a, b| a.compare_sorted(b, sort));
+        sorted.into_iter().map(|item| item.id).collect()
+    }
+
+    #[test]
+    fn sort_ties_fall_back_to_id_order() {
+        let items = [
+            item(3, "Active", "c"),
+            item(1, "Active", "c"),
+            item(4, "New", "c"),
+            item(2, "Active", "c"),
+        ];
+        let sort = SortConfig {
+            primary: SortKey::State,
+            secondary: SortKey::None,
+            descending: false,
+        };
+        assert_eq!(sorted_ids(&items, &sort), [1, 2, 3, 4]);
+
+        let descending = SortConfig {
+            descending: true,
+            ..sort
+        };
+        assert_eq!(sorted_ids(&items, &descending), [4, 1, 2, 3]);
+    }
 
-    stripped_text.trim().to_string()
+    #[test]
+    fn sort_ties_use_the_secondary_key_before_the_id() {
+        let items = [
+            item(1, "Active", "b"),
+            item(2, "Active", "a"),
+            item(3, "Active", "a"),
+        ];
+        let sort = SortConfig {
+            primary: SortKey::State,
+            secondary: SortKey::Title,
+            descending: false,
+        };
+        assert_eq!(sorted_ids(&items, &sort), [2, 3, 1]);
+    }
 }
@@ -0,0 +1,46 @@
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+use anyhow::{Result, bail};
+
+/// Whether the repository at `path` has uncommitted changes.
+pub fn is_dirty(path: &Path) -> Result<bool> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(path)
+        .args(["status", "--porcelain"])
+        .output()?;
+    if !output.status.success() {
+        bail!("{} is not a git repository", path.display());
+    }
+    Ok(!output.stdout.is_empty())
+}
+
+pub fn checkout(path: &Path, branch: &str) -> Result<()> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(path)
+        .args(["checkout", branch])
+        .stdin(Stdio::null())
+        .output()?;
+    if !output.status.success() {
+        bail!(
+            "git checkout {} failed: {}",
+            branch,
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(())
+}
+
+/// Starts the editor on the repository without waiting for it, so the
+/// editor should be a GUI one such as `code`.
+pub fn open_in_editor(editor: &str, path: &Path) -> Result<()> {
+    Command::new(editor)
+        .arg(path)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()?;
+    Ok(())
+}
@@ -1,20 +1,42 @@
-use anyhow::{Context, Result, anyhow};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+use std::future::IntoFuture;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, PoisonError, RwLock};
+use std::time::Duration;
 
-use azure_devops_rust_api::Credential;
+use azure_core::credentials::TokenCredential;
+use azure_core::http::{Method, Request, Url, check_success, new_http_client};
 use azure_devops_rust_api::core::ClientBuilder as CoreClientBuilder;
+use azure_devops_rust_api::git::ClientBuilder as GitClientBuilder;
 use azure_devops_rust_api::processes::ClientBuilder as ProcessesClientBuilder;
 use azure_devops_rust_api::processes::models::FormLayout;
 use azure_devops_rust_api::wit::ClientBuilder as WitClientBuilder;
 use azure_devops_rust_api::wit::models::json_patch_operation::Op;
 use azure_devops_rust_api::wit::models::{JsonPatchOperation, WorkItem as ADOWorkItem};
 use azure_devops_rust_api::work::ClientBuilder as WorkClientBuilder;
+use azure_devops_rust_api::work::models::{ReorderOperation, TeamSettingsIteration};
+use azure_devops_rust_api::{ADO_SCOPE, Credential};
 use azure_identity::AzureCliCredential;
 
-use crate::config::BoardConfig;
-use crate::models::{WorkItem, clean_ado_text};
-use crate::{cache::FieldMetaCacheKey, app::RefreshPolicy, cache::read_field_meta_cache, cache::write_field_meta_cache};
+use base64::Engine;
+use base64::prelude::BASE64_STANDARD;
+
+use crate::config::{
+    BoardConfig, DEFAULT_BASE_URL, DEFAULT_MAX_CONCURRENT_REQUESTS,
+    DEFAULT_REQUEST_TIMEOUT_SECONDS, FieldFormat,
+};
+use crate::error::{AdoError, NetworkErrorKind, Result};
+use crate::models::{
+    LinkedArtifact, LinkedBranch, WorkItem, clean_ado_text, work_item_id_from_url,
+};
+use crate::{
+    app::RefreshPolicy, cache::FieldMetaCacheKey, cache::read_field_meta_cache,
+    cache::write_field_meta_cache,
+};
+use lazy_static::lazy_static;
 use serde::{Deserialize, Serialize};
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
 
 fn authenticate_with_cli_credential() -> Result<Credential> {
     let azure_cli_credential = AzureCliCredential::new(None)?;
@@ -31,19 +53,130 @@ fn get_credential() -> Result<Credential> {
     }
 }
 
+/// Seconds a request to ADO may take before it is given up; 0 waits as long
+/// as it takes.
+static REQUEST_TIMEOUT_SECONDS: AtomicU64 = AtomicU64::new(DEFAULT_REQUEST_TIMEOUT_SECONDS);
+
+/// Applies `request_timeout_seconds` to every following request.
+pub fn set_request_timeout(seconds: u64) {
+    REQUEST_TIMEOUT_SECONDS.store(seconds, Ordering::Relaxed);
+}
+
+/// Awaits a request, failing with a timeout error when it takes longer than
+/// the configured request timeout.
+async fn timed<T, E>(request: impl IntoFuture<Output = std::result::Result<T, E>>) -> Result<T>
+where
+    AdoError: From<E>,
+{
+    let seconds = REQUEST_TIMEOUT_SECONDS.load(Ordering::Relaxed);
+    if seconds == 0 {
+        return Ok(request.await?);
+    }
+    match tokio::time::timeout(Duration::from_secs(seconds), request).await {
+        Ok(result) => Ok(result?),
+        Err(_) => Err(AdoError::Network(
+            NetworkErrorKind::Timeout,
+            format!("no response after {}s", seconds),
+        )),
+    }
+}
+
+lazy_static! {
+    /// Shared by bulk operations so that together they keep at most
+    /// `max_concurrent_requests` requests in flight.
+    static ref REQUEST_PERMITS: RwLock<Arc<Semaphore>> =
+        RwLock::new(Arc::new(Semaphore::new(DEFAULT_MAX_CONCURRENT_REQUESTS)));
+}
+
+/// Applies `max_concurrent_requests` to bulk operations started from now on.
+pub fn set_max_concurrent_requests(limit: usize) {
+    let permits = Arc::new(Semaphore::new(limit.max(1)));
+    *REQUEST_PERMITS
+        .write()
+        .unwrap_or_else(PoisonError::into_inner) = permits;
+}
+
+/// Awaits `request` once the bulk operations have a request to spare.
+pub async fn limited<F: Future>(request: F) -> F::Output {
+    let permits = REQUEST_PERMITS
+        .read()
+        .unwrap_or_else(PoisonError::into_inner)
+        .clone();
+    // The semaphore is never closed, so acquiring can only wait.
+    let _permit = permits.acquire_owned().await;
+    request.await
+}
+
+/// Iteration path that resolves to the team's current iteration.
+pub const CURRENT_ITERATION_MACRO: &str = "@CurrentIteration";
+
+#[derive(Clone, Debug, Default)]
+pub struct ResolvedIteration {
+    pub id: String,
+    pub name: Option<String>,
+    /// Iteration path as used in `System.IterationPath`.
+    pub path: Option<String>,
+    /// Unix times of the first and the last day of the iteration, if set.
+    pub start_date: Option<u64>,
+    pub finish_date: Option<u64>,
+    /// Unix time after which a relative iteration may resolve differently.
+    pub valid_until: Option<u64>,
+}
+
+/// `relative` marks an iteration picked by a macro, which may resolve to a
+/// different iteration once this one has finished.
+fn resolved_from(iteration: TeamSettingsIteration, relative: bool) -> Option<ResolvedIteration> {
+    let unix = |date: azure_core::time::OffsetDateTime| date.unix_timestamp().max(0) as u64;
+    let start_date = iteration
+        .attributes
+        .as_ref()
+        .and_then(|attributes| attributes.start_date)
+        .map(unix);
+    let finish_date = iteration
+        .attributes
+        .as_ref()
+        .and_then(|attributes| attributes.finish_date)
+        .map(unix);
+    Some(ResolvedIteration {
+        id: iteration.id?,
+        name: iteration.name,
+        path: iteration.path,
+        start_date,
+        finish_date,
+        // The finish date is the start of the last day of the iteration.
+        valid_until: finish_date
+            .filter(|_| relative)
+            .map(|finish| finish + 24 * 60 * 60),
+    })
+}
+
 pub async fn resolve_iteration_id(
     organization: &str,
     project: &str,
     team: &str,
     iteration_path: &str,
-) -> Result<String> {
+) -> Result<ResolvedIteration> {
     let credential = get_credential()?;
     let work_client = WorkClientBuilder::new(credential).build();
+    let iterations_client = work_client.iterations_client();
+
+    if iteration_path.eq_ignore_ascii_case(CURRENT_ITERATION_MACRO) {
+        let current = timed(
+            iterations_client
+                .list(organization, project, team)
+                .timeframe("current"),
+        )
+        .await?
+        .value
+        .into_iter()
+        .next();
+        let resolved = current.and_then(|iteration| resolved_from(iteration, true));
+        return resolved
+            .ok_or_else(|| AdoError::NotFound(format!("No current iteration for team '{team}'")));
+    }
 
     // Fetch all iterations for the team and match by path or name
-    let iterations_client = work_client.iterations_client();
-    let iterations = iterations_client
-        .list(organization, project, team)
+    let iterations = timed(iterations_client.list(organization, project, team))
         .await?
         .value;
 
@@ -54,10 +187,12 @@ pub async fn resolve_iteration_id(
             (_, Some(name)) if name == iteration_path => true,
             _ => false,
         })
-        .and_then(|i| i.id);
+        .and_then(|iteration| resolved_from(iteration, false));
 
     matched.ok_or_else(|| {
-        anyhow::anyhow!("Iteration not found for team '{team}' and path or name '{iteration_path}'")
+        AdoError::NotFound(format!(
+            "Iteration not found for team '{team}' and path or name '{iteration_path}'"
+        ))
     })
 }
 
@@ -70,9 +205,13 @@ pub async fn get_iteration_ids(
     let credential = get_credential()?;
     let work_client = WorkClientBuilder::new(credential).build();
     let iterations_client = work_client.iterations_client();
-    let iteration_work_items = iterations_client
-        .get_iteration_work_items(organization, project, iteration_id, team)
-        .await?;
+    let iteration_work_items = timed(iterations_client.get_iteration_work_items(
+        organization,
+        project,
+        iteration_id,
+        team,
+    ))
+    .await?;
     let work_item_ids: Vec<i32> = iteration_work_items
         .work_item_relations
         .into_iter()
@@ -82,17 +221,23 @@ pub async fn get_iteration_ids(
     Ok(work_item_ids)
 }
 
-pub async fn get_backlog_ids(organization: &str, project: &str, team: &str) -> Result<Vec<i32>> {
+pub async fn get_backlog_ids(
+    organization: &str,
+    project: &str,
+    team: &str,
+    backlog_level: &str,
+) -> Result<Vec<i32>> {
     let credential = get_credential()?;
     let work_client = WorkClientBuilder::new(credential).build();
 
-    // Black magic string
-    let backlog_level = "Microsoft.RequirementCategory";
-
     let backlogs_client = work_client.backlogs_client();
-    let backlog_result = backlogs_client
-        .get_backlog_level_work_items(organization, project, team, backlog_level)
-        .await?;
+    let backlog_result = timed(backlogs_client.get_backlog_level_work_items(
+        organization,
+        project,
+        team,
+        backlog_level,
+    ))
+    .await?;
 
     let work_item_ids: Vec<i32> = backlog_result
         .work_items
@@ -104,28 +249,126 @@ pub async fn get_backlog_ids(organization: &str, project: &str, team: &str) -> R
     Ok(work_item_ids)
 }
 
+/// Most ids the work items list endpoint accepts in one request.
+const WORK_ITEMS_PAGE_SIZE: usize = 200;
+
+/// Whether loading a list keeps the pages that loaded when others fail.
+static PARTIAL_LOADS: AtomicBool = AtomicBool::new(false);
+
+/// Applies `partial_loads` to every following list load.
+pub fn set_partial_loads(enabled: bool) {
+    PARTIAL_LOADS.store(enabled, Ordering::Relaxed);
+}
+
+/// Fetches the items in pages of `WORK_ITEMS_PAGE_SIZE` ids, keeping the
+/// order of `work_item_ids`, so large backlogs are loaded completely.
 pub async fn get_items(
     organization: &str,
     project: &str,
     work_item_ids: Vec<i32>,
 ) -> Result<Vec<WorkItem>> {
-    let credential = get_credential()?;
+    let mut items = Vec::with_capacity(work_item_ids.len());
+    for (_, page) in fetch_item_pages(organization, project, &work_item_ids).await? {
+        items.extend(page?);
+    }
+    Ok(items)
+}
 
-    let ids: String = work_item_ids
-        .into_iter()
-        .map(|id| id.to_string())
-        .collect::<Vec<_>>()
-        .join(",");
+/// Items of a list, with the ids left out when pages failed to load.
+pub struct ListedItems {
+    pub items: Vec<WorkItem>,
+    pub missing: usize,
+    /// First failure among the pages left out.
+    pub error: Option<AdoError>,
+}
 
-    let wit_client = WitClientBuilder::new(credential).build();
+/// Like `get_items`, but with `partial_loads` on, failed pages are left out
+/// rather than failing the whole list, as long as any page loaded.
+pub async fn get_list_items(
+    organization: &str,
+    project: &str,
+    work_item_ids: Vec<i32>,
+) -> Result<ListedItems> {
+    if !PARTIAL_LOADS.load(Ordering::Relaxed) {
+        return Ok(ListedItems {
+            items: get_items(organization, project, work_item_ids).await?,
+            missing: 0,
+            error: None,
+        });
+    }
+    let mut listed = ListedItems {
+        items: Vec::with_capacity(work_item_ids.len()),
+        missing: 0,
+        error: None,
+    };
+    for (page_size, page) in fetch_item_pages(organization, project, &work_item_ids).await? {
+        match page {
+            Ok(items) => listed.items.extend(items),
+            Err(err) => {
+                listed.missing += page_size;
+                listed.error.get_or_insert(err);
+            }
+        }
+    }
+    if listed.items.is_empty()
+        && let Some(err) = listed.error
+    {
+        return Err(err);
+    }
+    Ok(listed)
+}
 
+/// Fetches every page concurrently, returning each page's size and result
+/// in the order of `work_item_ids`.
+async fn fetch_item_pages(
+    organization: &str,
+    project: &str,
+    work_item_ids: &[i32],
+) -> Result<Vec<(usize, Result<Vec<WorkItem>>)>> {
+    let credential = get_credential()?;
+    let wit_client = WitClientBuilder::new(credential).build();
     let work_items_client = wit_client.work_items_client();
-    let full_items = work_items_client
-        .list(organization, ids, project)
-        .await?;
 
-    let items = full_items.value.into_iter().map(WorkItem::from).collect();
-    Ok(items)
+    let mut pages = JoinSet::new();
+    for (index, (page_size, ids)) in id_pages(work_item_ids).into_iter().enumerate() {
+        let request = work_items_client
+            .list(organization, ids, project)
+            .expand("relations");
+        pages.spawn(async move {
+            let items = limited(timed(request))
+                .await
+                .map(|list| list.value.into_iter().map(WorkItem::from).collect());
+            (index, (page_size, items))
+        });
+    }
+
+    let mut fetched = Vec::with_capacity(pages.len());
+    while let Some(joined) = pages.join_next().await {
+        fetched.push(joined.map_err(|err| AdoError::Other(err.to_string()))?);
+    }
+    Ok(in_page_order(fetched))
+}
+
+/// Splits ids into pages of at most `WORK_ITEMS_PAGE_SIZE`, each as its size
+/// and the comma separated ids the list request takes.
+fn id_pages(work_item_ids: &[i32]) -> Vec<(usize, String)> {
+    work_item_ids
+        .chunks(WORK_ITEMS_PAGE_SIZE)
+        .map(|page| {
+            let ids = page
+                .iter()
+                .map(|id| id.to_string())
+                .collect::<Vec<_>>()
+                .join(",");
+            (page.len(), ids)
+        })
+        .collect()
+}
+
+/// Pages finish in any order; puts them back in the order of their index.
+fn in_page_order<T>(mut pages: Vec<(usize, T)>) -> Vec<T> {
+    pages.sort_by_key(|(index, _)| *index);
+    pages.into_iter().map(|(_, page)| page).collect()
 }
 
 pub async fn fetch_project_id(organization: &str, project_name: &str) -> Result<String> {
@@ -133,12 +376,11 @@ pub async fn fetch_project_id(organization: &str, project_name: &str) -> Result<
     let core_client = CoreClientBuilder::new(credential).build();
 
     let projects_client = core_client.projects_client();
-    let project = projects_client
-        .get(organization, project_name)
+    let project = timed(projects_client.get(organization, project_name))
         .await?
         .team_project_reference
         .id
-        .ok_or_else(|| anyhow!("Project id missing in response"))?;
+        .ok_or_else(|| AdoError::Parse("Project id missing in response".to_string()))?;
 
     Ok(project)
 }
@@ -148,10 +390,12 @@ pub async fn fetch_process_template_type(organization: &str, project_id: &str) -
     let core_client = CoreClientBuilder::new(credential).build();
 
     let projects_client = core_client.projects_client();
-    let properties = projects_client
-        .get_project_properties(organization, project_id)
-        .keys("System.ProcessTemplateType")
-        .await?;
+    let properties = timed(
+        projects_client
+            .get_project_properties(organization, project_id)
+            .keys("System.ProcessTemplateType"),
+    )
+    .await?;
 
     let process_template_type = properties
         .value
@@ -159,7 +403,7 @@ pub async fn fetch_process_template_type(organization: &str, project_id: &str) -
         .find(|prop| prop.name.as_deref() == Some("System.ProcessTemplateType"))
         .and_then(|prop| prop.value)
         .and_then(|val| val.as_str().map(|s| s.to_string()))
-        .ok_or_else(|| anyhow!("System.ProcessTemplateType not found"))?;
+        .ok_or_else(|| AdoError::NotFound("System.ProcessTemplateType".to_string()))?;
 
     Ok(process_template_type)
 }
@@ -172,8 +416,7 @@ pub async fn fetch_process_work_item_types(
     let processes_client = ProcessesClientBuilder::new(credential).build();
 
     let work_item_types_client = processes_client.work_item_types_client();
-    let work_item_types = work_item_types_client
-        .list(organization, process_id)
+    let work_item_types = timed(work_item_types_client.list(organization, process_id))
         .await?
         .value;
 
@@ -198,10 +441,7 @@ pub async fn fetch_work_item_layout(
     let processes_client = ProcessesClientBuilder::new(credential).build();
 
     let layout_client = processes_client.layout_client();
-    let layout = layout_client
-        .get(organization, process_id, wit_ref_name)
-        .await
-        .context("Failed to fetch work item layout")?;
+    let layout = timed(layout_client.get(organization, process_id, wit_ref_name)).await?;
     Ok(layout)
 }
 
@@ -220,11 +460,13 @@ pub async fn fetch_work_item_type_fields(
     let wit_client = WitClientBuilder::new(credential).build();
 
     let work_item_types_field_client = wit_client.work_item_types_field_client();
-    let fields = work_item_types_field_client
-        .list(organization, project, work_item_type_ref)
-        .expand("allowedValues")
-        .await?
-        .value;
+    let fields = timed(
+        work_item_types_field_client
+            .list(organization, project, work_item_type_ref)
+            .expand("allowedValues"),
+    )
+    .await?
+    .value;
 
     let mapped = fields
         .into_iter()
@@ -254,6 +496,7 @@ pub async fn build_field_metadata_cache(
     refresh_policy: RefreshPolicy,
 ) -> HashMap<String, Vec<WorkItemFieldInfo>> {
     let mut cache = HashMap::new();
+    let mut fetches = JoinSet::new();
     for display_name in display_names {
         let cache_key = FieldMetaCacheKey {
             organization: organization.to_string(),
@@ -273,10 +516,24 @@ pub async fn build_field_metadata_cache(
             continue;
         }
 
-        match fetch_work_item_type_fields(organization, project, &display_name).await {
+        fetches.spawn(async move {
+            let fields = limited(fetch_work_item_type_fields(
+                &cache_key.organization,
+                &cache_key.project,
+                &display_name,
+            ))
+            .await;
+            (display_name, cache_key, fields)
+        });
+    }
+    while let Some(joined) = fetches.join_next().await {
+        let Ok((display_name, cache_key, fields)) = joined else {
+            continue;
+        };
+        match fields {
             Ok(fields) => {
                 let _ = write_field_meta_cache(&cache_key, &fields);
-                cache.insert(display_name.clone(), fields);
+                cache.insert(display_name, fields);
             }
             Err(err) => {
                 eprintln!(
@@ -289,14 +546,200 @@ pub async fn build_field_metadata_cache(
     cache
 }
 
-pub async fn update_work_item_in_ado(
-    board: &BoardConfig,
-    item: &WorkItem,
-    state: &crate::app::DetailEditState,
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct TeamMemberInfo {
+    pub display_name: String,
+    pub unique_name: String,
+}
+
+impl TeamMemberInfo {
+    /// Identity in the `Display Name <unique name>` form ADO accepts for identity fields.
+    pub fn identity(&self) -> String {
+        format!("{} <{}>", self.display_name, self.unique_name)
+    }
+}
+
+/// Paths of the iterations selected for the team, as `System.IterationPath`
+/// values.
+pub async fn fetch_team_iteration_paths(
+    organization: &str,
+    project: &str,
+    team: &str,
+) -> Result<Vec<String>> {
+    let credential = get_credential()?;
+    let work_client = WorkClientBuilder::new(credential).build();
+
+    let iterations = timed(
+        work_client
+            .iterations_client()
+            .list(organization, project, team),
+    )
+    .await?
+    .value;
+
+    Ok(iterations
+        .into_iter()
+        .filter_map(|iteration| iteration.path)
+        .collect())
+}
+
+pub async fn fetch_team_members(
+    organization: &str,
+    project: &str,
+    team: &str,
+) -> Result<Vec<TeamMemberInfo>> {
+    let credential = get_credential()?;
+    let core_client = CoreClientBuilder::new(credential).build();
+
+    let members = timed(
+        core_client
+            .teams_client()
+            .get_team_members_with_extended_properties(organization, project, team),
+    )
+    .await?
+    .value;
+
+    let mapped = members
+        .into_iter()
+        .filter_map(|member| member.identity)
+        .filter(|identity| !identity.is_container.unwrap_or(false))
+        .filter_map(|identity| {
+            Some(TeamMemberInfo {
+                display_name: identity.graph_subject_base.display_name?,
+                unique_name: identity.unique_name?,
+            })
+        })
+        .collect();
+
+    Ok(mapped)
+}
+
+pub async fn fetch_repository_name(
+    organization: &str,
+    project: &str,
+    repository_id: &str,
+) -> Result<String> {
+    let credential = get_credential()?;
+    let git_client = GitClientBuilder::new(credential).build();
+
+    let repository = timed(git_client.repositories_client().get_repository(
+        organization,
+        repository_id,
+        project,
+    ))
+    .await?;
+    Ok(repository.name)
+}
+
+pub async fn assign_work_item(
+    organization: &str,
+    project: &str,
+    work_item_id: u32,
+    assignee: &str,
+) -> Result<()> {
+    let credential = get_credential()?;
+    let wit_client = WitClientBuilder::new(credential).build();
+
+    let operations = vec![JsonPatchOperation {
+        from: None,
+        op: Some(Op::Replace),
+        path: Some("/fields/System.AssignedTo".to_string()),
+        value: Some(serde_json::json!(assignee)),
+    }];
+
+    timed(wit_client.work_items_client().update(
+        organization,
+        operations,
+        work_item_id as i32,
+        project,
+    ))
+    .await
+    .map(|_| ())
+}
+
+/// Where a reordered item goes: between two neighbours (`0` for the start or
+/// end of the list), under its parent (`0` without one).
+pub struct RankPlacement {
+    pub previous_id: u32,
+    pub next_id: u32,
+    pub parent_id: u32,
+}
+
+/// Moves a work item in the team's backlog order, or in the iteration's
+/// order when an iteration id is given.
+pub async fn reorder_work_item(
+    organization: &str,
+    project: &str,
+    team: &str,
+    iteration_id: Option<&str>,
+    work_item_id: u32,
+    placement: RankPlacement,
 ) -> Result<()> {
+    let credential = get_credential()?;
+    let work_client = WorkClientBuilder::new(credential).build();
+    let orders_client = work_client.workitemsorder_client();
+
+    let operation = ReorderOperation {
+        ids: vec![work_item_id as i32],
+        iteration_path: None,
+        next_id: Some(placement.next_id as i32),
+        parent_id: Some(placement.parent_id as i32),
+        previous_id: Some(placement.previous_id as i32),
+    };
+    match iteration_id {
+        Some(iteration_id) => {
+            timed(orders_client.reorder_iteration_work_items(
+                organization,
+                operation,
+                project,
+                team,
+                iteration_id,
+            ))
+            .await?;
+        }
+        None => {
+            timed(orders_client.reorder_backlog_work_items(organization, operation, project, team))
+                .await?;
+        }
+    }
+    Ok(())
+}
+
+/// Creates a work item of the given type with the fields set to the values.
+pub async fn create_work_item(
+    organization: &str,
+    project: &str,
+    work_item_type: &str,
+    fields: Vec<(String, serde_json::Value)>,
+) -> Result<WorkItem> {
     let credential = get_credential()?;
     let wit_client = WitClientBuilder::new(credential).build();
 
+    let operations = fields
+        .into_iter()
+        .map(|(reference, value)| JsonPatchOperation {
+            from: None,
+            op: Some(Op::Add),
+            path: Some(format!("/fields/{}", reference)),
+            value: Some(value),
+        })
+        .collect();
+
+    let created = timed(wit_client.work_items_client().create(
+        organization,
+        operations,
+        project,
+        work_item_type,
+    ))
+    .await?;
+    Ok(WorkItem::from(created))
+}
+
+/// JSON patch that `update_work_item_in_ado` sends for the edited fields.
+pub fn build_update_operations(
+    state: &crate::app::DetailEditState,
+    field_formats: &BTreeMap<String, FieldFormat>,
+) -> Vec<JsonPatchOperation> {
     let mut operations = vec![JsonPatchOperation {
         from: None,
         op: Some(Op::Replace),
@@ -304,26 +747,206 @@ pub async fn update_work_item_in_ado(
         value: Some(serde_json::json!(state.title.clone())),
     }];
 
-    for field in &state.visible_fields {
+    // Untouched fields are left as they are in ADO; that also lets ADO derive
+    // the reason from a new state, as the loaded one may not be valid for it.
+    for field in state
+        .visible_fields
+        .iter()
+        .filter(|field| field.is_modified())
+    {
         operations.push(JsonPatchOperation {
             from: None,
             op: Some(Op::Replace),
             path: Some(format!("/fields/{}", field.reference)),
             value: Some(serde_json::json!(field.value.clone())),
         });
+        // Without the format flag ADO treats the text as HTML.
+        if field_formats.get(&field.reference) == Some(&FieldFormat::Markdown) {
+            operations.push(JsonPatchOperation {
+                from: None,
+                op: Some(Op::Add),
+                path: Some(format!("/multilineFieldsFormat/{}", field.reference)),
+                value: Some(serde_json::json!("Markdown")),
+            });
+        }
     }
 
-    wit_client
-        .work_items_client()
-        .update(
-            &board.organization,
-            operations,
-            item.id as i32,
-            &board.project,
-        )
-        .await
-        .map(|_| ())
-        .map_err(anyhow::Error::from)
+    operations
+}
+
+pub async fn update_work_item_in_ado(
+    board: &BoardConfig,
+    item: &WorkItem,
+    state: &crate::app::DetailEditState,
+    field_formats: &BTreeMap<String, FieldFormat>,
+) -> Result<()> {
+    let credential = get_credential()?;
+    let wit_client = WitClientBuilder::new(credential).build();
+
+    let operations = build_update_operations(state, field_formats);
+
+    timed(wit_client.work_items_client().update(
+        &board.organization,
+        operations,
+        item.id as i32,
+        &board.project,
+    ))
+    .await
+    .map(|_| ())
+}
+
+/// Authorization header for requests the generated clients don't cover,
+/// using the same credential as `get_credential`.
+async fn authorization_header() -> Result<String> {
+    match std::env::var("ADO_TOKEN") {
+        Ok(token) if !token.is_empty() => Ok(format!(
+            "Basic {}",
+            BASE64_STANDARD.encode(format!(":{}", token))
+        )),
+        _ => {
+            let credential = AzureCliCredential::new(None)?;
+            let token = credential.get_token(&[ADO_SCOPE], None).await?;
+            Ok(format!("Bearer {}", token.token.secret()))
+        }
+    }
+}
+
+/// Sends a request to `_apis/<path>` of the organization and returns the
+/// JSON response, for endpoints the generated clients don't cover.
+async fn send_api_request(
+    organization: &str,
+    method: Method,
+    path: &str,
+    body: Option<serde_json::Value>,
+) -> Result<serde_json::Value> {
+    let url = format!("{}/{}/_apis/{}", DEFAULT_BASE_URL, organization, path);
+    let url = Url::parse(&url).map_err(|err| AdoError::Other(err.to_string()))?;
+    let mut request = Request::new(url, method);
+    request.insert_header("authorization", timed(authorization_header()).await?);
+    if let Some(body) = body {
+        request.set_json(&body)?;
+    }
+
+    let body = timed(async {
+        let response = new_http_client().execute_request(&request).await?;
+        check_success(response).await?.into_body().collect().await
+    })
+    .await?;
+    if body.is_empty() {
+        return Ok(serde_json::Value::Null);
+    }
+    Ok(serde_json::from_slice(&body)?)
+}
+
+/// How requests are authenticated, for display.
+pub fn auth_method_label() -> &'static str {
+    match std::env::var("ADO_TOKEN") {
+        Ok(token) if !token.is_empty() => "PAT from $ADO_TOKEN",
+        _ => "Azure CLI",
+    }
+}
+
+/// User the credential signs in as.
+pub struct AuthenticatedUser {
+    pub display_name: String,
+    pub account: Option<String>,
+}
+
+impl AuthenticatedUser {
+    /// Name and account, as shown on the loading screen.
+    pub fn label(&self) -> String {
+        match &self.account {
+            Some(account) => format!("{} <{}>", self.display_name, account),
+            None => self.display_name.clone(),
+        }
+    }
+}
+
+pub async fn fetch_authenticated_user(organization: &str) -> Result<AuthenticatedUser> {
+    let response = send_api_request(organization, Method::Get, "connectionData", None).await?;
+    let user = response
+        .get("authenticatedUser")
+        .ok_or_else(|| AdoError::Parse("connection data has no user".to_string()))?;
+    let display_name = user
+        .get("providerDisplayName")
+        .and_then(|name| name.as_str())
+        .unwrap_or_default()
+        .to_string();
+    let account = user
+        .pointer("/properties/Account/$value")
+        .and_then(|account| account.as_str())
+        .filter(|account| !account.is_empty())
+        .map(str::to_string);
+    Ok(AuthenticatedUser {
+        display_name,
+        account,
+    })
+}
+
+fn follow_filter(work_item_id: u32) -> serde_json::Value {
+    serde_json::json!({
+        "type": "Artifact",
+        "eventType": "",
+        "artifactType": "WorkItem",
+        "artifactId": work_item_id.to_string(),
+    })
+}
+
+/// Id of the current user's follow subscription for the work item, if any.
+pub async fn fetch_follow_subscription(
+    organization: &str,
+    work_item_id: u32,
+) -> Result<Option<String>> {
+    let body = serde_json::json!({
+        "conditions": [{ "filter": follow_filter(work_item_id) }],
+    });
+    let response = send_api_request(
+        organization,
+        Method::Post,
+        "notification/subscriptionquery?api-version=7.1",
+        Some(body),
+    )
+    .await?;
+
+    Ok(response
+        .get("value")
+        .and_then(|value| value.as_array())
+        .and_then(|subscriptions| subscriptions.first())
+        .and_then(|subscription| subscription.get("id"))
+        .and_then(|id| id.as_str())
+        .map(|id| id.to_string()))
+}
+
+/// Follows the work item and returns the id of the new subscription.
+pub async fn follow_work_item(organization: &str, work_item_id: u32) -> Result<String> {
+    let body = serde_json::json!({ "filter": follow_filter(work_item_id) });
+    let response = send_api_request(
+        organization,
+        Method::Post,
+        "notification/subscriptions?api-version=7.1",
+        Some(body),
+    )
+    .await?;
+
+    response
+        .get("id")
+        .and_then(|id| id.as_str())
+        .map(|id| id.to_string())
+        .ok_or_else(|| AdoError::Parse("follow subscription has no id".to_string()))
+}
+
+pub async fn unfollow_work_item(organization: &str, subscription_id: &str) -> Result<()> {
+    send_api_request(
+        organization,
+        Method::Delete,
+        &format!(
+            "notification/subscriptions/{}?api-version=7.1",
+            subscription_id
+        ),
+        None,
+    )
+    .await
+    .map(|_| ())
 }
 
 impl From<ADOWorkItem> for WorkItem {
@@ -334,14 +957,20 @@ impl From<ADOWorkItem> for WorkItem {
                 .and_then(|v| v.as_str())
                 .map_or("".to_string(), clean_ado_text)
         };
-        let assigned_to_name: String = item
+        let assigned_to = item
             .fields
             .get("System.AssignedTo")
-            .and_then(|assigned_to| assigned_to.as_object())
+            .and_then(|assigned_to| assigned_to.as_object());
+        let assigned_to_name: String = assigned_to
             .and_then(|assigned_to| assigned_to.get("displayName"))
             .and_then(|display_name| display_name.as_str())
             .map(|s| s.to_string())
             .unwrap_or("Unassigned".to_string());
+        let assigned_to_email: String = assigned_to
+            .and_then(|assigned_to| assigned_to.get("uniqueName"))
+            .and_then(|unique_name| unique_name.as_str())
+            .map(|s| s.to_string())
+            .unwrap_or_default();
 
         let fields = item
             .fields
@@ -354,6 +983,15 @@ impl From<ADOWorkItem> for WorkItem {
                     .collect()
             })
             .unwrap_or_default();
+        let raw_fields = item
+            .fields
+            .as_object()
+            .map(|map| {
+                map.iter()
+                    .map(|(key, value)| (key.clone(), value.clone()))
+                    .collect()
+            })
+            .unwrap_or_default();
 
         WorkItem {
             id: item.id as u32,
@@ -362,8 +1000,78 @@ impl From<ADOWorkItem> for WorkItem {
             description: get_and_clean_field("System.Description"),
             acceptance_criteria: get_and_clean_field("Microsoft.VSTS.Common.AcceptanceCriteria"),
             assigned_to: assigned_to_name,
+            assigned_to_email,
             state: get_and_clean_field("System.State"),
+            changed_date: get_and_clean_field("System.ChangedDate"),
+            board_column: get_and_clean_field("System.BoardColumn"),
+            board_lane: get_and_clean_field("System.BoardLane"),
+            linked_branches: item
+                .relations
+                .iter()
+                .filter(|relation| relation.link.rel == "ArtifactLink")
+                .filter_map(|relation| LinkedBranch::from_artifact_url(&relation.link.url))
+                .collect(),
+            linked_artifacts: item
+                .relations
+                .iter()
+                .filter(|relation| relation.link.rel == "ArtifactLink")
+                .filter_map(|relation| LinkedArtifact::from_artifact_url(&relation.link.url))
+                .collect(),
+            parent_id: item
+                .relations
+                .iter()
+                .find(|relation| relation.link.rel == "System.LinkTypes.Hierarchy-Reverse")
+                .and_then(|relation| work_item_id_from_url(&relation.link.url)),
+            tags: get_and_clean_field("System.Tags")
+                .split(';')
+                .map(|tag| tag.trim().to_string())
+                .filter(|tag| !tag.is_empty())
+                .collect(),
             fields,
+            raw_fields,
+            board: None,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ids_are_split_into_pages_of_at_most_the_page_size() {
+        let ids: Vec<i32> = (1..=450).collect();
+        let pages = id_pages(&ids);
+
+        let sizes: Vec<usize> = pages.iter().map(|(size, _)| *size).collect();
+        assert_eq!(sizes, [200, 200, 50]);
+        assert!(pages[0].1.starts_with("1,2,3,"));
+        assert!(pages[0].1.ends_with(",200"));
+        assert!(pages[2].1.starts_with("401,"));
+        assert!(pages[2].1.ends_with(",450"));
+        assert!(id_pages(&[]).is_empty());
+    }
+
+    #[test]
+    fn pages_are_put_back_in_id_order() {
+        let finished = vec![(2, "c"), (0, "a"), (1, "b")];
+        assert_eq!(in_page_order(finished), ["a", "b", "c"]);
+    }
+
+    #[test]
+    fn ado_item_without_title_type_or_state_gets_placeholders() {
+        let ado_item: ADOWorkItem = serde_json::from_value(serde_json::json!({
+            "id": 42,
+            "rev": 1,
+            "fields": {},
+            "url": "https://dev.azure.com/org/project/_apis/wit/workItems/42",
+        }))
+        .unwrap();
+        let item = WorkItem::from(ado_item);
+
+        assert_eq!(item.display_title(), "(untitled #42)");
+        assert_eq!(item.display_type(), "Unknown type");
+        assert_eq!(item.display_state(), "Unknown state");
+        assert!(item.matches_query("untitled"));
+    }
+}
@@ -1,26 +1,87 @@
 use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
-    text::{Line, Span},
+    text::{Line, Span, Text},
     widgets::{Block, Borders, Clear, List, ListItem, Paragraph, Wrap},
 };
 
-use crate::app::App;
-use crate::models::DetailField;
+use std::time::{Duration, SystemTime};
 
-fn calculate_popup_rect(frame_area: Rect, app: &App, list_area: Rect) -> Option<Rect> {
+use crate::app::{
+    ACCEPTANCE_CRITERIA_FIELD, App, DESCRIPTION_FIELD, ErrorReport, HoverContent, ItemChange,
+    ListPicker, ListRow, REASON_FIELD,
+};
+use crate::config::{DetailWrap, KeysConfig, SortKey};
+use crate::models::{DetailField, WorkItem};
+use crate::services::ResolvedIteration;
+
+/// Items untouched for this long get the "oldest" end of the heatmap ramp.
+const AGE_HEATMAP_MAX_DAYS: f32 = 90.0;
+
+/// Text lines a collapsed detail field keeps visible.
+const COLLAPSED_FIELD_LINES: u16 = 1;
+
+/// Maps an item's age onto a continuous green → yellow → red ramp.
+fn age_color(age: Duration) -> Color {
+    let days = age.as_secs_f32() / 86_400.0;
+    let t = (days / AGE_HEATMAP_MAX_DAYS).clamp(0.0, 1.0);
+    let (red, green) = if t < 0.5 {
+        ((t * 2.0 * 255.0) as u8, 200)
+    } else {
+        (255, ((1.0 - t) * 2.0 * 200.0) as u8)
+    };
+    Color::Rgb(red, green, 60)
+}
+
+/// Color for a work item state, grouped by where the state sits in the usual
+/// ADO workflows.
+fn state_color(state: &str) -> Color {
+    match state.to_lowercase().as_str() {
+        "new" | "to do" | "proposed" => Color::Gray,
+        "active" | "in progress" | "committed" | "open" => Color::LightBlue,
+        "resolved" | "ready" | "in review" => Color::LightYellow,
+        "done" | "closed" | "completed" => Color::LightGreen,
+        "removed" | "cut" => Color::DarkGray,
+        _ => Color::White,
+    }
+}
+
+fn state_pill(state: &str) -> Span<'static> {
+    Span::styled(
+        format!(" {} ", state),
+        Style::default()
+            .fg(Color::Black)
+            .bg(state_color(state))
+            .add_modifier(Modifier::BOLD),
+    )
+}
+
+/// Lines between the first visible row and the selected one, and the height
+/// of the selected row.
+fn selected_row_position(app: &App) -> Option<(u16, u16)> {
     let selected_index = app.list_view_state.list_state.selected()?;
     let offset = app.list_view_state.list_state.offset();
+    let heights = &app.list_view_state.row_heights;
+    let height_of = |index: usize| heights.get(index).copied().unwrap_or(1);
+    let relative_y = (offset..selected_index).map(height_of).sum();
+    Some((relative_y, height_of(selected_index)))
+}
 
-    let relative_y = (selected_index.saturating_sub(offset)) as u16;
+fn calculate_popup_rect(
+    frame_area: Rect,
+    app: &App,
+    list_area: Rect,
+    content_lines: u16,
+    popup_width: u16,
+) -> Option<Rect> {
+    let (relative_y, row_height) = selected_row_position(app)?;
 
-    let popup_height = 4;
-    let popup_width = 45;
+    let popup_height = content_lines.saturating_add(2);
 
     let selected_y_on_screen = list_area.y + 1 + relative_y;
 
     let mut x = list_area.x + 20;
-    let mut y = selected_y_on_screen + 1;
+    let mut y = selected_y_on_screen + row_height;
 
     if y + popup_height > frame_area.height {
         y = selected_y_on_screen.saturating_sub(popup_height);
@@ -32,23 +93,24 @@ fn calculate_popup_rect(frame_area: Rect, app: &App, list_area: Rect) -> Option<
         x = frame_area.width.saturating_sub(popup_width + 1);
     }
     x = x.max(frame_area.x + 1);
-    Some(Rect {
-        x,
-        y,
-        width: popup_width,
-        height: popup_height,
-    })
+    Some(
+        Rect {
+            x,
+            y,
+            width: popup_width,
+            height: popup_height,
+        }
+        .clamp(frame_area),
+    )
 }
 
-fn calculate_type_filter_rect(
+fn calculate_list_picker_rect(
     frame_area: Rect,
     app: &App,
     list_area: Rect,
     content_lines: u16,
 ) -> Option<Rect> {
-    let selected_index = app.list_view_state.list_state.selected()?;
-    let offset = app.list_view_state.list_state.offset();
-    let relative_y = (selected_index.saturating_sub(offset)) as u16;
+    let (relative_y, row_height) = selected_row_position(app)?;
 
     let desired_height = content_lines.saturating_add(2);
     let popup_height = desired_height
@@ -60,7 +122,7 @@ fn calculate_type_filter_rect(
 
     let indent = 2;
     let mut x = list_area.x.saturating_add(indent);
-    let mut y = selected_y_on_screen + 1;
+    let mut y = selected_y_on_screen + row_height;
 
     let list_max_width = list_area.width.saturating_sub(2);
     popup_width = popup_width
@@ -84,12 +146,15 @@ fn calculate_type_filter_rect(
     x = x.min(list_right_bound).min(frame_right_bound);
     x = x.max(list_area.x + 1).max(frame_area.x + 1);
 
-    Some(Rect {
-        x,
-        y,
-        width: popup_width,
-        height: popup_height,
-    })
+    Some(
+        Rect {
+            x,
+            y,
+            width: popup_width,
+            height: popup_height,
+        }
+        .clamp(frame_area),
+    )
 }
 
 fn calculate_detail_picker_rect(
@@ -122,31 +187,190 @@ fn calculate_detail_picker_rect(
     }
     x = x.max(frame_area.x + 1);
 
-    Some(Rect {
-        x,
-        y,
-        width: popup_width,
-        height: popup_height,
-    })
+    Some(
+        Rect {
+            x,
+            y,
+            width: popup_width,
+            height: popup_height,
+        }
+        .clamp(frame_area),
+    )
 }
 
-fn draw_hover_popup(f: &mut ratatui::Frame, app: &mut App, list_area: Rect) {
-    if app.list_view_state.is_list_details_hover_visible {
-        if let Some(item) = app.get_selected_item() {
-            if let Some(popup_rect) = calculate_popup_rect(f.area(), app, list_area) {
-                f.render_widget(Clear, popup_rect);
-                let content_text = vec![
-                    Line::from(format!("Assigned To: {}", item.assigned_to)),
-                    Line::from(format!("State: {}", item.state)),
-                ];
-
-                let popup_block = Block::default()
-                    .borders(Borders::ALL)
-                    .title("Details")
-                    .border_style(Style::default().fg(Color::LightBlue));
-                f.render_widget(Paragraph::new(content_text).block(popup_block), popup_rect);
+/// Greedy word wrap; words longer than `width` are split.
+fn wrap_words(text: &str, width: usize) -> Vec<String> {
+    let width = width.max(1);
+    let mut lines: Vec<String> = Vec::new();
+    let mut current = String::new();
+    for word in text.split_whitespace() {
+        let mut word: Vec<char> = word.chars().collect();
+        while word.len() > width {
+            if !current.is_empty() {
+                lines.push(std::mem::take(&mut current));
             }
+            lines.push(word.drain(..width).collect());
+        }
+        let word: String = word.into_iter().collect();
+        let current_len = current.chars().count();
+        if current_len > 0 && current_len + 1 + word.chars().count() > width {
+            lines.push(std::mem::take(&mut current));
+        }
+        if !current.is_empty() {
+            current.push(' ');
         }
+        current.push_str(&word);
+    }
+    if !current.is_empty() || lines.is_empty() {
+        lines.push(current);
+    }
+    lines
+}
+
+fn draw_title_peek_popup(f: &mut ratatui::Frame, app: &App, list_area: Rect) {
+    let Some(item) = app.get_selected_item() else {
+        return;
+    };
+    let popup_width = 60.min(f.area().width.saturating_sub(2));
+    let lines: Vec<Line> = wrap_words(
+        &item.display_title(),
+        popup_width.saturating_sub(2) as usize,
+    )
+    .into_iter()
+    .map(Line::from)
+    .collect();
+
+    let Some(popup_rect) =
+        calculate_popup_rect(f.area(), app, list_area, lines.len() as u16, popup_width)
+    else {
+        return;
+    };
+    f.render_widget(Clear, popup_rect);
+
+    let popup_block = Block::default()
+        .borders(Borders::ALL)
+        .title(format!("#{}", item.id))
+        .border_style(Style::default().fg(Color::LightBlue));
+    f.render_widget(Paragraph::new(lines).block(popup_block), popup_rect);
+}
+
+/// Assignee, state and board position shown by the hover popup and the
+/// details pane.
+fn item_summary_lines(app: &App, item: &WorkItem) -> Vec<Line<'static>> {
+    let mut lines = vec![
+        Line::from(format!(
+            "Assigned To: {}",
+            item.assignee_label(app.assignee_display)
+        )),
+        Line::from(vec![
+            Span::raw("State: "),
+            Span::styled(
+                item.display_state().to_string(),
+                Style::default().fg(state_color(&item.state)),
+            ),
+        ]),
+    ];
+    if !item.board_column.is_empty() {
+        lines.push(Line::from(format!("Column: {}", item.board_column)));
+    }
+    if !item.board_lane.is_empty() {
+        lines.push(Line::from(format!("Lane: {}", item.board_lane)));
+    }
+    lines
+}
+
+/// Total, per-state and own item counts, states in their list colors.
+fn summary_line(app: &App) -> Line<'static> {
+    let summary = app.item_summary();
+    let separator = || Span::styled(" · ", Style::default().fg(Color::DarkGray));
+    let mut spans = vec![Span::raw(format!(" {} items", summary.total))];
+    for (state, count) in summary.by_state {
+        spans.push(separator());
+        spans.push(Span::styled(
+            format!("{} {}", count, state),
+            Style::default().fg(state_color(&state)),
+        ));
+    }
+    spans.push(separator());
+    spans.push(Span::styled(
+        format!("{} mine", summary.assigned_to_me),
+        Style::default().add_modifier(Modifier::BOLD),
+    ));
+    Line::from(spans)
+}
+
+/// Narrowest list that still gets a details pane next to it.
+const MIN_LIST_WIDTH_WITH_PANE: u16 = 30;
+
+/// Splits off the details pane from the list area when it is pinned and the
+/// area is wide enough for both.
+fn split_details_pane(app: &App, area: Rect) -> (Rect, Option<Rect>) {
+    let pane_width = app.details_pane_width;
+    if !app.list_view_state.details_pane_pinned
+        || area.width < pane_width.saturating_add(MIN_LIST_WIDTH_WITH_PANE)
+    {
+        return (area, None);
+    }
+    let chunks = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Min(0), Constraint::Length(pane_width)])
+        .split(area);
+    (chunks[0], Some(chunks[1]))
+}
+
+fn draw_details_pane(f: &mut ratatui::Frame, app: &App, area: Rect) {
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Color::LightBlue)
+        .title("Details");
+    let Some(item) = app.get_selected_item() else {
+        let empty = Paragraph::new(Line::from("No item selected"))
+            .style(Style::default().fg(Color::DarkGray))
+            .block(block);
+        f.render_widget(empty, area);
+        return;
+    };
+
+    let mut lines = item_summary_lines(app, item);
+    if !item.description.is_empty() {
+        lines.push(Line::from(""));
+        lines.extend(
+            item.description
+                .lines()
+                .map(|line| Line::from(line.to_string())),
+        );
+    }
+    let paragraph = Paragraph::new(lines)
+        .wrap(Wrap { trim: false })
+        .block(block);
+    f.render_widget(paragraph, area);
+}
+
+fn draw_hover_popup(f: &mut ratatui::Frame, app: &mut App, list_area: Rect) {
+    if app.list_view_state.is_list_details_hover_visible
+        && app.list_view_state.hover_content == HoverContent::Title
+    {
+        draw_title_peek_popup(f, app, list_area);
+        return;
+    }
+
+    if app.list_view_state.is_list_details_hover_visible
+        && let Some(item) = app.get_selected_item()
+    {
+        let content_text = item_summary_lines(app, item);
+
+        let Some(popup_rect) =
+            calculate_popup_rect(f.area(), app, list_area, content_text.len() as u16, 45)
+        else {
+            return;
+        };
+        f.render_widget(Clear, popup_rect);
+
+        let popup_block = Block::default()
+            .borders(Borders::ALL)
+            .title("Details")
+            .border_style(Style::default().fg(Color::LightBlue));
+        f.render_widget(Paragraph::new(content_text).block(popup_block), popup_rect);
     }
 }
 
@@ -181,27 +405,453 @@ fn draw_picker_popup(
 
     f.render_widget(Clear, rect);
 
+    // Keep the selected option visible when there are more options than rows.
+    let visible_rows = rect.height.saturating_sub(2).max(1) as usize;
+    let scroll = picker
+        .selected
+        .map(|selected| selected.saturating_sub(visible_rows - 1))
+        .unwrap_or(0) as u16;
+
     let popup_block = Block::default()
         .borders(Borders::ALL)
         .title(title)
         .border_style(Style::default().fg(Color::LightBlue));
-    f.render_widget(Paragraph::new(content_lines).block(popup_block), rect);
+    f.render_widget(
+        Paragraph::new(content_lines)
+            .scroll((scroll, 0))
+            .block(popup_block),
+        rect,
+    );
 }
 
-fn draw_type_filter_popup(f: &mut ratatui::Frame, app: &mut App, list_area: Rect) {
-    if !app.list_view_state.type_picker.is_open {
+pub fn draw_command_palette(f: &mut ratatui::Frame, app: &App) {
+    let palette = &app.command_palette;
+    if !palette.is_open {
         return;
     }
 
-    let content_height = app.list_view_state.type_picker.options.len().max(1) as u16;
+    let matches = palette.matches();
+    let area = f.area();
+    let width = 70.min(area.width.saturating_sub(2));
+    let height = (matches.len().max(1) as u16)
+        .saturating_add(3)
+        .min(area.height.saturating_sub(2));
+    let popup_rect = Rect {
+        x: area.x + area.width.saturating_sub(width) / 2,
+        y: area.y + area.height.saturating_sub(height) / 2,
+        width,
+        height,
+    }
+    .clamp(area);
+
+    let mut lines: Vec<Line> = vec![Line::from(vec![
+        Span::styled(":", Style::default().fg(Color::Cyan)),
+        Span::raw(palette.query.clone()),
+    ])];
+    if matches.is_empty() {
+        lines.push(Line::from("No matching commands"));
+    }
+    for (idx, command) in matches.iter().enumerate() {
+        let style = if idx == palette.selected {
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default()
+        };
+        lines.push(Line::from(vec![
+            Span::styled(command.name(), style),
+            Span::styled(
+                format!("  {}", command.binding(&app.keys)),
+                Style::default().fg(Color::DarkGray),
+            ),
+        ]));
+    }
 
-    if let Some(popup_rect) = calculate_type_filter_rect(f.area(), app, list_area, content_height) {
-        draw_picker_popup(
-            f,
-            &app.list_view_state.type_picker,
-            "Type Filter",
-            popup_rect,
-        );
+    // The query line stays put; only the command list scrolls.
+    let visible_rows = popup_rect.height.saturating_sub(3).max(1) as usize;
+    let scroll = palette.selected.saturating_sub(visible_rows - 1);
+    if scroll > 0 {
+        lines.drain(1..=scroll);
+    }
+
+    f.render_widget(Clear, popup_rect);
+    let popup_block = Block::default()
+        .borders(Borders::ALL)
+        .title("Commands")
+        .border_style(Style::default().fg(Color::LightBlue));
+    f.render_widget(Paragraph::new(lines).block(popup_block), popup_rect);
+}
+
+pub fn draw_global_search(f: &mut ratatui::Frame, app: &App) {
+    let search = &app.global_search;
+    if !search.is_open {
+        return;
+    }
+
+    let area = f.area();
+    let width = 90.min(area.width.saturating_sub(2));
+    let height = 24.min(area.height.saturating_sub(2));
+    let popup_rect = Rect {
+        x: area.x + area.width.saturating_sub(width) / 2,
+        y: area.y + area.height.saturating_sub(height) / 2,
+        width,
+        height,
+    }
+    .clamp(area);
+
+    let mut header: Vec<Line> = vec![Line::from(vec![
+        Span::styled("/", Style::default().fg(Color::Cyan)),
+        Span::raw(search.query.clone()),
+    ])];
+    for failure in &search.failures {
+        header.push(Line::from(Span::styled(
+            format!("Skipped {}", failure),
+            Style::default().fg(Color::Yellow),
+        )));
+    }
+
+    // Results are grouped under a heading per source; remember which line
+    // the selected result ends up on so it can be scrolled into view.
+    let mut lines: Vec<Line> = Vec::new();
+    let mut selected_line = 0;
+    if search.is_searching() {
+        lines.push(Line::from("Searching all boards..."));
+    } else if !search.has_current_results() {
+        lines.push(Line::from(Span::styled(
+            "Press Enter to search all boards",
+            Style::default().fg(Color::DarkGray),
+        )));
+    } else if search.results.is_empty() {
+        lines.push(Line::from("No matching items"));
+    }
+    let mut previous_source = None;
+    for (idx, result) in search.results.iter().enumerate() {
+        if previous_source != Some(result.source_index) {
+            previous_source = Some(result.source_index);
+            lines.push(Line::from(Span::styled(
+                app.sources[result.source_index].title.clone(),
+                Style::default()
+                    .fg(Color::LightBlue)
+                    .add_modifier(Modifier::BOLD),
+            )));
+        }
+        let style = if idx == search.selected {
+            selected_line = lines.len();
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default()
+        };
+        lines.push(Line::from(vec![
+            Span::styled(format!("  {} ", result.item.id), style),
+            Span::styled(result.item.display_title(), style),
+            Span::styled(
+                format!("  {}", result.item.display_state()),
+                Style::default().fg(state_color(&result.item.state)),
+            ),
+        ]));
+    }
+
+    let visible_rows = (popup_rect.height.saturating_sub(2) as usize)
+        .saturating_sub(header.len())
+        .max(1);
+    let scroll = selected_line.saturating_sub(visible_rows - 1);
+    header.extend(lines.into_iter().skip(scroll));
+
+    f.render_widget(Clear, popup_rect);
+    let popup_block = Block::default()
+        .borders(Borders::ALL)
+        .title("Search all boards")
+        .border_style(Style::default().fg(Color::LightBlue));
+    f.render_widget(Paragraph::new(header).block(popup_block), popup_rect);
+}
+
+pub fn draw_clone_prompt(f: &mut ratatui::Frame, app: &App) {
+    if !app.detail_view_state.confirming_clone {
+        return;
+    }
+    let Some(item) = app.get_selected_item() else {
+        return;
+    };
+
+    let lines = vec![
+        Line::from(format!("Create a copy of #{}? (y/n)", item.id)),
+        Line::from(Span::styled(
+            "State and assignee are not copied",
+            Style::default().fg(Color::DarkGray),
+        )),
+    ];
+
+    let area = f.area();
+    let width = 40.min(area.width);
+    let height = (lines.len() as u16 + 2).min(area.height);
+    let popup_rect = Rect {
+        x: area.x + area.width.saturating_sub(width) / 2,
+        y: area.y + area.height.saturating_sub(height) / 2,
+        width,
+        height,
+    }
+    .clamp(area);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::LightBlue));
+    f.render_widget(Clear, popup_rect);
+    f.render_widget(Paragraph::new(lines).block(block), popup_rect);
+}
+
+pub fn draw_draft_prompt(f: &mut ratatui::Frame, app: &App) {
+    if app.detail_view_state.draft_prompt.is_none() {
+        return;
+    }
+
+    let lines = vec![
+        Line::from("Restore draft? (y/n)"),
+        Line::from(Span::styled(
+            "n discards it, Esc cancels editing",
+            Style::default().fg(Color::DarkGray),
+        )),
+    ];
+
+    let area = f.area();
+    let width = 40.min(area.width);
+    let height = (lines.len() as u16 + 2).min(area.height);
+    let popup_rect = Rect {
+        x: area.x + area.width.saturating_sub(width) / 2,
+        y: area.y + area.height.saturating_sub(height) / 2,
+        width,
+        height,
+    }
+    .clamp(area);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::LightBlue));
+    f.render_widget(Clear, popup_rect);
+    f.render_widget(Paragraph::new(lines).block(block), popup_rect);
+}
+
+pub fn draw_quit_prompt(f: &mut ratatui::Frame, app: &App) {
+    if !app.confirming_quit {
+        return;
+    }
+
+    let mut lines = vec![Line::from("Quit adoboards? (y/n)")];
+    if app.has_unsaved_edits() {
+        lines.push(Line::from(Span::styled(
+            "Unsaved edits will be lost.",
+            Style::default().fg(Color::Yellow),
+        )));
+    }
+
+    let area = f.area();
+    let width = 34.min(area.width);
+    let height = (lines.len() as u16 + 2).min(area.height);
+    let popup_rect = Rect {
+        x: area.x + area.width.saturating_sub(width) / 2,
+        y: area.y + area.height.saturating_sub(height) / 2,
+        width,
+        height,
+    }
+    .clamp(area);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::LightBlue));
+    f.render_widget(Clear, popup_rect);
+    f.render_widget(Paragraph::new(lines).block(block), popup_rect);
+}
+
+pub fn draw_patch_preview(f: &mut ratatui::Frame, app: &App) {
+    let Some((preview, scroll)) = app.detail_view_state.patch_preview.as_ref() else {
+        return;
+    };
+
+    let area = f.area();
+    let width = (area.width as f32 * 0.8).round() as u16;
+    let height = (area.height as f32 * 0.8).round() as u16;
+    let popup_rect = Rect {
+        x: area.x + area.width.saturating_sub(width) / 2,
+        y: area.y + area.height.saturating_sub(height) / 2,
+        width,
+        height,
+    }
+    .clamp(area);
+
+    let popup_block = Block::default()
+        .borders(Borders::ALL)
+        .title("Patch preview (not sent)")
+        .title_bottom(" ↑/↓ scroll, any other key closes ")
+        .border_style(Style::default().fg(Color::LightBlue));
+    let paragraph = Paragraph::new(preview.as_str())
+        .wrap(Wrap { trim: false })
+        .scroll((*scroll, 0))
+        .block(popup_block);
+    f.render_widget(Clear, popup_rect);
+    f.render_widget(paragraph, popup_rect);
+}
+
+pub fn draw_command_output(f: &mut ratatui::Frame, app: &App) {
+    let Some(view) = app.command_output.as_ref() else {
+        return;
+    };
+
+    let area = f.area();
+    let width = (area.width as f32 * 0.8).round() as u16;
+    let height = (area.height as f32 * 0.8).round() as u16;
+    let popup_rect = Rect {
+        x: area.x + area.width.saturating_sub(width) / 2,
+        y: area.y + area.height.saturating_sub(height) / 2,
+        width,
+        height,
+    }
+    .clamp(area);
+
+    let popup_block = Block::default()
+        .borders(Borders::ALL)
+        .title(view.title.as_str())
+        .title_bottom(" ↑/↓ scroll, any other key closes ")
+        .border_style(Style::default().fg(Color::LightBlue));
+    let paragraph = Paragraph::new(view.text.as_str())
+        .wrap(Wrap { trim: false })
+        .scroll((view.scroll, 0))
+        .block(popup_block);
+    f.render_widget(Clear, popup_rect);
+    f.render_widget(paragraph, popup_rect);
+}
+
+/// `12s`, `5m`, `3h` or `2d`.
+fn format_elapsed(elapsed: Duration) -> String {
+    let seconds = elapsed.as_secs();
+    match seconds {
+        0..60 => format!("{}s", seconds),
+        60..3600 => format!("{}m", seconds / 60),
+        3600..86400 => format!("{}h", seconds / 3600),
+        _ => format!("{}d", seconds / 86400),
+    }
+}
+
+pub fn draw_error_log(f: &mut ratatui::Frame, app: &App) {
+    let log = &app.error_log;
+    if !log.is_open {
+        return;
+    }
+
+    let area = f.area();
+    let width = (area.width as f32 * 0.8).round() as u16;
+    let height = (area.height as f32 * 0.8).round() as u16;
+    let popup_rect = Rect {
+        x: area.x + area.width.saturating_sub(width) / 2,
+        y: area.y + area.height.saturating_sub(height) / 2,
+        width,
+        height,
+    }
+    .clamp(area);
+
+    let now = SystemTime::now();
+    let lines: Vec<Line> = if log.entries.is_empty() {
+        vec![Line::from(Span::styled(
+            "No errors",
+            Style::default().fg(Color::DarkGray),
+        ))]
+    } else {
+        log.entries
+            .iter()
+            .rev()
+            .map(|entry| {
+                let ago = now.duration_since(entry.at).unwrap_or_default();
+                Line::from(vec![
+                    Span::styled(
+                        format!("{:>4} ago  ", format_elapsed(ago)),
+                        Style::default().fg(Color::DarkGray),
+                    ),
+                    Span::styled(entry.message.as_str(), Style::default().fg(Color::Red)),
+                ])
+            })
+            .collect()
+    };
+
+    let popup_block = Block::default()
+        .borders(Borders::ALL)
+        .title(format!("Errors ({})", log.entries.len()))
+        .title_bottom(" ↑/↓ scroll, c clears, any other key closes ")
+        .border_style(Style::default().fg(Color::LightBlue));
+    let paragraph = Paragraph::new(lines)
+        .wrap(Wrap { trim: false })
+        .scroll((log.scroll, 0))
+        .block(popup_block);
+    f.render_widget(Clear, popup_rect);
+    f.render_widget(paragraph, popup_rect);
+}
+
+pub fn draw_assign_picker_popup(f: &mut ratatui::Frame, app: &App) {
+    let picker = &app.detail_view_state.assign_picker;
+    if !picker.is_open {
+        return;
+    }
+
+    let area = f.area();
+    let width = 60.min(area.width.saturating_sub(2));
+    let height = (picker.options.len().max(1) as u16)
+        .saturating_add(2)
+        .min(area.height.saturating_sub(2));
+    let popup_rect = Rect {
+        x: area.x + area.width.saturating_sub(width) / 2,
+        y: area.y + area.height.saturating_sub(height) / 2,
+        width,
+        height,
+    }
+    .clamp(area);
+    draw_picker_popup(f, picker, "Assign To", popup_rect);
+}
+
+pub fn draw_board_picker_popup(f: &mut ratatui::Frame, app: &App) {
+    let picker = &app.board_picker;
+    if !picker.is_open {
+        return;
+    }
+
+    let area = f.area();
+    let width = 60.min(area.width.saturating_sub(2));
+    let height = (picker.options.len().max(1) as u16)
+        .saturating_add(2)
+        .min(area.height.saturating_sub(2));
+    let popup_rect = Rect {
+        x: area.x + area.width.saturating_sub(width) / 2,
+        y: area.y + area.height.saturating_sub(height) / 2,
+        width,
+        height,
+    }
+    .clamp(area);
+    draw_picker_popup(f, picker, "Switch Board", popup_rect);
+}
+
+fn draw_list_picker_popup(f: &mut ratatui::Frame, app: &mut App, list_area: Rect) {
+    let Some(which) = app.list_view_state.open_picker() else {
+        return;
+    };
+    let picker = app.list_view_state.picker(which);
+
+    let content_height = picker.options.len().max(1) as u16;
+
+    let title = if which == ListPicker::Tag {
+        format!(
+            "{} (match {}, Tab to switch)",
+            which.title(),
+            if app.list_view_state.tag_match_all {
+                "all"
+            } else {
+                "any"
+            }
+        )
+    } else {
+        which.title().to_string()
+    };
+
+    if let Some(popup_rect) = calculate_list_picker_rect(f.area(), app, list_area, content_height) {
+        draw_picker_popup(f, picker, &title, popup_rect);
     }
 }
 
@@ -232,7 +882,8 @@ pub fn draw_help_popup(f: &mut ratatui::Frame, app: &App) {
         y,
         width,
         height,
-    };
+    }
+    .clamp(area);
 
     let mut lines: Vec<Line> = Vec::new();
 
@@ -269,38 +920,106 @@ pub fn draw_help_popup(f: &mut ratatui::Frame, app: &App) {
     lines.push(Line::from(vec![
         Span::raw("  Enter open item, "),
         key(&keys.hover),
-        Span::raw(" hover"),
+        Span::raw(" hover, "),
+        key(&keys.peek_title),
+        Span::raw(" full title, "),
+        key(&keys.pin_details),
+        Span::raw(" details pane, "),
+        key(&keys.toggle_summary),
+        Span::raw(" counts"),
+    ]));
+    lines.push(Line::from(vec![
+        Span::raw("  "),
+        key(&keys.search),
+        Span::raw(" search, "),
+        key(&keys.find),
+        Span::raw(" find, "),
+        key(&keys.find_next),
+        Span::raw(" / "),
+        key(&keys.find_previous),
+        Span::raw(" next / previous match"),
+    ]));
+    lines.push(Line::from(vec![
+        Span::raw("  "),
+        key(&keys.global_search),
+        Span::raw(" search all boards"),
+    ]));
+    lines.push(Line::from(vec![
+        Span::raw("  "),
+        key(&keys.work_item_type_filter),
+        Span::raw(" type filter ("),
+        key(&keys.cycle_type_filter),
+        Span::raw(" cycles one type), "),
+        key(&keys.board_column_filter),
+        Span::raw(" board column filter, "),
+        key(&keys.tag_filter),
+        Span::raw(" tag filter, "),
+        key(&keys.assigned_to_me_filter),
+        Span::raw(" assigned-to-me, "),
+        key(&keys.hide_done),
+        Span::raw(" hide done, "),
+        key(&keys.current_sprint_filter),
+        Span::raw(" current sprint, "),
+        key(&keys.bypass_filters),
+        Span::raw(" filters off / on"),
+    ]));
+    lines.push(Line::from(vec![
+        Span::raw("  "),
+        key(&keys.group_by_assignee),
+        Span::raw(" group by assignee (Enter / Space on a header collapses it), "),
+        key(&keys.collapse_all_groups),
+        Span::raw(" / "),
+        key(&keys.expand_all_groups),
+        Span::raw(" collapse / expand all groups"),
     ]));
     lines.push(Line::from(vec![
         Span::raw("  "),
-        key(&keys.search),
-        Span::raw(" search"),
+        key(&keys.toggle_pin),
+        Span::raw(" pin / unpin the item to the top of the list"),
     ]));
     lines.push(Line::from(vec![
         Span::raw("  "),
-        key(&keys.work_item_type_filter),
-        Span::raw(" type filter, "),
-        key(&keys.assigned_to_me_filter),
-        Span::raw(" assigned-to-me"),
+        key(&keys.command_palette),
+        Span::raw(" command palette"),
     ]));
     lines.push(Line::from(vec![
         Span::raw("  "),
         key(&keys.next_board),
         Span::raw(" next board / "),
         key(&keys.previous_board),
-        Span::raw(" prev board"),
+        Span::raw(" prev board, "),
+        key(&keys.pick_board),
+        Span::raw(" pick board"),
     ]));
     lines.push(Line::from(vec![
         Span::raw("  "),
         key(&keys.refresh),
         Span::raw(" refresh / "),
         key(&keys.full_refresh),
-        Span::raw(" full refresh"),
+        Span::raw(" full refresh / "),
+        key(&keys.hard_refresh),
+        Span::raw(" refresh and clear filters"),
+    ]));
+    lines.push(Line::from(vec![
+        Span::raw("  "),
+        key(&keys.open_board),
+        Span::raw(" open board in browser"),
+    ]));
+    lines.push(Line::from(vec![
+        Span::raw("  "),
+        key(&keys.copy_branch_name),
+        Span::raw(" copy branch name, "),
+        key(&keys.open_repo),
+        Span::raw(" open linked repo, "),
+        key(&keys.open_pull_request),
+        Span::raw(" open linked PR"),
     ]));
     lines.push(Line::from(vec![
         Span::raw("  "),
         key(&keys.edit_config),
-        Span::raw(" edit config"),
+        Span::raw(" edit config, "),
+        key(&keys.reload_config),
+        Span::raw(" reload config"),
     ]));
 
     lines.push(Line::from(""));
@@ -315,6 +1034,96 @@ pub fn draw_help_popup(f: &mut ratatui::Frame, app: &App) {
         key(&keys.edit_item),
         Span::raw(" edit item"),
     ]));
+    lines.push(Line::from(vec![
+        Span::raw("  "),
+        key(&keys.refresh_item),
+        Span::raw(" refresh item"),
+    ]));
+    lines.push(Line::from(vec![
+        Span::raw("  "),
+        key(&keys.reassign),
+        Span::raw(" assign to team member, "),
+        key(&keys.assign_to_me),
+        Span::raw(" assign to me"),
+    ]));
+    lines.push(Line::from(vec![
+        Span::raw("  "),
+        key(&keys.follow),
+        Span::raw(" follow / unfollow item"),
+    ]));
+    lines.push(Line::from(vec![
+        Span::raw("  "),
+        key(&keys.clone_item),
+        Span::raw(" create a copy of the item"),
+    ]));
+    lines.push(Line::from(vec![
+        Span::raw("  "),
+        key(&keys.rank_up),
+        Span::raw(" / "),
+        key(&keys.rank_down),
+        Span::raw(" move item up / down in the backlog"),
+    ]));
+    lines.push(Line::from(vec![
+        Span::raw("  "),
+        key(&keys.move_mode),
+        Span::raw(" move mode: "),
+        key(&keys.next),
+        Span::raw(" / "),
+        key(&keys.previous),
+        Span::raw(" move the item, Esc / Enter to finish"),
+    ]));
+    lines.push(Line::from(vec![
+        Span::raw("  "),
+        key(&keys.cycle_sort),
+        Span::raw(" sort key, "),
+        key(&keys.reverse_sort),
+        Span::raw(" reverse sort"),
+    ]));
+    lines.push(Line::from(vec![
+        Span::raw("  "),
+        key(&keys.narrow_list),
+        Span::raw(" / "),
+        key(&keys.widen_list),
+        Span::raw(" narrow / widen the list"),
+    ]));
+    lines.push(Line::from(vec![
+        Span::raw("  "),
+        key(&keys.collapse_description),
+        Span::raw(" / "),
+        key(&keys.collapse_acceptance_criteria),
+        Span::raw(" collapse / expand description / acceptance criteria, "),
+        key(&keys.raw_description),
+        Span::raw(" raw / cleaned description"),
+    ]));
+    lines.push(Line::from(vec![
+        Span::raw("  "),
+        key(&keys.retry_field_metadata),
+        Span::raw(" retry loading allowed values"),
+    ]));
+    lines.push(Line::from(vec![
+        Span::raw("  "),
+        key(&keys.error_log),
+        Span::raw(" recent errors"),
+    ]));
+    if app.debug {
+        lines.push(Line::from(vec![
+            Span::raw("  "),
+            key(&keys.inspect_fields),
+            Span::raw(" inspect field reference names"),
+        ]));
+    }
+
+    if !app.external_commands.is_empty() {
+        lines.push(Line::from(""));
+        lines.push(Line::from("Commands"));
+        for command in &app.external_commands {
+            lines.push(Line::from(vec![
+                Span::raw("  "),
+                key(&command.key),
+                Span::raw(format!(" {}", command.name)),
+            ]));
+        }
+    }
 
     lines.push(Line::from(""));
     lines.push(Line::from("Edit Mode"));
@@ -335,10 +1144,28 @@ pub fn draw_help_popup(f: &mut ratatui::Frame, app: &App) {
         key("Esc"),
         Span::raw(" cancel edit"),
     ]));
+    lines.push(Line::from(vec![
+        Span::raw("  "),
+        key("Ctrl-p"),
+        Span::raw(" preview patch without saving"),
+    ]));
+    lines.push(Line::from(vec![
+        Span::raw("  "),
+        key("Ctrl-d"),
+        Span::raw(" save draft locally and stop editing"),
+    ]));
+    lines.push(Line::from(vec![
+        Span::raw("  "),
+        key("Ctrl-s"),
+        Span::raw(" save and keep editing, "),
+        key("Ctrl-x"),
+        Span::raw(" save and back to list"),
+    ]));
 
     lines.push(Line::from(""));
-    lines.push(Line::from("Type Filter"));
+    lines.push(Line::from("Type / Column / Tag Filter"));
     lines.push(Line::from("  ↑/↓ move, Space/Enter toggle"));
+    lines.push(Line::from("  Tab match any / all tags"));
     lines.push(Line::from("  c clear filters, Esc close"));
 
     lines.push(Line::from(""));
@@ -361,8 +1188,197 @@ pub fn draw_help_popup(f: &mut ratatui::Frame, app: &App) {
     f.render_widget(paragraph, popup_rect);
 }
 
+/// Title of the list block from the `list_title_format` template, with the
+/// `{board}`, `{filtered}`, `{total}`, `{filters}`, `{assignee}` and `{work}`
+/// placeholders filled in.
+fn list_title(app: &App) -> String {
+    let mut filters: String = ListPicker::ALL
+        .into_iter()
+        .filter_map(|which| {
+            let active = &app.list_view_state.picker(which).active;
+            if active.is_empty() {
+                return None;
+            }
+            let separator = match which {
+                ListPicker::Tag if app.list_view_state.tag_match_all => " & ",
+                _ => ", ",
+            };
+            let joined = active.iter().cloned().collect::<Vec<_>>().join(separator);
+            Some(format!(" | {}: {}", which.label(), joined))
+        })
+        .collect();
+    if app.list_view_state.stashed_filters.is_some() {
+        filters.push_str(" | filters off");
+    }
+    if app.sort.primary != SortKey::None {
+        let arrow = if app.sort.descending { "↓" } else { "↑" };
+        filters.push_str(&format!(" | by {} {}", app.sort.primary.label(), arrow));
+    }
+    if app.current_source().is_capped(app.items.len()) {
+        filters.push_str(&format!(" | first {} items", app.items.len()));
+    }
+    if app.list_view_state.group_by_assignee {
+        filters.push_str(" | by assignee");
+    }
+    if app.list_view_state.hide_done {
+        filters.push_str(&format!(" | {} done hidden", app.hidden_done_count()));
+    }
+    if let Some(sprint) = &app.list_view_state.current_sprint {
+        filters.push_str(&format!(" | sprint {}", sprint.name));
+    }
+    if app.list_view_state.is_moving {
+        filters.push_str(" | MOVING (Esc to finish)");
+    }
+
+    let assignee = if !app.list_view_state.assigned_to_me_filter_on {
+        String::new()
+    } else if app.current_me().is_empty() {
+        ", Assigned to <name not configured>".to_string()
+    } else {
+        format!(", Assigned to {}", app.current_me())
+    };
+
+    let work = app
+        .iteration_work_rollup()
+        .map(|rollup| {
+            format!(
+                " | {}h remaining, {}h completed",
+                format_hours(rollup.remaining),
+                format_hours(rollup.completed)
+            )
+        })
+        .unwrap_or_default();
+
+    let sprint = app
+        .current_iteration
+        .as_ref()
+        .map(|iteration| sprint_summary(iteration, unix_now()))
+        .unwrap_or_default();
+
+    app.list_title_format
+        .replace("{board}", &app.current_title())
+        .replace("{filtered}", &app.get_filtered_items().len().to_string())
+        .replace("{total}", &app.items.len().to_string())
+        .replace("{filters}", &filters)
+        .replace("{assignee}", &assignee)
+        .replace("{work}", &work)
+        .replace("{sprint}", &sprint)
+}
+
+const SECONDS_PER_DAY: u64 = 24 * 60 * 60;
+
+const MONTH_NAMES: [&str; 12] = [
+    "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+];
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs())
+        .unwrap_or_default()
+}
+
+/// ` | Mar 3 – Mar 14 · 4 days left`, or empty when the iteration has no dates.
+fn sprint_summary(iteration: &ResolvedIteration, now: u64) -> String {
+    let (Some(start), Some(finish)) = (iteration.start_date, iteration.finish_date) else {
+        return String::new();
+    };
+    let today = now / SECONDS_PER_DAY;
+    let start_day = start / SECONDS_PER_DAY;
+    let finish_day = finish / SECONDS_PER_DAY;
+    let plural = |days: u64| if days == 1 { "day" } else { "days" };
+    let progress = if today < start_day {
+        let days = start_day - today;
+        format!("starts in {} {}", days, plural(days))
+    } else if today > finish_day {
+        "ended".to_string()
+    } else {
+        // The finish date is the last working day, so it still counts.
+        let days = finish_day - today + 1;
+        format!("{} {} left", days, plural(days))
+    };
+    format!(
+        " | {} – {} · {}",
+        format_day(start_day),
+        format_day(finish_day),
+        progress
+    )
+}
+
+/// `Mar 3` for a number of days since the Unix epoch.
+fn format_day(days: u64) -> String {
+    // Converts days to a proleptic Gregorian date, counting eras of 400 years
+    // from 0000-03-01 so that the leap day falls at the end of a year.
+    let days = days + 719_468;
+    let day_of_era = days % 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = if shifted_month < 10 {
+        shifted_month + 2
+    } else {
+        shifted_month - 10
+    };
+    format!("{} {}", MONTH_NAMES[month as usize], day)
+}
+
+/// Hours rounded to one decimal, without a trailing `.0`.
+fn format_hours(hours: f64) -> String {
+    let rounded = (hours * 10.0).round() / 10.0;
+    format!("{}", rounded)
+}
+
+/// Cuts `text` to at most `max_chars` characters, ending in `…` when cut;
+/// 0 keeps it whole. Counts characters, so multibyte ones are never split.
+fn truncate_with_ellipsis(text: &str, max_chars: usize) -> String {
+    if max_chars == 0 || text.chars().count() <= max_chars {
+        return text.to_string();
+    }
+    let mut truncated: String = text.chars().take(max_chars - 1).collect();
+    truncated.push('…');
+    truncated
+}
+
+fn group_header(assignee: &str, count: usize, collapsed: bool) -> ListItem<'static> {
+    let marker = if collapsed { "▸" } else { "▾" };
+    let name = if assignee.is_empty() {
+        "Unassigned"
+    } else {
+        assignee
+    };
+    ListItem::new(Line::from(Span::styled(
+        format!("{} {} ({})", marker, name, count),
+        Style::default()
+            .fg(Color::LightBlue)
+            .add_modifier(Modifier::BOLD),
+    )))
+}
+
+/// Smallest area the list and detail views are laid out in.
+const MIN_VIEW_WIDTH: u16 = 20;
+const MIN_VIEW_HEIGHT: u16 = 6;
+
+/// Shows a notice instead of a view squeezed below its minimum size, and
+/// returns whether it did.
+fn draw_too_small(f: &mut ratatui::Frame, area: Rect) -> bool {
+    if area.width >= MIN_VIEW_WIDTH && area.height >= MIN_VIEW_HEIGHT {
+        return false;
+    }
+    let notice = Paragraph::new(Line::from("Terminal too small"))
+        .style(Style::default().fg(Color::DarkGray))
+        .wrap(Wrap { trim: true });
+    f.render_widget(Clear, area);
+    f.render_widget(notice, area);
+    true
+}
+
 pub fn draw_list_view(f: &mut ratatui::Frame, app: &mut App, area: Rect) {
-    let constraints = if app.list_view_state.is_filtering {
+    if draw_too_small(f, area) {
+        return;
+    }
+    let constraints = if app.list_view_state.is_filtering || app.list_view_state.is_finding {
         [Constraint::Min(0), Constraint::Length(3)]
     } else {
         [Constraint::Min(0), Constraint::Length(0)]
@@ -373,54 +1389,102 @@ pub fn draw_list_view(f: &mut ratatui::Frame, app: &mut App, area: Rect) {
         .constraints(constraints.iter().copied())
         .split(area);
 
-    let items_to_display = app.get_filtered_items();
-
-    let list_items: Vec<ListItem> = if items_to_display.is_empty() {
-        vec![
-            ListItem::new(Line::from(
-                "No items match filters — press c in type filter to clear",
-            ))
-            .style(Style::default()),
-        ]
+    let list_area = if app.list_view_state.show_summary {
+        let parts = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(1), Constraint::Min(0)])
+            .split(chunks[0]);
+        f.render_widget(Clear, parts[0]);
+        f.render_widget(Paragraph::new(summary_line(app)), parts[0]);
+        parts[1]
     } else {
-        items_to_display
-            .iter()
-            .map(|item| {
-                let content = Line::from(format!("{}", item.title));
-                ListItem::new(content).style(Style::default())
-            })
-            .collect()
+        chunks[0]
     };
+    let (list_area, pane_area) = split_details_pane(app, list_area);
+    // Titles wrap within the list borders.
+    let title_room = list_area.width.saturating_sub(2) as usize;
 
-    let type_filter_label = if app.list_view_state.type_picker.active.is_empty() {
-        "".to_string()
-    } else {
-        let joined = app
-            .list_view_state
-            .type_picker
-            .active
-            .iter()
-            .cloned()
-            .collect::<Vec<_>>()
-            .join(", ");
-        format!(" | Types: {}", joined)
-    };
+    let rows = app.list_rows();
+    let find_query = &app.list_view_state.find_query;
 
-    let base_title = app.current_title();
-    let board_title: String = if app.list_view_state.assigned_to_me_filter_on {
-        format!(
-            "{}, Assigned to {}{}",
-            base_title,
-            if app.me.is_empty() {
-                "<name not configured>".to_string()
-            } else {
-                app.me.to_string()
-            },
-            type_filter_label,
-        )
+    let list_items: Vec<ListItem> = if rows.is_empty() {
+        vec![ListItem::new(Line::from(app.empty_list_message())).style(Style::default())]
     } else {
-        format!("{} {}", base_title, type_filter_label)
+        let now = SystemTime::now();
+        rows.iter()
+            .map(|row| {
+                let item = match row {
+                    ListRow::Item(item) => item,
+                    ListRow::Group {
+                        assignee,
+                        count,
+                        collapsed,
+                    } => return group_header(assignee, *count, *collapsed),
+                };
+                let mut spans = Vec::new();
+                if app.list_view_state.group_by_assignee {
+                    spans.push(Span::raw("  "));
+                }
+                if app.age_heatmap {
+                    let bar_style = match item.age(now) {
+                        Some(age) => Style::default().fg(age_color(age)),
+                        None => Style::default().fg(Color::DarkGray),
+                    };
+                    spans.push(Span::styled("▌ ", bar_style));
+                }
+                if let Some((change, fading)) = app.recent_change(item) {
+                    let (marker, color) = match change {
+                        ItemChange::Added => ("+ ", Color::Green),
+                        ItemChange::StateChanged => ("» ", Color::Cyan),
+                        ItemChange::Updated => ("~ ", Color::Yellow),
+                    };
+                    let color = if fading { Color::DarkGray } else { color };
+                    spans.push(Span::styled(marker, Style::default().fg(color)));
+                }
+                if app.is_pinned(item) {
+                    spans.push(Span::styled("◆ ", Style::default().fg(Color::Magenta)));
+                }
+                if let Some(board) = &item.board {
+                    spans.push(Span::styled(
+                        format!("[{}] ", board.title),
+                        Style::default().fg(Color::DarkGray),
+                    ));
+                }
+                let title = truncate_with_ellipsis(&item.display_title(), app.max_title_width);
+                let title_style = if app.is_unread(item) {
+                    spans.push(Span::styled("● ", Style::default().fg(Color::LightBlue)));
+                    Style::default().add_modifier(Modifier::BOLD)
+                } else {
+                    Style::default()
+                };
+                let style = if !find_query.is_empty() && item.matches_query(find_query) {
+                    Style::default().fg(Color::Yellow)
+                } else {
+                    Style::default()
+                };
+                if !app.wrap_titles {
+                    spans.push(Span::styled(title, title_style));
+                    return ListItem::new(Line::from(spans)).style(style);
+                }
+                // Continuation lines line up with the start of the title.
+                let indent: usize = spans.iter().map(Span::width).sum();
+                let mut title_lines = wrap_words(&title, title_room.saturating_sub(indent));
+                let first = title_lines.remove(0);
+                spans.push(Span::styled(first, title_style));
+                let mut lines = vec![Line::from(spans)];
+                lines.extend(title_lines.into_iter().map(|rest| {
+                    Line::from(vec![
+                        Span::raw(" ".repeat(indent)),
+                        Span::styled(rest, title_style),
+                    ])
+                }));
+                ListItem::new(lines).style(style)
+            })
+            .collect()
     };
+    app.list_view_state.row_heights = list_items.iter().map(|item| item.height() as u16).collect();
+
+    let board_title = list_title(app);
 
     let list = List::new(list_items)
         .block(
@@ -431,16 +1495,23 @@ pub fn draw_list_view(f: &mut ratatui::Frame, app: &mut App, area: Rect) {
         )
         .highlight_style(
             Style::default()
-                .bg(Color::DarkGray)
+                .bg(if app.list_view_state.is_moving {
+                    Color::Magenta
+                } else {
+                    Color::DarkGray
+                })
                 .add_modifier(Modifier::BOLD),
         );
 
-    let list_area = chunks[0];
     f.render_widget(Clear, list_area);
     f.render_stateful_widget(list, list_area, &mut app.list_view_state.list_state);
 
+    if let Some(pane_area) = pane_area {
+        f.render_widget(Clear, pane_area);
+        draw_details_pane(f, app, pane_area);
+    }
     draw_hover_popup(f, app, list_area);
-    draw_type_filter_popup(f, app, list_area);
+    draw_list_picker_popup(f, app, list_area);
 
     if app.list_view_state.is_filtering {
         let filter_block = Block::default()
@@ -456,14 +1527,36 @@ pub fn draw_list_view(f: &mut ratatui::Frame, app: &mut App, area: Rect) {
         let x = chunks[1].x + 2 + app.list_view_state.filter_query.len() as u16;
         let y = chunks[1].y + 1;
         f.set_cursor_position(ratatui::layout::Position::new(x, y));
+    } else if app.list_view_state.is_finding {
+        let find_query = &app.list_view_state.find_query;
+        let matches = app.find_matches().len();
+        let title = match app.list_view_state.find_match {
+            Some(index) if matches > 0 => format!("Find ({}/{})", index + 1, matches),
+            _ if find_query.is_empty() => "Find".to_string(),
+            _ => "Find (no match)".to_string(),
+        };
+        let find_block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::LightBlue))
+            .title(title);
+
+        let find_paragraph =
+            Paragraph::new(Line::from(format!(">{}", find_query))).block(find_block);
+        f.render_widget(Clear, chunks[1]);
+        f.render_widget(find_paragraph, chunks[1]);
+
+        let x = chunks[1].x + 2 + find_query.len() as u16;
+        let y = chunks[1].y + 1;
+        f.set_cursor_position(ratatui::layout::Position::new(x, y));
     }
 }
 
 pub fn draw_detail_view(f: &mut ratatui::Frame, app: &App, area: Rect) {
+    if draw_too_small(f, area) {
+        return;
+    }
     f.render_widget(Clear, area);
-    let filtered_items = app.get_filtered_items();
-    let selected_index = app.list_view_state.list_state.selected().unwrap_or(0);
-    let item = match filtered_items.get(selected_index) {
+    let item = match app.get_selected_item() {
         Some(item) => item,
         None => {
             let block = Block::default()
@@ -482,18 +1575,47 @@ pub fn draw_detail_view(f: &mut ratatui::Frame, app: &App, area: Rect) {
     let edit_state = app.detail_view_state.edit_state.as_ref();
     let is_editing = edit_state.map(|s| s.is_editing).unwrap_or(false);
 
+    let linked_lines = linked_artifact_lines(item);
     let chunks = Layout::default()
         .direction(Direction::Vertical)
-        .constraints([Constraint::Length(3), Constraint::Min(0)].as_ref())
+        .constraints(
+            [
+                Constraint::Length(3),
+                Constraint::Min(0),
+                Constraint::Length(linked_lines.len() as u16 + 2),
+            ]
+            .as_ref(),
+        )
         .split(area);
 
+    let linked_block = Block::default()
+        .title("Linked PRs")
+        .title_bottom(
+            Line::from(format!(" {} opens in browser ", app.keys.open_pull_request))
+                .style(Style::default().fg(Color::DarkGray))
+                .right_aligned(),
+        )
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::LightBlue));
+    f.render_widget(Paragraph::new(linked_lines).block(linked_block), chunks[2]);
+
     let mut fields_to_render = if let Some(state) = edit_state {
-        state.visible_fields.clone()
+        let mut fields = state.visible_fields.clone();
+        if !is_editing {
+            // Formats only apply to the read-only view; edits keep the stored value.
+            for field in fields.iter_mut().filter(|field| !field.is_modified()) {
+                if let Some(value) = item.display_field(&field.reference, &app.field_formats) {
+                    field.original_value = value.clone();
+                    field.value = value;
+                }
+            }
+        }
+        fields
     } else {
-        let source = app.current_source();
+        let (organization, project) = app.item_project(item);
         let cache_key = (
-            source.organization.clone(),
-            source.project.clone(),
+            organization.to_string(),
+            project.to_string(),
             item.work_item_type.clone(),
         );
 
@@ -503,20 +1625,17 @@ pub fn draw_detail_view(f: &mut ratatui::Frame, app: &App, area: Rect) {
                 controls
                     .iter()
                     .filter_map(|(id, label)| {
-                        item.fields.get(id).map(|value| {
-                            let allowed_values = app
-                                .field_meta_cache
-                                .get(&item.work_item_type)
-                                .and_then(|fields| {
-                                    fields
-                                        .iter()
-                                        .find(|f| f.reference_name == *id)
-                                        .map(|f| f.allowed_values.clone())
-                                });
+                        item.display_field(id, &app.field_formats).map(|value| {
+                            let allowed_values = app.type_fields(item).and_then(|fields| {
+                                fields
+                                    .iter()
+                                    .find(|f| f.reference_name == *id)
+                                    .map(|f| f.allowed_values.clone())
+                            });
                             crate::app::VisibleField::with_value(
                                 label.clone(),
                                 id.clone(),
-                                value.clone(),
+                                value,
                                 allowed_values,
                             )
                         })
@@ -529,12 +1648,41 @@ pub fn draw_detail_view(f: &mut ratatui::Frame, app: &App, area: Rect) {
     let (title_value, active_field) = if let Some(state) = edit_state {
         (state.title.clone(), state.active_field)
     } else {
-        (item.title.clone(), DetailField::Title)
+        (item.display_title(), DetailField::Title)
     };
 
+    let state_value = edit_state
+        .and_then(|state| {
+            state
+                .visible_fields
+                .iter()
+                .find(|field| field.reference == "System.State")
+                .map(|field| field.value.clone())
+        })
+        .unwrap_or_else(|| item.display_state().to_string());
+    let reason_value = edit_state
+        .and_then(|state| {
+            state
+                .visible_fields
+                .iter()
+                .find(|field| field.reference == REASON_FIELD)
+                .map(|field| field.value.clone())
+        })
+        .or_else(|| item.fields.get(REASON_FIELD).cloned())
+        .unwrap_or_default();
+    let mut state_spans = Vec::new();
+    if !reason_value.is_empty() {
+        state_spans.push(Span::styled(
+            format!(" {} ", reason_value),
+            Style::default().fg(Color::DarkGray),
+        ));
+    }
+    state_spans.push(state_pill(&state_value));
     let title_text = format!("{}: {}", item.id, title_value);
+    let title_modified = is_editing && edit_state.is_some_and(|state| state.is_title_modified());
     let title_block = Block::default()
-        .title(item.work_item_type.to_string())
+        .title(modified_label(item.display_type(), title_modified))
+        .title(Line::from(state_spans).right_aligned())
         .borders(Borders::ALL)
         .border_type(if is_editing && active_field == DetailField::Title {
             ratatui::widgets::BorderType::Thick
@@ -548,6 +1696,31 @@ pub fn draw_detail_view(f: &mut ratatui::Frame, app: &App, area: Rect) {
                 Color::LightBlue
             }),
         );
+    let metadata_hint = app.field_metadata_missing(item).then(|| {
+        Line::from(format!(
+            " allowed values unavailable — press {} to retry ",
+            app.keys.retry_field_metadata
+        ))
+        .style(Style::default().fg(Color::DarkGray))
+    });
+    let title_block = if app.is_selected_item_followed() {
+        title_block.title(
+            Line::from(" ★ following ")
+                .style(Style::default().fg(Color::Yellow))
+                .right_aligned(),
+        )
+    } else {
+        title_block
+    };
+    let title_block = if is_editing && active_field == DetailField::Title {
+        title_block.title(text_counts(&title_value))
+    } else {
+        title_block
+    };
+    let title_block = match metadata_hint.clone() {
+        Some(hint) if !is_editing => title_block.title_bottom(hint),
+        _ => title_block,
+    };
     let title_paragraph = Paragraph::new(title_text)
         .style(Style::default().add_modifier(Modifier::BOLD))
         .block(title_block);
@@ -561,6 +1734,17 @@ pub fn draw_detail_view(f: &mut ratatui::Frame, app: &App, area: Rect) {
             None,
         ));
     }
+    if app.detail_view_state.show_raw_description
+        && !is_editing
+        && let Some(raw) = item.raw_text(DESCRIPTION_FIELD)
+        && let Some(field) = fields_to_render
+            .iter_mut()
+            .find(|field| field.reference == DESCRIPTION_FIELD)
+    {
+        field.label = format!("{} (raw)", field.label);
+        field.value = raw.to_string();
+    }
+    let collapsed_fields = &app.detail_view_state.collapsed_fields;
     let constraints: Vec<Constraint> = fields_to_render
         .iter()
         .map(|field| {
@@ -570,6 +1754,8 @@ pub fn draw_detail_view(f: &mut ratatui::Frame, app: &App, area: Rect) {
                 .is_some_and(|picker| !picker.options.is_empty())
             {
                 Constraint::Length(3)
+            } else if collapsed_fields.contains(&field.reference) {
+                Constraint::Length(COLLAPSED_FIELD_LINES + 2)
             } else {
                 Constraint::Min(3)
             }
@@ -588,7 +1774,10 @@ pub fn draw_detail_view(f: &mut ratatui::Frame, app: &App, area: Rect) {
         let is_active =
             matches!(active_field, DetailField::Dynamic(active_idx) if active_idx == idx);
         let block = Block::default()
-            .title(field.label.as_str())
+            .title(modified_label(
+                &field.label,
+                is_editing && field.is_modified(),
+            ))
             .borders(Borders::ALL)
             .border_type(if is_editing && is_active {
                 ratatui::widgets::BorderType::Thick
@@ -600,31 +1789,69 @@ pub fn draw_detail_view(f: &mut ratatui::Frame, app: &App, area: Rect) {
             } else {
                 Color::LightBlue
             }));
+        let block = if is_editing && is_active {
+            block.title(text_counts(&field.value))
+        } else {
+            block
+        };
+        let block = match metadata_hint.clone() {
+            Some(hint) if is_editing && is_active => block.title_bottom(hint),
+            _ => block,
+        };
+        let text_width = area.width.saturating_sub(2) as usize;
+        let is_truncated = collapsed_fields.contains(&field.reference)
+            && field
+                .value
+                .lines()
+                .map(|line| wrap_words(line, text_width).len())
+                .sum::<usize>()
+                > COLLAPSED_FIELD_LINES as usize;
+        let collapse_key = if field.reference == ACCEPTANCE_CRITERIA_FIELD {
+            &app.keys.collapse_acceptance_criteria
+        } else {
+            &app.keys.collapse_description
+        };
+        let block = if is_truncated {
+            block.title_bottom(
+                Line::from(format!(" …more ({}) ", collapse_key))
+                    .style(Style::default().fg(Color::DarkGray))
+                    .right_aligned(),
+            )
+        } else {
+            block
+        };
 
-        let lines = vec![Line::from(Span::raw(field.value.clone()))];
-        let wrap = if field
+        let has_picker = field
             .picker
             .as_ref()
-            .is_some_and(|picker| !picker.options.is_empty())
-        {
-            Wrap { trim: true }
-        } else {
-            Wrap { trim: false }
+            .is_some_and(|picker| !picker.options.is_empty());
+        let paragraph = match app.detail_wrap {
+            _ if has_picker => {
+                Paragraph::new(Line::from(Span::raw(field.value.clone()))).wrap(Wrap { trim: true })
+            }
+            DetailWrap::Wrap => Paragraph::new(Line::from(Span::raw(field.value.clone())))
+                .wrap(Wrap { trim: false }),
+            // Editing keeps wrapping so the cursor stays where it is drawn.
+            _ if is_editing => Paragraph::new(Line::from(Span::raw(field.value.clone())))
+                .wrap(Wrap { trim: false }),
+            DetailWrap::Truncate => Paragraph::new(Text::from(field.value.clone())),
+            DetailWrap::Scroll => Paragraph::new(Text::from(field.value.clone()))
+                .scroll((0, app.detail_view_state.horizontal_scroll)),
         };
-
-        let paragraph = Paragraph::new(lines).wrap(wrap).block(block);
+        let paragraph = paragraph.block(block);
         f.render_widget(paragraph, *area);
 
-        if is_editing && is_active {
-            if let Some(picker) = field.picker.as_ref() {
-                draw_detail_picker_popup(f, picker, *area);
-            }
+        if is_editing
+            && is_active
+            && let Some(picker) = field.picker.as_ref()
+        {
+            draw_detail_picker_popup(f, picker, *area);
         }
     }
 
     let status_line = match &app.detail_view_state.save_status {
         crate::app::SaveStatus::Idle => None,
-        crate::app::SaveStatus::Saving => Some("Saving...".to_string()),
+        crate::app::SaveStatus::Saving => Some(format!("{} Saving...", spinner_frame())),
         crate::app::SaveStatus::Failed(msg) => Some(format!("Save failed: {}", msg)),
     };
 
@@ -642,26 +1869,169 @@ pub fn draw_detail_view(f: &mut ratatui::Frame, app: &App, area: Rect) {
             y: chunks[1].y.saturating_sub(3).max(chunks[0].y + 3),
             width: chunks[1].width,
             height: 3,
-        };
+        }
+        .clamp(area);
         f.render_widget(status_para, status_area);
     }
 }
 
-pub fn draw_status_screen(f: &mut ratatui::Frame, message: &str) {
+const SPINNER_FRAMES: [char; 10] = ['⠋', '⠙', '⠹', '⠸', '⠼', '⠴', '⠦', '⠧', '⠇', '⠏'];
+
+/// Spinner character for the current time, advancing every 100 ms, which is
+/// how often the app redraws while idle.
+fn spinner_frame() -> char {
+    let ticks = SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_millis() / 100)
+        .unwrap_or_default();
+    SPINNER_FRAMES[(ticks % SPINNER_FRAMES.len() as u128) as usize]
+}
+
+/// Block title marked with a yellow `*` while its value differs from the
+/// loaded one.
+fn modified_label(label: &str, modified: bool) -> Line<'static> {
+    if modified {
+        Line::from(vec![
+            Span::raw(label.to_string()),
+            Span::styled(" *", Style::default().fg(Color::Yellow)),
+        ])
+    } else {
+        Line::from(label.to_string())
+    }
+}
+
+/// Characters and words of the field being edited. Characters are counted
+/// as Unicode scalar values, not bytes.
+fn text_counts(text: &str) -> Line<'static> {
+    Line::from(format!(
+        "({} chars, {} words)",
+        text.chars().count(),
+        text.split_whitespace().count()
+    ))
+    .style(Style::default().fg(Color::DarkGray))
+}
+
+/// Most linked pull requests and commits listed in the detail view.
+const MAX_LINKED_ARTIFACT_LINES: usize = 4;
+
+fn linked_artifact_lines(item: &WorkItem) -> Vec<Line<'static>> {
+    if item.linked_artifacts.is_empty() {
+        return vec![Line::from("No linked PRs").style(Style::default().fg(Color::DarkGray))];
+    }
+    let mut lines: Vec<Line> = item
+        .linked_artifacts
+        .iter()
+        .take(MAX_LINKED_ARTIFACT_LINES)
+        .map(|artifact| Line::from(artifact.label()))
+        .collect();
+    let hidden = item
+        .linked_artifacts
+        .len()
+        .saturating_sub(MAX_LINKED_ARTIFACT_LINES);
+    if hidden > 0 {
+        lines.push(
+            Line::from(format!("… {} more", hidden)).style(Style::default().fg(Color::DarkGray)),
+        );
+    }
+    lines
+}
+
+pub fn draw_status_message(f: &mut ratatui::Frame, app: &App) {
+    let Some(message) = app.active_status_message() else {
+        return;
+    };
+
+    let area = f.area();
+    let width = (message.chars().count() as u16)
+        .saturating_add(4)
+        .min(area.width);
+    let height = 3.min(area.height);
+    let popup_rect = Rect {
+        x: area.x + area.width.saturating_sub(width),
+        y: area.y + area.height.saturating_sub(height),
+        width,
+        height,
+    }
+    .clamp(area);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::LightBlue));
+    let paragraph = Paragraph::new(Line::from(message.to_string())).block(block);
+    f.render_widget(Clear, popup_rect);
+    f.render_widget(paragraph, popup_rect);
+}
+
+pub fn draw_error_screen(
+    f: &mut ratatui::Frame,
+    report: &ErrorReport,
+    show_details: bool,
+    keys: &KeysConfig,
+) {
+    if !show_details {
+        draw_status_screen(
+            f,
+            &format!(
+                "Failed to load data. {} (press '{}' to retry, '{}' for details)",
+                report.summary, keys.refresh, keys.error_details
+            ),
+            &[],
+        );
+        return;
+    }
+
+    let area = f.area();
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Color::LightBlue)
+        .title("Failed to load data");
+
+    let mut text = vec![
+        Line::from(Span::styled(
+            report.summary.clone(),
+            Style::default().add_modifier(Modifier::BOLD),
+        )),
+        Line::from(""),
+    ];
+    text.extend(
+        report
+            .details
+            .lines()
+            .map(|line| Line::from(line.to_string())),
+    );
+    text.push(Line::from(""));
+    text.push(Line::from(format!(
+        "Press '{}' to retry, '{}' to hide details, 'q' to quit.",
+        keys.refresh, keys.error_details
+    )));
+
+    let paragraph = Paragraph::new(text).wrap(Wrap { trim: false }).block(block);
+    f.render_widget(Clear, area);
+    f.render_widget(paragraph, area);
+}
+
+pub fn draw_status_screen(f: &mut ratatui::Frame, message: &str, details: &[String]) {
     let area = f.area();
     let block = Block::default()
         .borders(Borders::ALL)
         .border_style(Color::LightBlue)
         .title("Status");
-    let text = vec![
+    let mut text = vec![
         Line::from(""),
         Line::from(Span::styled(
             message,
             Style::default().add_modifier(Modifier::BOLD),
         )),
-        Line::from(""),
-        Line::from("Press 'q' to quit."),
     ];
+    for detail in details {
+        text.push(Line::from(Span::styled(
+            detail.as_str(),
+            Style::default().fg(Color::DarkGray),
+        )));
+    }
+    text.push(Line::from(""));
+    text.push(Line::from("Press 'q' to quit."));
+    let height = text.len() as u16 + 2;
 
     let paragraph = Paragraph::new(text)
         .alignment(ratatui::layout::Alignment::Center)
@@ -673,7 +2043,7 @@ pub fn draw_status_screen(f: &mut ratatui::Frame, message: &str) {
         .constraints(
             [
                 Constraint::Percentage(40),
-                Constraint::Length(5),
+                Constraint::Length(height),
                 Constraint::Percentage(40),
             ]
             .as_ref(),
@@ -683,3 +2053,77 @@ pub fn draw_status_screen(f: &mut ratatui::Frame, message: &str) {
     f.render_widget(Clear, chunks[1]);
     f.render_widget(paragraph, chunks[1]);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::AppConfig;
+
+    const SIZES: [u16; 7] = [0, 1, 2, 3, 4, 6, 10];
+
+    fn frames() -> impl Iterator<Item = Rect> {
+        SIZES.into_iter().flat_map(|width| {
+            SIZES.into_iter().map(move |height| Rect {
+                x: 0,
+                y: 0,
+                width,
+                height,
+            })
+        })
+    }
+
+    fn app_with_selected_row(row: usize) -> App {
+        let mut app = App::new(AppConfig::default(), None);
+        app.list_view_state.list_state.select(Some(row));
+        app
+    }
+
+    fn assert_inside(rect: Rect, frame: Rect) {
+        assert!(
+            rect.x >= frame.x
+                && rect.y >= frame.y
+                && rect.right() <= frame.right()
+                && rect.bottom() <= frame.bottom(),
+            "{rect:?} is not inside {frame:?}"
+        );
+    }
+
+    #[test]
+    fn popup_rect_stays_inside_tiny_frames() {
+        for row in [0, 3, 50] {
+            let app = app_with_selected_row(row);
+            for frame in frames() {
+                for content_lines in [0, 4, 40] {
+                    let rect = calculate_popup_rect(frame, &app, frame, content_lines, 60);
+                    assert_inside(rect.unwrap(), frame);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn list_picker_rect_stays_inside_tiny_frames() {
+        for row in [0, 3, 50] {
+            let app = app_with_selected_row(row);
+            for frame in frames() {
+                for content_lines in [0, 4, 40] {
+                    let rect = calculate_list_picker_rect(frame, &app, frame, content_lines);
+                    assert_inside(rect.unwrap(), frame);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn detail_picker_rect_stays_inside_tiny_frames() {
+        for frame in frames() {
+            for content_lines in [0, 4, 40] {
+                let field_area = Rect { height: 1, ..frame };
+                match calculate_detail_picker_rect(frame, field_area, content_lines) {
+                    Some(rect) => assert_inside(rect, frame),
+                    None => assert!(frame.width < 3 || frame.height < 3),
+                }
+            }
+        }
+    }
+}